@@ -2,7 +2,7 @@
 
 use crate::error::MachineError;
 use crate::machine::MachineW;
-use crate::types::MachineWState;
+use crate::types::{MachineWState, CallFrame};
 
 impl MachineW {
     pub fn step_instruction(&mut self) -> Result<(), MachineError> {
@@ -29,6 +29,43 @@ impl MachineW {
         Ok(())
     }
 
+    /// one instruction, a subroutine call is executed until it returns
+    pub fn step_over(&mut self) -> Result<(), MachineError> {
+        if !self.is_running {
+            return Ok(());
+        }
+
+        let depth = self.call_stack.len();
+        self.step_instruction()?;
+        self.run_while_deeper_than(depth)
+    }
+
+    /// runs until the current subroutine returns
+    pub fn step_out(&mut self) -> Result<(), MachineError> {
+        if self.call_stack.is_empty() {
+            return self.run_until_halt_or_breakpoint();
+        }
+
+        let depth = self.call_stack.len() - 1;
+        self.run_while_deeper_than(depth)
+    }
+
+    fn run_while_deeper_than(&mut self, depth: usize) -> Result<(), MachineError> {
+        while self.is_running && self.call_stack.len() > depth {
+            if self.breakpoints.contains(&self.l) {
+                return Err(MachineError::BreakpointHit { address: self.l });
+            }
+
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// active subroutine calls, innermost last
+    pub fn get_call_stack(&self) -> &[CallFrame] {
+        &self.call_stack
+    }
+
     /// snapshot
     pub fn get_current_state(&self) -> MachineWState {
         MachineWState {
@@ -37,6 +74,7 @@ impl MachineW {
             ad: self.ad,
             kod: self.kod,
             ws: self.ws,
+            flags: self.flags,
             is_running: self.is_running,
             interrupts_enabled: self.interrupts_enabled,
             interrupt_mask: self.interrupt_mask,
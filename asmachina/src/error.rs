@@ -22,4 +22,24 @@ pub enum MachineError {
     InvalidAddressingMode { mode: u8 },
     #[error("Invalid register number: {register} (must be 0-7)")]
     InvalidRegister { register: u8 },
+    #[error("Opcode {opcode} at address {address} is not part of instruction set profile '{profile}'")]
+    InstructionNotInProfile { opcode: u8, address: u16, profile: String },
+}
+
+impl MachineError {
+    /// stable code of the kind of error, E05xx
+    pub fn code(&self) -> &'static str {
+        match self {
+            MachineError::AddressOutOfBounds { .. } => "E0501",
+            MachineError::InvalidOpcode { .. } => "E0502",
+            MachineError::StackOverflow => "E0503",
+            MachineError::StackUnderflow => "E0504",
+            MachineError::DivisionByZero { .. } => "E0505",
+            MachineError::IoError { .. } => "E0506",
+            MachineError::BreakpointHit { .. } => "E0507",
+            MachineError::InvalidAddressingMode { .. } => "E0508",
+            MachineError::InvalidRegister { .. } => "E0509",
+            MachineError::InstructionNotInProfile { .. } => "E0510",
+        }
+    }
 }
@@ -1,37 +1,31 @@
 use crate::error::MachineError;
 use crate::machine::MachineW;
-use asmodeus_shared::{extract_addressing_mode, addressing_mode_bits};
 
 impl MachineW {
     /// DOD - Add: (AK) + ((AD)) → AK
     pub(crate) fn execute_dod(&mut self) -> Result<(), MachineError> {
-        let raw_instruction = self.memory[self.l.wrapping_sub(1) as usize];
-        let addressing_mode_bits = extract_addressing_mode(raw_instruction);
+        let raw_instruction = self.current_instruction();
+        let operand = self.read_operand(raw_instruction)?;
         
-        let operand = if addressing_mode_bits == addressing_mode_bits::IMMEDIATE {
-            self.ad
-        } else {
-            let effective_address = self.resolve_effective_address(raw_instruction)?;
-            self.read_memory(effective_address)?
-        };
+        let (result, carry) = self.ak.overflowing_add(operand);
+        let (_, overflow) = (self.ak as i16).overflowing_add(operand as i16);
         
-        self.ak = self.ak.wrapping_add(operand);
+        self.ak = result;
+        self.update_arithmetic_flags(carry, overflow);
         Ok(())
     }
 
     /// ODE - Subtract: (AK) - ((AD)) → AK  
     pub(crate) fn execute_ode(&mut self) -> Result<(), MachineError> {
-        let raw_instruction = self.memory[self.l.wrapping_sub(1) as usize];
-        let addressing_mode_bits = extract_addressing_mode(raw_instruction);
+        let raw_instruction = self.current_instruction();
+        let operand = self.read_operand(raw_instruction)?;
         
-        let operand = if addressing_mode_bits == addressing_mode_bits::IMMEDIATE {
-            self.ad
-        } else {
-            let effective_address = self.resolve_effective_address(raw_instruction)?;
-            self.read_memory(effective_address)?
-        };
+        // carry holds the borrow, so it is set when (AK) < ((AD)) as unsigned values
+        let (result, carry) = self.ak.overflowing_sub(operand);
+        let (_, overflow) = (self.ak as i16).overflowing_sub(operand as i16);
         
-        self.ak = self.ak.wrapping_sub(operand);
+        self.ak = result;
+        self.update_arithmetic_flags(carry, overflow);
         Ok(())
     }
 }
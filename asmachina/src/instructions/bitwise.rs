@@ -0,0 +1,68 @@
+use crate::error::MachineError;
+use crate::machine::MachineW;
+use asmodeus_shared::shift_operations;
+
+impl MachineW {
+    /// AND - Bitwise AND: (AK) & ((AD)) → AK
+    pub(crate) fn execute_and(&mut self) -> Result<(), MachineError> {
+        let raw_instruction = self.current_instruction();
+        let operand = self.read_operand(raw_instruction)?;
+        self.ak &= operand;
+        self.update_result_flags();
+        Ok(())
+    }
+
+    /// OR - Bitwise OR: (AK) | ((AD)) → AK
+    pub(crate) fn execute_or(&mut self) -> Result<(), MachineError> {
+        let raw_instruction = self.current_instruction();
+        let operand = self.read_operand(raw_instruction)?;
+        self.ak |= operand;
+        self.update_result_flags();
+        Ok(())
+    }
+
+    /// XOR - Bitwise XOR: (AK) ^ ((AD)) → AK
+    pub(crate) fn execute_xor(&mut self) -> Result<(), MachineError> {
+        let raw_instruction = self.current_instruction();
+        let operand = self.read_operand(raw_instruction)?;
+        self.ak ^= operand;
+        self.update_result_flags();
+        Ok(())
+    }
+
+    /// SHL/SAL - Shift Left: (AK) << n → AK
+    /// SHR - Logical Shift Right: (AK) >> n → AK
+    /// SAR - Arithmetic Shift Right: (AK) >> n → AK (sign preserved)
+    /// NOT - Bitwise Negation: !(AK) → AK
+    pub(crate) fn execute_prz(&mut self) -> Result<(), MachineError> {
+        let operation = ((self.ad >> 4) & 0b1111) as u8;
+        let count = (self.ad & 0b1111) as u32;
+
+        // C receives the last bit shifted out
+        match operation {
+            shift_operations::SHL => {
+                if count > 0 {
+                    self.update_carry_flag((self.ak >> (16 - count)) & 1 != 0);
+                }
+                self.ak <<= count;
+            }
+            shift_operations::SHR => {
+                if count > 0 {
+                    self.update_carry_flag((self.ak >> (count - 1)) & 1 != 0);
+                }
+                self.ak >>= count;
+            }
+            shift_operations::SAR => {
+                if count > 0 {
+                    self.update_carry_flag((self.ak >> (count - 1)) & 1 != 0);
+                }
+                self.ak = ((self.ak as i16) >> count) as u16;
+            }
+            shift_operations::NOT => self.ak = !self.ak,
+            _ => return Err(MachineError::InvalidOpcode { opcode: self.kod }),
+        }
+
+        self.update_result_flags();
+        Ok(())
+    }
+}
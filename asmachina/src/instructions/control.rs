@@ -1,10 +1,11 @@
 use crate::error::MachineError;
 use crate::machine::MachineW;
+use crate::types::flags;
 
 impl MachineW {
     /// SOB - Unconditional jump: (AD) → L
     pub(crate) fn execute_sob(&mut self) -> Result<(), MachineError> {
-        self.l = self.ad & 0b0000011111111111;
+        self.l = self.jump_target()?;
         Ok(())
     }
 
@@ -12,7 +13,7 @@ impl MachineW {
     pub(crate) fn execute_som(&mut self) -> Result<(), MachineError> {
         // checking if the AK value is negative 
         if (self.ak & 0x8000) != 0 {
-            self.l = self.ad & 0b0000011111111111;
+            self.l = self.jump_target()?;
         }
         Ok(())
     }
@@ -21,7 +22,40 @@ impl MachineW {
     pub(crate) fn execute_soz(&mut self) -> Result<(), MachineError> {
         // AK == zero
         if self.ak == 0 {
-            self.l = self.ad & 0b0000011111111111;
+            self.l = self.jump_target()?;
+        }
+        Ok(())
+    }
+
+    /// SOC - Conditional jump: (AD) → L, when carry flag is set
+    pub(crate) fn execute_soc(&mut self) -> Result<(), MachineError> {
+        if self.flag(flags::C) {
+            self.l = self.jump_target()?;
+        }
+        Ok(())
+    }
+
+    /// SOV - Conditional jump: (AD) → L, when overflow flag is set
+    pub(crate) fn execute_sov(&mut self) -> Result<(), MachineError> {
+        if self.flag(flags::V) {
+            self.l = self.jump_target()?;
+        }
+        Ok(())
+    }
+
+    /// SOP - Conditional jump: (AD) → L, when (AK) > 0
+    pub(crate) fn execute_sop(&mut self) -> Result<(), MachineError> {
+        // AK positive: sign bit clear and not zero
+        if (self.ak & 0x8000) == 0 && self.ak != 0 {
+            self.l = self.jump_target()?;
+        }
+        Ok(())
+    }
+
+    /// SNZ - Conditional jump: (AD) → L, when (AK) ≠ 0
+    pub(crate) fn execute_snz(&mut self) -> Result<(), MachineError> {
+        if self.ak != 0 {
+            self.l = self.jump_target()?;
         }
         Ok(())
     }
@@ -5,12 +5,26 @@ mod stack;
 mod interrupts;
 mod io;
 mod extended_arithmetics;
+mod registers;
+mod subroutines;
+mod bitwise;
 
 use crate::error::MachineError;
 use crate::machine::MachineW;
+use asmodeus_shared::Opcode;
 
 impl MachineW {
     pub(crate) fn execute_instruction(&mut self) -> Result<(), MachineError> {
+        if let Some(opcode) = Opcode::from_u8(self.kod) {
+            if !self.isa_profile.allows(opcode) {
+                return Err(MachineError::InstructionNotInProfile {
+                    opcode: self.kod,
+                    address: self.instruction_address(),
+                    profile: self.isa_profile.to_string(),
+                });
+            }
+        }
+
         match self.kod {
             // Arithmetic instructions
             0b00001 => self.execute_dod(), // DOD - Add
@@ -24,6 +38,10 @@ impl MachineW {
             0b00101 => self.execute_sob(), // SOB - Unconditional jump
             0b00110 => self.execute_som(), // SOM - Conditional jump
             0b10000 => self.execute_soz(), // SOZ - Jump if zero
+            0b11011 => self.execute_soc(), // SOC - Jump if carry
+            0b11100 => self.execute_sov(), // SOV - Jump if overflow
+            0b11101 => self.execute_sop(), // SOP - Jump if positive
+            0b11110 => self.execute_snz(), // SNZ - Jump if not zero
             0b00111 => self.execute_stp(), // STP - Stop
             
             // Stack instructions
@@ -44,6 +62,19 @@ impl MachineW {
             0b10001 => self.execute_mno(), // MNO - Multiply
             0b10010 => self.execute_dzi(), // DZI - Divide
             0b10011 => self.execute_mod(), // MOD - Modulo
+
+            // Register instructions
+            0b10100 => self.execute_rej(), // INR/DER - Increment/decrement register
+
+            // Subroutine instructions
+            0b10101 => self.execute_wyw(), // WYW - Call subroutine
+            0b10110 => self.execute_pow(), // POW - Return from subroutine
+
+            // Bitwise instructions
+            0b10111 => self.execute_and(), // AND - Bitwise AND
+            0b11000 => self.execute_or(),  // OR - Bitwise OR
+            0b11001 => self.execute_xor(), // XOR - Bitwise XOR
+            0b11010 => self.execute_prz(), // SHL/SHR/SAR/NOT - Shifts and negation
             
             // Invalid opcode
             _ => Err(MachineError::InvalidOpcode { opcode: self.kod }),
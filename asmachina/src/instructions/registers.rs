@@ -0,0 +1,24 @@
+use crate::error::MachineError;
+use crate::machine::MachineW;
+use asmodeus_shared::register_operations;
+
+impl MachineW {
+    /// INR - Increment Register: (Rn) + 1 → Rn
+    /// DER - Decrement Register: (Rn) - 1 → Rn
+    pub(crate) fn execute_rej(&mut self) -> Result<(), MachineError> {
+        let operation = ((self.ad >> 3) & 0b11111) as u8;
+        let register_num = (self.ad & 0b111) as usize;
+
+        match operation {
+            register_operations::INR => {
+                self.registers[register_num] = self.registers[register_num].wrapping_add(1);
+            }
+            register_operations::DER => {
+                self.registers[register_num] = self.registers[register_num].wrapping_sub(1);
+            }
+            _ => return Err(MachineError::InvalidOpcode { opcode: self.kod }),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use crate::error::MachineError;
+use crate::machine::MachineW;
+use crate::types::CallFrame;
+
+impl MachineW {
+    /// WYW - Call subroutine: (L) → stack, (AD) → L
+    pub(crate) fn execute_wyw(&mut self) -> Result<(), MachineError> {
+        let target = self.jump_target()?;
+        self.push_to_stack(self.l)?;
+        self.call_stack.push(CallFrame {
+            target,
+            return_address: self.l,
+        });
+        self.l = target;
+        Ok(())
+    }
+
+    /// POW - Return from subroutine: stack → L
+    pub(crate) fn execute_pow(&mut self) -> Result<(), MachineError> {
+        self.l = self.pop_from_stack()? & 0b0000011111111111;
+        self.call_stack.pop();
+        Ok(())
+    }
+}
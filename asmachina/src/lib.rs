@@ -14,8 +14,9 @@ mod instructions;
 mod debug;
 
 pub use error::MachineError;
-pub use types::{AddressingMode, MachineWState};
+pub use types::{AddressingMode, MachineWState, CallFrame, flags};
 pub use machine::MachineW;
+pub use asmodeus_shared::IsaProfile;
 
 #[cfg(test)]
 mod tests {
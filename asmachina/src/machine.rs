@@ -1,6 +1,8 @@
 //! core emulator implementation
 
 use std::collections::HashSet;
+use crate::types::CallFrame;
+use asmodeus_shared::IsaProfile;
 
 mod memory;
 mod stack;
@@ -8,9 +10,10 @@ mod interrupts;
 mod io;
 mod addressing;
 mod execution;
+mod flags;
 
 /// - operating memory (PaO): 2048 words, 16-bit each
-/// - registers: AK (accumulator), L (instruction counter), AD (address), KOD (opcode), WS (stack pointer)
+/// - registers: AK (accumulator), L (instruction counter), AD (address), KOD (opcode), WS (stack pointer), F (flags)
 /// - execution state and control flags
 /// - interrupt handling system
 
@@ -24,8 +27,11 @@ pub struct MachineW {
     
     /// L - instruction counter (11-bit, range 0-2047)
     pub l: u16,
+
+    /// address of the first word of the last fetched instruction (far prefix included)
+    pub instruction_start: u16,
     
-    /// AD - address register (11-bit)
+    /// AD - address register (8-bit argument, 16-bit for far instructions)
     pub ad: u16,
     
     /// KOD - opcode register (5-bit)
@@ -34,6 +40,9 @@ pub struct MachineW {
     /// WS - stack pointer (11-bit, grows downward, initialized to 2047)
     pub ws: u16,
     
+    /// F - status flags (Z, N, C, V), set by arithmetic and bitwise instructions
+    pub flags: u16,
+    
     /// execution control flag
     pub is_running: bool,
     
@@ -53,7 +62,13 @@ pub struct MachineW {
 
     pub breakpoints: HashSet<u16>,
 
+    /// active subroutine calls (for debugging: backtrace, step over)
+    pub call_stack: Vec<CallFrame>,
+
     pub interactive_mode: bool,
+
+    /// instruction set profile, opcodes outside of it are rejected at run time
+    pub isa_profile: IsaProfile,
 }
 
 impl Default for MachineW {
@@ -68,9 +83,11 @@ impl MachineW {
             memory: vec![0; 2048],
             ak: 0,
             l: 0,
+            instruction_start: 0,
             ad: 0,
             kod: 0,
             ws: 2047, // stack pointer initialized to top of memory (grows downward)
+            flags: 0,
             is_running: false,
             interrupts_enabled: true,
             interrupt_mask: 0,
@@ -79,7 +96,9 @@ impl MachineW {
             input_buffer: Vec::new(),
             output_buffer: Vec::new(),
             breakpoints: HashSet::new(),
+            call_stack: Vec::new(),
             interactive_mode: false,
+            isa_profile: IsaProfile::FULL,
         }
     }
 
@@ -87,13 +106,19 @@ impl MachineW {
         self.interactive_mode = enabled;
     }
 
+    pub fn set_isa_profile(&mut self, profile: IsaProfile) {
+        self.isa_profile = profile;
+    }
+
     pub fn reset(&mut self) {
         self.memory.fill(0);
         self.ak = 0;
         self.l = 0;
+        self.instruction_start = 0;
         self.ad = 0;
         self.kod = 0;
         self.ws = 2047;
+        self.flags = 0;
         self.is_running = false;
         self.interrupts_enabled = true;
         self.interrupt_mask = 0;
@@ -102,5 +127,6 @@ impl MachineW {
         self.input_buffer.clear();
         self.output_buffer.clear();
         self.breakpoints.clear();
+        self.call_stack.clear();
     }
 }
@@ -1,5 +1,5 @@
 use crate::error::MachineError;
-use asmodeus_shared::{addressing_mode_bits, extract_addressing_mode};
+use asmodeus_shared::{addressing_mode_bits, decode_base_register, extract_addressing_mode, relative_target};
 use super::MachineW;
 
 impl MachineW {
@@ -57,11 +57,7 @@ impl MachineW {
                 Ok(address)
             }
             bits if bits == addressing_mode_bits::BASE_REGISTER => {
-                let register_num = (self.ad >> 6) & 0b111;
-                let offset = self.ad & 0b111111;
-                if register_num > 7 {
-                    return Err(MachineError::InvalidRegister { register: register_num as u8 });
-                }
+                let (register_num, offset) = decode_base_register(self.ad);
                 let base_address = self.registers[register_num as usize];
                 let effective_address = base_address.wrapping_add(offset);
                 if effective_address >= 2048 {
@@ -70,23 +66,59 @@ impl MachineW {
                 Ok(effective_address)
             }
             bits if bits == addressing_mode_bits::RELATIVE => {
-                let offset = if (self.ad & 0x80) != 0 {
-                    (self.ad | 0xFF00) as i16
-                } else {
-                    self.ad as i16
-                };
-                
-                let target_address = (self.l as i32) + (offset as i32);
-                if target_address < 0 || target_address >= 2048 {
-                    return Err(MachineError::AddressOutOfBounds { 
-                        address: target_address.max(0) as u16 
-                    });
-                }
-                Ok(target_address as u16)
+                // L already points past the instruction
+                relative_target(self.l, self.ad).ok_or_else(|| MachineError::AddressOutOfBounds {
+                    address: (self.l as i32 + self.ad as u8 as i8 as i32).max(0) as u16,
+                })
             }
             _ => {
                 Err(MachineError::InvalidAddressingMode { mode: addressing_mode_bits })
             }
         }
     }
+
+    /// address of the instruction being executed, L already points past it and wraps from 2047 to 0
+    pub(crate) fn instruction_address(&self) -> u16 {
+        self.l.wrapping_sub(1) & 0b0000011111111111
+    }
+
+    /// word of the instruction being executed, holding its addressing mode
+    pub(crate) fn current_instruction(&self) -> u16 {
+        self.memory[self.instruction_address() as usize]
+    }
+
+    /// where a jump or call goes: the argument, or L plus the offset in relative mode
+    pub(crate) fn jump_target(&self) -> Result<u16, MachineError> {
+        let raw_instruction = self.current_instruction();
+        if extract_addressing_mode(raw_instruction) == addressing_mode_bits::RELATIVE {
+            self.resolve_effective_address(raw_instruction)
+        } else {
+            Ok(self.ad & 0b0000011111111111)
+        }
+    }
+
+    /// reads an operand value: immediate argument, register contents or memory word
+    pub(crate) fn read_operand(&self, instruction_code: u16) -> Result<u16, MachineError> {
+        match extract_addressing_mode(instruction_code) {
+            bits if bits == addressing_mode_bits::IMMEDIATE => Ok(self.ad),
+            bits if bits == addressing_mode_bits::REGISTER => {
+                Ok(self.registers[(self.ad & 0b111) as usize])
+            }
+            _ => {
+                let effective_address = self.resolve_effective_address(instruction_code)?;
+                self.read_memory(effective_address)
+            }
+        }
+    }
+
+    /// writes an operand value: register contents or memory word
+    pub(crate) fn write_operand(&mut self, instruction_code: u16, value: u16) -> Result<(), MachineError> {
+        if extract_addressing_mode(instruction_code) == addressing_mode_bits::REGISTER {
+            self.registers[(self.ad & 0b111) as usize] = value;
+            Ok(())
+        } else {
+            let effective_address = self.resolve_effective_address(instruction_code)?;
+            self.write_memory(effective_address, value)
+        }
+    }
 }
@@ -1,13 +1,29 @@
 use crate::error::MachineError;
-use asmodeus_shared::{extract_opcode, extract_argument};
+use asmodeus_shared::{extract_opcode, extract_argument, is_far_prefix, decode_far_instruction, Opcode};
 use super::MachineW;
 
 impl MachineW {
     fn fetch_and_decode(&mut self) -> Result<(), MachineError> {
         let raw_instruction = self.read_memory(self.l)?;
         
-        self.kod = extract_opcode(raw_instruction);
-        self.ad = extract_argument(raw_instruction);
+        if is_far_prefix(raw_instruction) {
+            if !self.isa_profile.allows(Opcode::DAL) {
+                return Err(MachineError::InstructionNotInProfile {
+                    opcode: Opcode::DAL as u8,
+                    address: self.l,
+                    profile: self.isa_profile.to_string(),
+                });
+            }
+            // far form: prefix holds the high argument byte, L moves on to the instruction word
+            self.l = (self.l + 1) & 0b0000011111111111;
+            let instruction = self.read_memory(self.l)?;
+            let (kod, _, ad) = decode_far_instruction(raw_instruction, instruction);
+            self.kod = kod;
+            self.ad = ad;
+        } else {
+            self.kod = extract_opcode(raw_instruction);
+            self.ad = extract_argument(raw_instruction);
+        }
         
         Ok(())
     }
@@ -31,6 +47,7 @@ impl MachineW {
             return Ok(()); // dont execute normal instruction this cycle
         }
 
+        self.instruction_start = self.l;
         self.fetch_and_decode()?;
         
         // increment instruction counter (before execution, may be overridden by jumps)
@@ -0,0 +1,35 @@
+use crate::types::flags;
+use super::MachineW;
+
+impl MachineW {
+    /// if given flag bit is set in F
+    pub fn flag(&self, flag: u16) -> bool {
+        (self.flags & flag) != 0
+    }
+
+    fn set_flag(&mut self, flag: u16, value: bool) {
+        if value {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+    }
+
+    /// Z and N from current AK, C and V left untouched
+    pub(crate) fn update_result_flags(&mut self) {
+        self.set_flag(flags::Z, self.ak == 0);
+        self.set_flag(flags::N, (self.ak & 0x8000) != 0);
+    }
+
+    /// Z and N from current AK, C and V from the operation
+    pub(crate) fn update_arithmetic_flags(&mut self, carry: bool, overflow: bool) {
+        self.update_result_flags();
+        self.set_flag(flags::C, carry);
+        self.set_flag(flags::V, overflow);
+    }
+
+    /// C only (shifts)
+    pub(crate) fn update_carry_flag(&mut self, carry: bool) {
+        self.set_flag(flags::C, carry);
+    }
+}
@@ -20,6 +20,23 @@ pub enum AddressingMode {
     Relative(i16),
 }
 
+/// subroutine call in progress (return address itself lives on the stack)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CallFrame {
+    /// subroutine entry point
+    pub target: u16,
+    /// address the matching POW returns to
+    pub return_address: u16,
+}
+
+/// bits of the F (status flags) register
+pub mod flags {
+    pub const Z: u16 = 0b0001; // Zero
+    pub const N: u16 = 0b0010; // Negative
+    pub const C: u16 = 0b0100; // Carry (borrow for subtraction)
+    pub const V: u16 = 0b1000; // Overflow (signed)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct MachineWState {
     pub ak: u16,
@@ -27,6 +44,7 @@ pub struct MachineWState {
     pub ad: u16,
     pub kod: u8,
     pub ws: u16,
+    pub flags: u16,
     pub is_running: bool,
     pub interrupts_enabled: bool,
     pub interrupt_mask: u16,
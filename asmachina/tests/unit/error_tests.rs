@@ -1,4 +1,4 @@
-use asmachina::{MachineW};
+use asmachina::{MachineW, MachineError, IsaProfile};
 
 #[test]
 fn test_arithmetic_overflow() {
@@ -23,3 +23,27 @@ fn test_arithmetic_overflow() {
     assert!(machine.step().is_ok());
     assert_eq!(machine.ak, 0xFFFF); // wrapped to max value
 }
+
+#[test]
+fn test_instruction_not_in_profile() {
+    let mut machine = MachineW::new();
+    
+    machine.set_isa_profile(IsaProfile::CLASSIC_W);
+    machine.memory[0] = (0b00100 << 11) | (0b001 << 8) | 6; // POB #6
+    machine.memory[1] = (0b10001 << 11) | (0b001 << 8) | 7; // MNO #7
+    machine.memory[2] = 0b00111 << 11;                      // STP
+    
+    let result = machine.run();
+    assert_eq!(result, Err(MachineError::InstructionNotInProfile {
+        opcode: 0b10001,
+        address: 1,
+        profile: "classic-w".to_string(),
+    }));
+    assert_eq!(machine.ak, 6);
+    
+    // same program runs under a profile with muldiv
+    machine.set_isa_profile("classic-w+muldiv".parse().unwrap());
+    machine.l = 0;
+    assert!(machine.run().is_ok());
+    assert_eq!(machine.ak, 42);
+}
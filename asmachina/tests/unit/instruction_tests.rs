@@ -1,4 +1,4 @@
-use asmachina::{MachineW, MachineError};
+use asmachina::{MachineW, MachineError, flags};
 
 #[test]
 fn test_dod_instruction() {
@@ -141,8 +141,9 @@ fn test_soz_instruction_no_jump_when_negative() {
 fn test_invalid_opcode() {
     let mut machine = MachineW::new();
     
-    let invalid_instruction = (0b11111 << 11) | 0; // opcode 31 (invalid)
+    let invalid_instruction = 0b11111 << 11; // far prefix...
     machine.memory[0] = invalid_instruction;
+    machine.memory[1] = invalid_instruction; // ...followed by another prefix (invalid)
     machine.l = 0;
     machine.is_running = true;
     
@@ -177,3 +178,366 @@ fn test_conditional_jump_negative() {
     assert!(machine.step().is_ok());
     assert_eq!(machine.l, 200); // L set to target address (jump taken)
 }
+
+#[test]
+fn test_pob_register_instruction() {
+    let mut machine = MachineW::new();
+    
+    machine.registers[3] = 1234;
+    machine.memory[1234] = 99;
+    let pob_instruction = (0b00100 << 11) | (0b100 << 8) | 3; // POB R3
+    machine.memory[0] = pob_instruction;
+    machine.l = 0;
+    machine.is_running = true;
+    
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.ak, 1234); // register contents, not memory at that address
+}
+
+#[test]
+fn test_lad_register_instruction() {
+    let mut machine = MachineW::new();
+    
+    machine.ak = 42;
+    let lad_instruction = (0b00011 << 11) | (0b100 << 8) | 5; // ŁAD R5
+    machine.memory[0] = lad_instruction;
+    machine.l = 0;
+    machine.is_running = true;
+    
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.registers[5], 42);
+    assert_eq!(machine.memory[42], 0); // memory untouched
+}
+
+#[test]
+fn test_register_indirect_load_store() {
+    let mut machine = MachineW::new();
+    
+    machine.registers[1] = 100;
+    machine.memory[100] = 7;
+    machine.memory[0] = (0b00100 << 11) | (0b101 << 8) | 1; // POB [R1]
+    machine.memory[1] = (0b00001 << 11) | (0b001 << 8) | 1; // DOD #1
+    machine.memory[2] = (0b00011 << 11) | (0b101 << 8) | 1; // ŁAD [R1]
+    machine.memory[3] = 0b00111 << 11;                      // STP
+    
+    assert!(machine.run().is_ok());
+    assert_eq!(machine.memory[100], 8);
+}
+
+#[test]
+fn test_base_register_load_store() {
+    let mut machine = MachineW::new();
+    
+    // register in bits 11-13 of the argument, address below it
+    let load = (2 << 11) | 1000;
+    let store = (2 << 11) | 1001;
+    machine.registers[2] = 3;
+    machine.memory[1003] = 7;
+    machine.memory[0] = (0b11111 << 11) | (load >> 8);                  // far prefix
+    machine.memory[1] = (0b00100 << 11) | (0b110 << 8) | (load & 0xFF);  // POB R2[1000]
+    machine.memory[2] = (0b11111 << 11) | (store >> 8);                 // far prefix
+    machine.memory[3] = (0b00011 << 11) | (0b110 << 8) | (store & 0xFF); // ŁAD R2[1001]
+    machine.memory[4] = (0b00001 << 11) | (0b110 << 8) | 5;              // DOD R0[5]
+    machine.memory[5] = 0b00111 << 11;                                   // STP
+    
+    assert!(machine.run().is_ok());
+    assert_eq!(machine.memory[1004], 7);
+    assert_eq!(machine.ak, 7 + machine.memory[5]);
+}
+
+#[test]
+fn test_dod_register_instruction() {
+    let mut machine = MachineW::new();
+    
+    machine.ak = 10;
+    machine.registers[2] = 32;
+    let dod_instruction = (0b00001 << 11) | (0b100 << 8) | 2; // DOD R2
+    machine.memory[0] = dod_instruction;
+    machine.l = 0;
+    machine.is_running = true;
+    
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.ak, 42);
+}
+
+#[test]
+fn test_inr_der_instructions() {
+    let mut machine = MachineW::new();
+    
+    machine.registers[1] = 10;
+    machine.memory[0] = (0b10100 << 11) | (0b100 << 8) | 1;              // INR R1
+    machine.memory[1] = (0b10100 << 11) | (0b100 << 8) | (0b00001 << 3); // DER R0
+    machine.memory[2] = 0b00111 << 11;                                   // STP
+    
+    assert!(machine.run().is_ok());
+    assert_eq!(machine.registers[1], 11);
+    assert_eq!(machine.registers[0], 0xFFFF); // wrapping
+    assert_eq!(machine.ak, 0); // AK untouched
+}
+
+#[test]
+fn test_invalid_register_operation() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[0] = (0b10100 << 11) | (0b100 << 8) | (0b11111 << 3) | 1;
+    machine.l = 0;
+    machine.is_running = true;
+    
+    let result = machine.step();
+    assert!(matches!(result, Err(MachineError::InvalidOpcode { opcode: 0b10100 })));
+}
+
+#[test]
+fn test_far_direct_instructions() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[1500] = 40;
+    machine.memory[0] = (0b11111 << 11) | (1500 >> 8);      // far prefix
+    machine.memory[1] = (0b00100 << 11) | (1500 & 0xFF);    // POB 1500
+    machine.memory[2] = (0b11111 << 11) | (2000 >> 8);      // far prefix
+    machine.memory[3] = (0b00011 << 11) | (2000 & 0xFF);    // ŁAD 2000
+    machine.memory[4] = (0b11111 << 11) | (1000 >> 8);      // far prefix
+    machine.memory[5] = (0b00101 << 11) | (1000 & 0xFF);    // SOB 1000
+    machine.memory[1000] = (0b00001 << 11) | (0b001 << 8) | 2; // DOD #2
+    machine.memory[1001] = 0b00111 << 11;                      // STP
+    machine.l = 0;
+    machine.is_running = true;
+    
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.ak, 40);
+    assert_eq!(machine.l, 2); // both words consumed
+    
+    assert!(machine.run().is_ok());
+    assert_eq!(machine.memory[2000], 40);
+    assert_eq!(machine.ak, 42);
+}
+
+#[test]
+fn test_far_immediate_instruction() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[0] = (0b11111 << 11) | (1000 >> 8);                  // far prefix
+    machine.memory[1] = (0b00100 << 11) | (0b001 << 8) | (1000 & 0xFF); // POB #1000
+    machine.l = 0;
+    machine.is_running = true;
+    
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.ak, 1000);
+}
+
+#[test]
+fn test_bitwise_instructions() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[100] = 0x00F0;
+    machine.memory[0] = (0b10111 << 11) | (0b001 << 8) | 0x3C; // AND #0x3C
+    machine.memory[1] = (0b11000 << 11) | 100;                 // OR 100
+    machine.memory[2] = (0b11001 << 11) | (0b001 << 8) | 0xFF; // XOR #0xFF
+    machine.memory[3] = 0b00111 << 11;                         // STP
+    machine.ak = 0x1234;
+    
+    assert!(machine.run().is_ok());
+    // 0x1234 & 0x3C = 0x34, | 0xF0 = 0xF4, ^ 0xFF = 0x0B
+    assert_eq!(machine.ak, 0x000B);
+}
+
+#[test]
+fn test_bitwise_instructions_at_last_address() {
+    // L wraps to 0 while the instruction at 2047 executes
+    for (opcode, expected) in [(0b10111u16, 0x0004), (0b11000, 0x123C), (0b11001, 0x1238)] {
+        let mut machine = MachineW::new();
+        machine.memory[2047] = (opcode << 11) | (0b001 << 8) | 0x0C; // AND/OR/XOR #0x0C
+        machine.memory[0] = 0b00111 << 11;                           // STP
+        machine.l = 2047;
+        machine.ak = 0x1234;
+
+        assert!(machine.run().is_ok());
+        assert_eq!(machine.ak, expected);
+    }
+}
+
+#[test]
+fn test_shift_instructions() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[0] = (0b11010 << 11) | (0b001 << 8) | 4;               // SHL #4
+    machine.memory[1] = (0b11010 << 11) | (0b001 << 8) | (0b0001 << 4) | 8; // SHR #8
+    machine.memory[2] = 0b00111 << 11;                                     // STP
+    machine.ak = 0x0123;
+    
+    assert!(machine.run().is_ok());
+    assert_eq!(machine.ak, 0x0012);
+    
+    machine.reset();
+    machine.memory[0] = (0b11010 << 11) | (0b001 << 8) | (0b0010 << 4) | 2; // SAR #2
+    machine.memory[1] = 0b00111 << 11;                                     // STP
+    machine.ak = 0xFFF0; // -16
+    
+    assert!(machine.run().is_ok());
+    assert_eq!(machine.ak, 0xFFFC); // -4
+}
+
+#[test]
+fn test_not_instruction() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[0] = (0b11010 << 11) | (0b0011 << 4); // NOT
+    machine.ak = 0x0F0F;
+    machine.l = 0;
+    machine.is_running = true;
+    
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.ak, 0xF0F0);
+}
+
+#[test]
+fn test_dod_sets_carry_and_overflow_flags() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[0] = (0b00001 << 11) | (0b001 << 8) | 1; // DOD #1
+    machine.l = 0;
+    machine.is_running = true;
+    
+    // unsigned wraparound: carry and zero, no signed overflow
+    machine.ak = 0xFFFF;
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.ak, 0);
+    assert!(machine.flag(flags::Z));
+    assert!(machine.flag(flags::C));
+    assert!(!machine.flag(flags::V));
+    assert!(!machine.flag(flags::N));
+    
+    // signed overflow: 32767 + 1
+    machine.ak = 0x7FFF;
+    machine.l = 0;
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.ak, 0x8000);
+    assert!(machine.flag(flags::V));
+    assert!(machine.flag(flags::N));
+    assert!(!machine.flag(flags::C));
+    assert!(!machine.flag(flags::Z));
+}
+
+#[test]
+fn test_ode_sets_borrow_flag() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[0] = (0b00010 << 11) | (0b001 << 8) | 5; // ODE #5
+    machine.ak = 3;
+    machine.l = 0;
+    machine.is_running = true;
+    
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.ak, 0xFFFE);
+    assert!(machine.flag(flags::C)); // 3 < 5 unsigned
+    assert!(machine.flag(flags::N));
+    assert!(!machine.flag(flags::V));
+    
+    // signed overflow: -32768 - 5
+    machine.ak = 0x8000;
+    machine.l = 0;
+    assert!(machine.step().is_ok());
+    assert!(machine.flag(flags::V));
+    assert!(!machine.flag(flags::C));
+}
+
+#[test]
+fn test_flag_conditional_jumps() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[0] = (0b11011 << 11) | 100; // SOC 100
+    machine.memory[1] = (0b11100 << 11) | 200; // SOV 200
+    machine.l = 0;
+    machine.is_running = true;
+    
+    // no flags: both fall through
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.l, 1);
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.l, 2);
+    
+    machine.flags = flags::C | flags::V;
+    machine.l = 0;
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.l, 100);
+    machine.l = 1;
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.l, 200);
+}
+
+#[test]
+fn test_sop_snz_instructions() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[0] = (0b11101 << 11) | 100; // SOP 100
+    machine.memory[1] = (0b11110 << 11) | 200; // SNZ 200
+    machine.is_running = true;
+    
+    for (ak, sop_target, snz_target) in [(5, 100, 200), (0, 1, 2), (0xFFFF, 1, 200)] {
+        machine.ak = ak;
+        machine.l = 0;
+        assert!(machine.step().is_ok());
+        assert_eq!(machine.l, sop_target, "SOP with AK = {}", ak);
+        machine.l = 1;
+        assert!(machine.step().is_ok());
+        assert_eq!(machine.l, snz_target, "SNZ with AK = {}", ak);
+    }
+}
+
+#[test]
+fn test_relative_operands_count_from_next_word() {
+    let mut machine = MachineW::new();
+    
+    // POB +2 at address 10: offset 2 from L = 11
+    machine.memory[10] = (0b00100 << 11) | (0b111 << 8) | 2;
+    machine.memory[13] = 77;
+    // DOD -4 at address 11: offset 0xFC from L = 12
+    machine.memory[11] = (0b00001 << 11) | (0b111 << 8) | 0xFC;
+    machine.memory[8] = 3;
+    
+    machine.l = 10;
+    machine.is_running = true;
+    
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.ak, 77);
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.ak, 80);
+}
+
+#[test]
+fn test_relative_jumps_and_calls() {
+    let mut machine = MachineW::new();
+    
+    // SOB -6 at address 20 goes to 21 - 6 = 15
+    machine.memory[20] = (0b00101 << 11) | (0b111 << 8) | 0xFA;
+    // WYW +4 at address 15 calls 20 from 16
+    machine.memory[15] = (0b10101 << 11) | (0b111 << 8) | 4;
+    // SOZ +1 skips over the next word when AK = 0
+    machine.memory[30] = (0b10000 << 11) | (0b111 << 8) | 1;
+    
+    machine.l = 20;
+    machine.is_running = true;
+    
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.l, 15);
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.l, 20);
+    assert_eq!(machine.memory[machine.ws as usize + 1], 16);
+    
+    machine.l = 30;
+    machine.ak = 0;
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.l, 32);
+}
+
+#[test]
+fn test_relative_target_outside_memory() {
+    let mut machine = MachineW::new();
+    
+    // SOB -3 at address 0 would go to -2
+    machine.memory[0] = (0b00101 << 11) | (0b111 << 8) | 0xFD;
+    machine.l = 0;
+    machine.is_running = true;
+    
+    assert!(matches!(machine.step(), Err(MachineError::AddressOutOfBounds { .. })));
+}
@@ -16,6 +16,7 @@ fn test_machine_initialization() {
     assert_eq!(machine.ad, 0);
     assert_eq!(machine.kod, 0);
     assert_eq!(machine.ws, 2047);
+    assert_eq!(machine.flags, 0);
     assert_eq!(machine.is_running, false);
     assert_eq!(machine.interrupts_enabled, true);
     assert_eq!(machine.interrupt_mask, 0);
@@ -66,6 +67,7 @@ fn test_reset_functionality() {
     machine.interrupts_enabled = false;
     machine.interrupt_mask = 0xFF;
     machine.registers[0] = 999;
+    machine.flags = 0b1111;
     machine.input_buffer.push(123);
     machine.output_buffer.push(456);
     
@@ -82,6 +84,7 @@ fn test_reset_functionality() {
     assert_eq!(machine.interrupt_mask, 0);
     assert_eq!(machine.pending_interrupt, None);
     assert_eq!(machine.registers[0], 0);
+    assert_eq!(machine.flags, 0);
     assert_eq!(machine.memory[50], 0);
     assert!(machine.input_buffer.is_empty());
     assert!(machine.output_buffer.is_empty());
@@ -83,3 +83,61 @@ fn test_stack_underflow() {
     let result = machine.step();
     assert!(matches!(result, Err(MachineError::StackUnderflow)));
 }
+
+#[test]
+fn test_call_and_return() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[0] = (0b10101 << 11) | 10;                 // WYW 10
+    machine.memory[1] = 0b00111 << 11;                        // STP
+    machine.memory[10] = (0b00100 << 11) | (0b001 << 8) | 42; // POB #42
+    machine.memory[11] = 0b10110 << 11;                       // POW
+    machine.l = 0;
+    machine.is_running = true;
+    
+    let initial_ws = machine.ws;
+    assert!(machine.step().is_ok());
+    assert_eq!(machine.l, 10); // jumped into subroutine
+    assert_eq!(machine.memory[initial_ws as usize], 1); // return address pushed
+    assert_eq!(machine.ws, initial_ws - 1);
+    assert_eq!(machine.call_stack.len(), 1);
+    
+    assert!(machine.run().is_ok());
+    assert_eq!(machine.ak, 42);
+    assert_eq!(machine.l, 2); // returned and stopped at STP
+    assert_eq!(machine.ws, initial_ws); // stack balanced
+    assert!(machine.call_stack.is_empty());
+}
+
+#[test]
+fn test_recursive_calls() {
+    let mut machine = MachineW::new();
+    
+    // countdown(AK): if AK == 0 return; AK -= 1; countdown(AK); R0++
+    machine.memory[0] = (0b00100 << 11) | (0b001 << 8) | 3;  // POB #3
+    machine.memory[1] = (0b10101 << 11) | 10;                // WYW 10
+    machine.memory[2] = 0b00111 << 11;                       // STP
+    machine.memory[10] = (0b10000 << 11) | 15;               // SOZ 15
+    machine.memory[11] = (0b00010 << 11) | (0b001 << 8) | 1; // ODE #1
+    machine.memory[12] = (0b10101 << 11) | 10;               // WYW 10
+    machine.memory[13] = (0b10100 << 11) | (0b100 << 8);     // INR R0
+    machine.memory[14] = 0b10110 << 11;                      // POW
+    machine.memory[15] = 0b10110 << 11;                      // POW
+    
+    let initial_ws = machine.ws;
+    assert!(machine.run().is_ok());
+    assert_eq!(machine.registers[0], 3);
+    assert_eq!(machine.ws, initial_ws);
+}
+
+#[test]
+fn test_return_with_empty_stack() {
+    let mut machine = MachineW::new();
+    
+    machine.memory[0] = 0b10110 << 11; // POW
+    machine.l = 0;
+    machine.is_running = true;
+    
+    let result = machine.step();
+    assert!(matches!(result, Err(MachineError::StackUnderflow)));
+}
@@ -2,6 +2,8 @@
 
 use crate::error::DisassemblerError;
 use crate::core::Disassembler;
+use crate::instruction::decode_at;
+use asmodeus_shared::{addressing_mode_bits, decode_base_register, extract_addressing_mode, relative_target};
 use std::collections::HashSet;
 
 pub struct AdvancedDisassembler {
@@ -35,9 +37,18 @@ impl AdvancedDisassembler {
             analyzed.insert(address);
             code_addresses.insert(address);
 
-            let word = machine_code[address as usize];
-            let opcode = (word >> 11) & 0b11111;
-            let argument = word & 0b0000011111111111;
+            let (opcode, argument, length) = decode_at(machine_code, address as usize);
+            let next = address + length as u16;
+            if length == 2 {
+                // far instruction word belongs to the prefix
+                code_addresses.insert(address + 1);
+            }
+            // where a jump or call goes: relative ones count from the next word, R1[tab] goes to tab
+            let argument = match extract_addressing_mode(machine_code[next as usize - 1]) {
+                addressing_mode_bits::RELATIVE => relative_target(next, argument).unwrap_or(u16::MAX),
+                addressing_mode_bits::BASE_REGISTER => decode_base_register(argument).1,
+                _ => argument,
+            };
 
             match opcode {
                 0b00101 => {
@@ -53,15 +64,15 @@ impl AdvancedDisassembler {
                         to_analyze.push(argument);
                     }
                     // continue to next instruction too
-                    to_analyze.push(address + 1);
+                    to_analyze.push(next);
                 }
-                0b10000 => {
-                    // SOZ - conditional jump
+                0b10000 | 0b11011..=0b11110 => {
+                    // SOZ, SOC, SOV, SOP, SNZ - conditional jumps
                     if (argument as usize) < machine_code.len() {
                         to_analyze.push(argument);
                     }
                     // continue to next instruction too
-                    to_analyze.push(address + 1);
+                    to_analyze.push(next);
                 }
                 0b00111 => {
                     // STP - stop
@@ -69,9 +80,19 @@ impl AdvancedDisassembler {
                 0b01101 => {
                     // PWR - return from interrupt dont continue normally
                 }
-                0b00001..=0b00110 | 0b01000..=0b01100 | 0b01110..=0b01111 | 0b10001..=0b10011 => {
-                    // other valid instructions (including extended set) - continue to next
-                    to_analyze.push(address + 1);
+                0b10101 => {
+                    // WYW - call, analyze subroutine and continue after the call
+                    if (argument as usize) < machine_code.len() {
+                        to_analyze.push(argument);
+                    }
+                    to_analyze.push(next);
+                }
+                0b10110 => {
+                    // POW - return from subroutine
+                }
+                0b00001..=0b00110 | 0b01000..=0b01100 | 0b01110..=0b01111 | 0b10001..=0b10100 | 0b10111..=0b11010 => {
+                    // other valid instructions (including extended set, register operations and bitwise set) - continue to next
+                    to_analyze.push(next);
                 }
                 _ => {
                     // invalid opcode - might be data, dont continue
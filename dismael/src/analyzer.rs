@@ -1,4 +1,6 @@
 use crate::error::DisassemblerError;
+use crate::instruction::decode_at;
+use asmodeus_shared::{addressing_mode_bits, decode_base_register, extract_addressing_mode, relative_target, Opcode};
 use std::collections::HashSet;
 
 pub struct CodeAnalyzer {
@@ -21,18 +23,28 @@ impl CodeAnalyzer {
     }
 
     pub fn analyze_code(&mut self, machine_code: &[u16]) -> Result<(), DisassemblerError> {
-        for (i, &word) in machine_code.iter().enumerate() {
+        let mut i = 0;
+
+        while i < machine_code.len() {
             let address = i as u16;
-            let opcode = (word >> 11) & 0b11111;
-            let argument = word & 0b0000011111111111;
+            let (opcode, argument, length) = decode_at(machine_code, i);
+            // relative operands count from the word after the instruction, ones outside memory point nowhere;
+            // R1[tab] refers to tab
+            let argument = match extract_addressing_mode(machine_code[i + length - 1]) {
+                addressing_mode_bits::RELATIVE => relative_target(address + length as u16, argument).unwrap_or(u16::MAX),
+                addressing_mode_bits::BASE_REGISTER => decode_base_register(argument).1,
+                _ => argument,
+            };
 
             match opcode {
-                0b00101 | 0b00110 | 0b10000 => {
-                    // SOB (unconditional jump), SOM (conditional jump), or SOZ (conditional jump)
-                    self.jump_targets.insert(argument);
+                0b00101 | 0b00110 | 0b10000 | 0b11011..=0b11110 | 0b10101 => {
+                    // SOB (unconditional jump), SOM/SOZ/SOC/SOV/SOP/SNZ (conditional jumps) or WYW (call)
+                    if self.is_valid_address(argument) {
+                        self.jump_targets.insert(argument);
+                    }
                 }
-                0b00001..=0b00111 | 0b01000..=0b01111 | 0b10001..=0b10011 => {
-                    // valid instruction opcodes (including extended set)
+                0b00001..=0b00111 | 0b01000..=0b01111 | 0b10001..=0b11010 => {
+                    // valid instruction opcodes (including extended set, register operations, subroutines and bitwise set)
                     // only mark as data if it looks like data access
                     if self.is_valid_address(argument) && 
                        self.could_be_data_reference(opcode as u8) &&
@@ -42,7 +54,7 @@ impl CodeAnalyzer {
                             // target looks like an instruction or data
                             let target_word = machine_code[argument as usize];
                             let target_opcode = (target_word >> 11) & 0b11111;
-                            if target_opcode > 0b10011 {
+                            if Opcode::from_u8(target_opcode as u8).is_none() {
                                 // invalid opcode, likely data
                                 self.data_addresses.insert(argument);
                             }
@@ -54,13 +66,16 @@ impl CodeAnalyzer {
                     self.data_addresses.insert(address);
                 }
             }
+
+            // invalid far pairs are data, valid ones are decoded together
+            i += if self.data_addresses.contains(&address) { 1 } else { length };
         }
 
         Ok(())
     }
 
     pub fn could_be_data_reference(&self, opcode: u8) -> bool {
-        matches!(opcode, 0b00001..=0b00100 | 0b01100 | 0b01110..=0b01111 | 0b10001..=0b10011)
+        matches!(opcode, 0b00001..=0b00100 | 0b01100 | 0b01110..=0b01111 | 0b10001..=0b10011 | 0b10111..=0b11001)
     }
 
     pub fn is_valid_address(&self, address: u16) -> bool {
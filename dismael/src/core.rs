@@ -4,21 +4,38 @@ use crate::error::DisassemblerError;
 use crate::analyzer::CodeAnalyzer;
 use crate::formatter::InstructionFormatter;
 use crate::instruction::InstructionDecoder;
+use asmodeus_shared::{is_far_prefix, IsaProfile, Opcode};
+use hephasm::DebugInfo;
 use std::collections::HashMap;
 
 pub struct Disassembler {
     analyzer: CodeAnalyzer,
     labels: HashMap<u16, String>,
+    profile: IsaProfile,
+    /// label names and source lines written by the assembler
+    debug_info: Option<DebugInfo>,
 }
 
 impl Disassembler {
     pub fn new() -> Self {
+        Self::new_with_profile(IsaProfile::FULL)
+    }
+
+    /// instructions outside of the profile are flagged with a comment
+    pub fn new_with_profile(profile: IsaProfile) -> Self {
         Self {
             analyzer: CodeAnalyzer::new(),
             labels: HashMap::new(),
+            profile,
+            debug_info: None,
         }
     }
 
+    /// labels keep their source names, instructions are annotated with the line they came from
+    pub fn set_debug_info(&mut self, debug_info: DebugInfo) {
+        self.debug_info = Some(debug_info);
+    }
+
     /// disassembles binary machine code into readable assembly
     pub fn disassemble(&mut self, machine_code: &[u16]) -> Result<Vec<String>, DisassemblerError> {
         if machine_code.is_empty() {
@@ -34,6 +51,13 @@ impl Disassembler {
             self.analyzer.get_jump_targets(), 
             self.analyzer.get_data_addresses()
         );
+        if let Some(debug_info) = &self.debug_info {
+            for address in 0..machine_code.len() as u16 {
+                if let Some(name) = debug_info.label_at(address) {
+                    self.labels.insert(address, name.to_string());
+                }
+            }
+        }
 
         // third pass: disassemble instructions
         let decoder = InstructionDecoder::new(self.labels.clone());
@@ -44,7 +68,25 @@ impl Disassembler {
         while i < machine_code.len() {
             let address = i as u16;
             let is_data = self.analyzer.is_data_address(address);
-            let instruction = decoder.disassemble_instruction(address, machine_code[i], is_data)?;
+            let mut instruction = if !is_data && is_far_prefix(machine_code[i]) && i + 1 < machine_code.len() {
+                decoder.disassemble_far_instruction(address, machine_code[i], machine_code[i + 1])?
+            } else {
+                decoder.disassemble_instruction(address, machine_code[i], is_data)?
+            };
+
+            // the far form needs the DAL prefix to be in the profile as well
+            let outside_profile = Opcode::from_u8(instruction.opcode).is_some_and(|opcode| !self.profile.allows(opcode))
+                || (instruction.far_prefix.is_some() && !self.profile.allows(Opcode::DAL));
+            if !instruction.is_data && outside_profile {
+                instruction.comment = Some(format!("not in ISA profile {}", self.profile));
+            }
+
+            if let Some(source) = self.source_comment(address) {
+                instruction.comment = Some(match instruction.comment.take() {
+                    Some(comment) => format!("{}; {}", source, comment),
+                    None => source,
+                });
+            }
 
             // add label if this address is a target
             if let Some(label_line) = output_formatter.format_label(address) {
@@ -54,12 +96,23 @@ impl Disassembler {
             let line = output_formatter.format_instruction(&instruction);
             result.push(line);
 
-            i += 1;
+            i += if instruction.far_prefix.is_some() { 2 } else { 1 };
         }
 
         Ok(result)
     }
 
+    /// main.asmod:12:5, with the macro calls the word came from
+    fn source_comment(&self, address: u16) -> Option<String> {
+        let debug_info = self.debug_info.as_ref()?;
+        let location = debug_info.location(address)?;
+        let mut comment = location.to_string();
+        if let Some(expansion) = location.expansion {
+            comment.push_str(&format!(" in macro {}", debug_info.macro_trace(expansion)));
+        }
+        Some(comment)
+    }
+
     // public access methods for analyzer
     pub fn get_analyzer_mut(&mut self) -> &mut CodeAnalyzer {
         &mut self.analyzer
@@ -11,3 +11,14 @@ pub enum DisassemblerError {
     #[error("Address out of bounds: {address}")]
     AddressOutOfBounds { address: u16 },
 }
+
+impl DisassemblerError {
+    /// stable code of the kind of error, E06xx
+    pub fn code(&self) -> &'static str {
+        match self {
+            DisassemblerError::InvalidOpcode { .. } => "E0601",
+            DisassemblerError::EmptyCode => "E0602",
+            DisassemblerError::AddressOutOfBounds { .. } => "E0603",
+        }
+    }
+}
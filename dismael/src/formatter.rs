@@ -16,7 +16,10 @@ impl InstructionFormatter {
         let mut result = String::new();
         
         // for debugging and reference
-        result.push_str(&format!("    ; {:04X}: {:04X}\n", instruction.address, instruction.raw_value));
+        match instruction.far_prefix {
+            Some(prefix) => result.push_str(&format!("    ; {:04X}: {:04X} {:04X}\n", instruction.address, prefix, instruction.raw_value)),
+            None => result.push_str(&format!("    ; {:04X}: {:04X}\n", instruction.address, instruction.raw_value)),
+        }
         result.push_str("    ");
         result.push_str(&instruction.mnemonic);
         
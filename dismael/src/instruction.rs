@@ -2,6 +2,7 @@
 
 use crate::error::DisassemblerError;
 use crate::types::{DisassembledInstruction, AddressingMode};
+use asmodeus_shared::{extract_argument, is_far_prefix, decode_far_instruction, decode_base_register, relative_target, register_operations, shift_operations};
 use std::collections::HashMap;
 
 pub struct InstructionDecoder {
@@ -14,36 +15,99 @@ impl InstructionDecoder {
     }
 
     pub fn disassemble_instruction(&self, address: u16, word: u16, is_data: bool) -> Result<DisassembledInstruction, DisassemblerError> {
+        self.decode(address, address + 1, word, extract_argument(word), is_data)
+    }
+
+    /// far form: prefix with the high argument byte followed by the instruction word
+    pub fn disassemble_far_instruction(&self, address: u16, prefix: u16, word: u16) -> Result<DisassembledInstruction, DisassemblerError> {
+        let (_, _, argument) = decode_far_instruction(prefix, word);
+        let mut instruction = self.decode(address, address + 2, word, argument, false)?;
+        instruction.far_prefix = Some(prefix);
+        Ok(instruction)
+    }
+
+    /// next_address is where L points while the instruction executes, relative operands count from it
+    fn decode(&self, address: u16, next_address: u16, word: u16, argument: u16, is_data: bool) -> Result<DisassembledInstruction, DisassemblerError> {
         let opcode = (word >> 11) & 0b11111;
-        let argument = word & 0b0000011111111111;
 
         let (mnemonic, operand, is_data_result) = if is_data {
             ("RST".to_string(), Some(word.to_string()), true)
         } else {
             let addressing_mode = self.detect_addressing_mode(word);
+            // jumps and calls take a plain address or an offset
+            let jump_mode = match addressing_mode {
+                AddressingMode::Relative => AddressingMode::Relative,
+                _ => AddressingMode::Direct,
+            };
             
             match opcode {
-                0b00001 => ("DOD".to_string(), Some(self.format_operand(argument, addressing_mode)), false),
-                0b00010 => ("ODE".to_string(), Some(self.format_operand(argument, addressing_mode)), false),
-                0b00011 => ("ŁAD".to_string(), Some(self.format_operand(argument, addressing_mode)), false),
-                0b00100 => ("POB".to_string(), Some(self.format_operand(argument, addressing_mode)), false),
-                0b00101 => ("SOB".to_string(), Some(self.format_operand(argument, AddressingMode::Direct)), false),
-                0b00110 => ("SOM".to_string(), Some(self.format_operand(argument, AddressingMode::Direct)), false),
-                0b10000 => ("SOZ".to_string(), Some(self.format_operand(argument, AddressingMode::Direct)), false),
+                0b00001 => ("DOD".to_string(), Some(self.format_operand(address, next_address, argument, addressing_mode)), false),
+                0b00010 => ("ODE".to_string(), Some(self.format_operand(address, next_address, argument, addressing_mode)), false),
+                0b00011 => ("ŁAD".to_string(), Some(self.format_operand(address, next_address, argument, addressing_mode)), false),
+                0b00100 => ("POB".to_string(), Some(self.format_operand(address, next_address, argument, addressing_mode)), false),
+                0b00101 => ("SOB".to_string(), Some(self.format_operand(address, next_address, argument, jump_mode)), false),
+                0b00110 => ("SOM".to_string(), Some(self.format_operand(address, next_address, argument, jump_mode)), false),
+                0b10000 => ("SOZ".to_string(), Some(self.format_operand(address, next_address, argument, jump_mode)), false),
+                0b11011 => ("SOC".to_string(), Some(self.format_operand(address, next_address, argument, jump_mode)), false),
+                0b11100 => ("SOV".to_string(), Some(self.format_operand(address, next_address, argument, jump_mode)), false),
+                0b11101 => ("SOP".to_string(), Some(self.format_operand(address, next_address, argument, jump_mode)), false),
+                0b11110 => ("SNZ".to_string(), Some(self.format_operand(address, next_address, argument, jump_mode)), false),
                 0b00111 => ("STP".to_string(), None, false),
                 0b01000 => ("DNS".to_string(), None, false),
                 0b01001 => ("PZS".to_string(), None, false),
                 0b01010 => ("SDP".to_string(), None, false),
                 0b01011 => ("CZM".to_string(), None, false),
-                0b01100 => ("MSK".to_string(), Some(self.format_operand(argument, AddressingMode::Direct)), false),
+                0b01100 => ("MSK".to_string(), Some(self.format_operand(address, next_address, argument, AddressingMode::Direct)), false),
                 0b01101 => ("PWR".to_string(), None, false),
-                0b01110 => ("WEJSCIE".to_string(), Some(self.format_operand(argument, AddressingMode::Direct)), false),
-                0b01111 => ("WYJSCIE".to_string(), Some(self.format_operand(argument, AddressingMode::Direct)), false),
+                // written without an operand in source
+                0b01110 => ("WEJSCIE".to_string(), None, false),
+                0b01111 => ("WYJSCIE".to_string(), None, false),
 
                 // extended instructions
-                0b10001 => ("MNO".to_string(), Some(self.format_operand(argument, addressing_mode)), false),
-                0b10010 => ("DZI".to_string(), Some(self.format_operand(argument, addressing_mode)), false),
-                0b10011 => ("MOD".to_string(), Some(self.format_operand(argument, addressing_mode)), false),
+                0b10001 => ("MNO".to_string(), Some(self.format_operand(address, next_address, argument, addressing_mode)), false),
+                0b10010 => ("DZI".to_string(), Some(self.format_operand(address, next_address, argument, addressing_mode)), false),
+                0b10011 => ("MOD".to_string(), Some(self.format_operand(address, next_address, argument, addressing_mode)), false),
+
+                // register operations
+                0b10100 => {
+                    let mnemonic = match (argument >> 3) as u8 {
+                        register_operations::INR => "INR",
+                        register_operations::DER => "DER",
+                        _ => {
+                            return Err(DisassemblerError::InvalidOpcode { 
+                                opcode: opcode as u8, 
+                                address 
+                            });
+                        }
+                    };
+                    (mnemonic.to_string(), Some(self.format_operand(address, next_address, argument, AddressingMode::Register)), false)
+                }
+
+                // subroutines
+                0b10101 => ("WYW".to_string(), Some(self.format_operand(address, next_address, argument, jump_mode)), false),
+                0b10110 => ("POW".to_string(), None, false),
+
+                // bitwise instructions
+                0b10111 => ("AND".to_string(), Some(self.format_operand(address, next_address, argument, addressing_mode)), false),
+                0b11000 => ("OR".to_string(), Some(self.format_operand(address, next_address, argument, addressing_mode)), false),
+                0b11001 => ("XOR".to_string(), Some(self.format_operand(address, next_address, argument, addressing_mode)), false),
+
+                // shift operations
+                0b11010 => {
+                    let count = argument & 0b1111;
+                    match (argument >> 4) as u8 {
+                        shift_operations::SHL => ("SHL".to_string(), Some(self.format_operand(address, next_address, count, AddressingMode::Immediate)), false),
+                        shift_operations::SHR => ("SHR".to_string(), Some(self.format_operand(address, next_address, count, AddressingMode::Immediate)), false),
+                        shift_operations::SAR => ("SAR".to_string(), Some(self.format_operand(address, next_address, count, AddressingMode::Immediate)), false),
+                        shift_operations::NOT => ("NOT".to_string(), None, false),
+                        _ => {
+                            return Err(DisassemblerError::InvalidOpcode { 
+                                opcode: opcode as u8, 
+                                address 
+                            });
+                        }
+                    }
+                }
 
                 _ => {
                     return Err(DisassemblerError::InvalidOpcode { 
@@ -54,15 +118,24 @@ impl InstructionDecoder {
             }
         };
 
+        // an offset without a label at its target gets the target spelled out
+        let comment = operand.as_deref()
+            .filter(|operand| operand.starts_with(['+', '-']) && operand[1..].starts_with(|c: char| c.is_ascii_digit()))
+            .map(|_| match relative_target(next_address, argument) {
+                Some(target) => format!("target {}", target),
+                None => "target outside memory".to_string(),
+            });
+
         Ok(DisassembledInstruction {
             address,
             raw_value: word,
+            far_prefix: None,
             opcode: opcode as u8,
             argument,
             mnemonic,
             operand,
             is_data: is_data_result,
-            comment: None,
+            comment,
         })
     }
 
@@ -75,41 +148,59 @@ impl InstructionDecoder {
             bits if bits == addressing_mode_bits::DIRECT => AddressingMode::Direct,
             bits if bits == addressing_mode_bits::IMMEDIATE => AddressingMode::Immediate,
             bits if bits == addressing_mode_bits::INDIRECT => AddressingMode::Indirect,
+            bits if bits == addressing_mode_bits::MULTIPLE_INDIRECT => AddressingMode::MultipleIndirect,
             bits if bits == addressing_mode_bits::REGISTER => AddressingMode::Register,
+            bits if bits == addressing_mode_bits::REGISTER_INDIRECT => AddressingMode::RegisterIndirect,
+            bits if bits == addressing_mode_bits::BASE_REGISTER => AddressingMode::BaseRegister,
             bits if bits == addressing_mode_bits::RELATIVE => AddressingMode::Relative,
             // default to Direct
             _ => AddressingMode::Direct,
         }
     }
 
-    fn format_operand(&self, argument: u16, mode: AddressingMode) -> String {
+    fn format_operand(&self, address: u16, next_address: u16, argument: u16, mode: AddressingMode) -> String {
         match mode {
             AddressingMode::None => String::new(),
-            AddressingMode::Direct => {
-                if let Some(label) = self.labels.get(&argument) {
-                    label.clone()
-                } else {
-                    argument.to_string()
-                }
-            }
+            AddressingMode::Direct => self.address_name(argument),
             AddressingMode::Immediate => format!("#{}", argument),
-            AddressingMode::Indirect => {
-                if let Some(label) = self.labels.get(&argument) {
-                    format!("[{}]", label)
-                } else {
-                    format!("[{}]", argument)
-                }
+            AddressingMode::Indirect => format!("[{}]", self.address_name(argument)),
+            AddressingMode::MultipleIndirect => format!("[[{}]]", self.address_name(argument)),
+            AddressingMode::Register => format!("R{}", argument & 0b111),
+            AddressingMode::RegisterIndirect => format!("[R{}]", argument & 0b111),
+            AddressingMode::BaseRegister => {
+                let (register, offset) = decode_base_register(argument);
+                format!("R{}[{}]", register, self.address_name(offset))
             }
-            AddressingMode::Register => format!("R{}", argument),
             AddressingMode::Relative => {
-                if (argument & 0x400) != 0 {
-                    // negative (sign extend)
-                    let offset = argument | 0xF800;
-                    format!("{}", offset as i16)
-                } else {
-                    format!("+{}", argument)
+                // +label when the target has one, else the distance from the instruction as written in source
+                match relative_target(next_address, argument).and_then(|target| self.labels.get(&target)) {
+                    Some(label) => format!("+{}", label),
+                    None => {
+                        let distance = next_address as i32 + (argument as u8 as i8) as i32 - address as i32;
+                        format!("{:+}", distance)
+                    }
                 }
             }
         }
     }
+
+    /// label at the address, else the address itself
+    fn address_name(&self, address: u16) -> String {
+        match self.labels.get(&address) {
+            Some(label) => label.clone(),
+            None => address.to_string(),
+        }
+    }
+}
+
+/// opcode, argument and length in words of the instruction at `address` (far prefix joined with the next word)
+pub(crate) fn decode_at(machine_code: &[u16], address: usize) -> (u16, u16, usize) {
+    let word = machine_code[address];
+
+    if is_far_prefix(word) && address + 1 < machine_code.len() {
+        let (opcode, _, argument) = decode_far_instruction(word, machine_code[address + 1]);
+        (opcode as u16, argument, 2)
+    } else {
+        ((word >> 11) & 0b11111, extract_argument(word), 1)
+    }
 }
@@ -14,12 +14,26 @@ pub use error::DisassemblerError;
 pub use types::{DisassembledInstruction, AddressingMode};
 pub use core::Disassembler;
 pub use advanced_disassembler::AdvancedDisassembler;
+pub use asmodeus_shared::IsaProfile;
+pub use hephasm::DebugInfo;
 
 pub fn disassemble(machine_code: &[u16]) -> Result<Vec<String>, DisassemblerError> {
     let mut disassembler = Disassembler::new();
     disassembler.disassemble(machine_code)
 }
 
+pub fn disassemble_with_profile(machine_code: &[u16], profile: IsaProfile) -> Result<Vec<String>, DisassemblerError> {
+    let mut disassembler = Disassembler::new_with_profile(profile);
+    disassembler.disassemble(machine_code)
+}
+
+/// label names and source lines taken from the assembler's debug info
+pub fn disassemble_with_debug_info(machine_code: &[u16], profile: IsaProfile, debug_info: DebugInfo) -> Result<Vec<String>, DisassemblerError> {
+    let mut disassembler = Disassembler::new_with_profile(profile);
+    disassembler.set_debug_info(debug_info);
+    disassembler.disassemble(machine_code)
+}
+
 pub fn disassemble_to_string(machine_code: &[u16]) -> Result<String, DisassemblerError> {
     let lines = disassemble(machine_code)?;
     Ok(lines.join("\n"))
@@ -4,6 +4,8 @@
 pub struct DisassembledInstruction {
     pub address: u16,
     pub raw_value: u16,
+    /// prefix word of a far (two-word) instruction
+    pub far_prefix: Option<u16>,
     pub opcode: u8,
     pub argument: u16,
     pub mnemonic: String,
@@ -18,6 +20,9 @@ pub enum AddressingMode {
     Direct,
     Immediate,
     Indirect,
+    MultipleIndirect,
     Register,
+    RegisterIndirect,
+    BaseRegister,
     Relative,
 }
@@ -20,3 +20,63 @@ fn test_advanced_disassembler() {
     // should be identified as data
     assert!(disasm_text.contains("42"));
 }
+
+#[test]
+fn test_advanced_disassembler_far_jump() {
+    let mut advanced = AdvancedDisassembler::new();
+    
+    let mut machine_code = vec![0u16; 301];
+    machine_code[0] = (0b11111 << 11) | (300 >> 8);   // far prefix
+    machine_code[1] = (0b00101 << 11) | (300 & 0xFF); // SOB 300
+    machine_code[300] = 0b00111 << 11;                // STP
+
+    let result = advanced.disassemble(&machine_code).unwrap();
+    let disasm_text = result.join("\n");
+    
+    assert!(disasm_text.contains("SOB L_012C"));
+    assert!(disasm_text.contains("L_012C:"));
+    assert!(disasm_text.contains("STP"));
+}
+
+#[test]
+fn test_advanced_disassembler_call_flow() {
+    let mut advanced = AdvancedDisassembler::new();
+    
+    let machine_code = vec![
+        (0b10101 << 11) | 3,   // WYW 3 (call)
+        0b00111 << 11,         // STP (reached after return)
+        42,                    // Data
+        (0b00100 << 11) | 2,   // POB 2 (subroutine)
+        0b10110 << 11,         // POW
+        7,                     // Data after return
+    ];
+
+    let result = advanced.disassemble(&machine_code).unwrap();
+    let disasm_text = result.join("\n");
+    
+    assert!(disasm_text.contains("WYW L_0003"));
+    assert!(disasm_text.contains("L_0003:"));
+    assert!(disasm_text.contains("STP"));
+    assert!(disasm_text.contains("POW"));
+    // words after STP and POW are not code
+    assert!(disasm_text.contains("RST 42"));
+    assert!(disasm_text.contains("RST 7"));
+}
+
+#[test]
+fn test_advanced_disassembler_relative_jump() {
+    let mut advanced = AdvancedDisassembler::new();
+
+    // SOB +start jumps over the data word to address 2
+    let machine_code = hephasm::assemble_source("SOB +start\ndane: RST 5\nstart: POB dane\nWYJ\nSTP\n").unwrap();
+    assert_eq!(machine_code[0] >> 11, 0b00101);
+
+    let result = advanced.disassemble(&machine_code).unwrap();
+    let disasm_text = result.join("\n");
+
+    assert!(disasm_text.contains("L_0002:"), "{}", disasm_text);
+    assert!(disasm_text.contains("POB"), "{}", disasm_text);
+    assert!(disasm_text.contains("WYJ"), "{}", disasm_text);
+    assert!(disasm_text.contains("STP"), "{}", disasm_text);
+    assert!(disasm_text.contains("RST 5"), "{}", disasm_text);
+}
@@ -1,4 +1,4 @@
-use dismael::{disassemble, disassemble_to_string};
+use dismael::{disassemble, disassemble_to_string, disassemble_with_profile, IsaProfile};
 
 #[test]
 fn test_simple_instruction_disassembly() {
@@ -74,3 +74,135 @@ fn test_disassemble_to_string() {
     assert!(result.contains("STP"));
     assert!(result.contains("100"));
 }
+
+#[test]
+fn test_register_instructions_disassembly() {
+    let machine_code = vec![
+        (0b00100 << 11) | (0b100 << 8) | 1,                   // POB R1
+        (0b00011 << 11) | (0b100 << 8) | 2,                   // ŁAD R2
+        (0b10100 << 11) | (0b100 << 8) | 1,  // INR R1
+        (0b10100 << 11) | (0b100 << 8) | (0b00001 << 3) | 7,  // DER R7
+        0b00111 << 11,                                        // STP
+    ];
+
+    let result = disassemble(&machine_code).unwrap();
+    let text = result.join("\n");
+
+    assert!(text.contains("POB R1"));
+    assert!(text.contains("ŁAD R2"));
+    assert!(text.contains("INR R1"));
+    assert!(text.contains("DER R7"));
+}
+
+#[test]
+fn test_indirect_and_base_register_disassembly() {
+    let indexed = (3 << 11) | 5;
+    let machine_code = vec![
+        (0b00100 << 11) | (0b011 << 8) | 200,              // POB [[200]]
+        (0b00001 << 11) | (0b101 << 8) | 4,                // DOD [R4]
+        (0b11111 << 11) | (indexed >> 8),                  // far prefix
+        (0b00011 << 11) | (0b110 << 8) | (indexed & 0xFF), // ŁAD R3[5]
+        0b00111 << 11,                                     // STP
+        (0b00100 << 11) | (0b110 << 8) | 100,              // POB R0[100]
+    ];
+
+    let result = disassemble(&machine_code).unwrap();
+    let text = result.join("
+");
+
+    assert!(text.contains("POB [[200]]"));
+    assert!(text.contains("DOD [R4]"));
+    assert!(text.contains("ŁAD R3[5]"));
+    assert!(text.contains("POB R0[100]"));
+}
+
+#[test]
+fn test_far_instruction_disassembly() {
+    let machine_code = vec![
+        (0b11111 << 11) | (1000 >> 8),                  // far prefix
+        (0b00100 << 11) | (0b001 << 8) | (1000 & 0xFF), // POB #1000
+        0b00111 << 11,                                  // STP
+    ];
+
+    let result = disassemble(&machine_code).unwrap();
+    let text = result.join("\n");
+
+    assert!(text.contains("POB #1000"));
+    assert!(text.contains("0000: F803 21E8")); // both words shown
+    assert!(text.contains("0002: 3800"));
+    assert!(!text.contains("RST"));
+}
+
+#[test]
+fn test_immediate_operand_disassembly() {
+    let machine_code = vec![
+        (0b00100 << 11) | (0b001 << 8) | 42, // POB #42
+        0b00111 << 11,                       // STP
+    ];
+
+    let result = disassemble(&machine_code).unwrap();
+    assert!(result.join("\n").contains("POB #42"));
+}
+
+#[test]
+fn test_bitwise_instructions_disassembly() {
+    let machine_code = vec![
+        (0b10111 << 11) | (0b001 << 8) | 0x0F,                 // AND #15
+        (0b11000 << 11) | 100,                                 // OR 100
+        (0b11001 << 11) | (0b100 << 8) | 2,                    // XOR R2
+        (0b11010 << 11) | (0b001 << 8) | 4,                    // SHL #4
+        (0b11010 << 11) | (0b001 << 8) | (0b0001 << 4) | 3,    // SHR #3
+        (0b11010 << 11) | (0b001 << 8) | (0b0010 << 4) | 1,    // SAR #1
+        (0b11010 << 11) | (0b0011 << 4),                       // NOT
+        0b00111 << 11,                                         // STP
+    ];
+
+    let result = disassemble(&machine_code).unwrap();
+    let text = result.join("\n");
+
+    assert!(text.contains("AND #15"));
+    assert!(text.contains("OR 100"));
+    assert!(text.contains("XOR R2"));
+    assert!(text.contains("SHL #4"));
+    assert!(text.contains("SHR #3"));
+    assert!(text.contains("SAR #1"));
+    assert!(text.contains("NOT"));
+}
+
+#[test]
+fn test_flag_jumps_disassembly() {
+    let machine_code = vec![
+        (0b11011 << 11) | 4, // SOC 4
+        (0b11100 << 11) | 4, // SOV 4
+        (0b11101 << 11) | 4, // SOP 4
+        (0b11110 << 11) | 4, // SNZ 4
+        0b00111 << 11,       // STP
+    ];
+
+    let result = disassemble(&machine_code).unwrap();
+    let text = result.join("\n");
+
+    assert!(text.contains("SOC L_0004"));
+    assert!(text.contains("SOV L_0004"));
+    assert!(text.contains("SOP L_0004"));
+    assert!(text.contains("SNZ L_0004"));
+    assert!(text.contains("L_0004:"));
+}
+
+#[test]
+fn test_instructions_outside_profile_flagged() {
+    let machine_code = vec![
+        (0b00100 << 11) | (0b001 << 8) | 6, // POB #6
+        (0b10001 << 11) | (0b001 << 8) | 7, // MNO #7
+        0b00111 << 11,                      // STP
+    ];
+
+    let result = disassemble_with_profile(&machine_code, IsaProfile::CLASSIC_W).unwrap();
+    let text = result.join("\n");
+    assert!(text.contains("MNO #7 ; not in ISA profile classic-w"));
+    assert!(!text.contains("POB #6 ;"));
+
+    // default disassembly does not flag anything
+    let text = disassemble(&machine_code).unwrap().join("\n");
+    assert!(!text.contains("not in ISA profile"));
+}
@@ -1,4 +1,5 @@
-use dismael::disassemble;
+use dismael::{disassemble, disassemble_with_debug_info, IsaProfile};
+use hephasm::{DebugInfo, DebugSymbol, MacroExpansion, SourceLocation, SymbolType, WordKind};
 
 #[test]
 fn test_jump_target_labels() {
@@ -32,6 +33,22 @@ fn test_conditional_jump_labels() {
     assert!(disasm_text.contains("SOM L_0003"));
 }
 
+#[test]
+fn test_relative_jump_labels() {
+    let machine_code = vec![
+        0b00111 << 11,                         // STP (target of jump)
+        (0b00101 << 11) | (0b111 << 8) | 0xFE, // SOB -1 (offset -2 from address 2)
+        (0b00100 << 11) | (0b111 << 8) | 0x20, // POB +33, past the end of the code
+    ];
+
+    let result = disassemble(&machine_code).unwrap();
+    
+    let disasm_text = result.join("\n");
+    assert!(disasm_text.contains("L_0000:"));
+    assert!(disasm_text.contains("SOB +L_0000"));
+    assert!(disasm_text.contains("POB +33 ; target 35"), "{}", disasm_text);
+}
+
 #[test]
 fn test_label_uniqueness() {
     // unique labels even with many jump targets
@@ -75,3 +92,28 @@ fn test_soz_jump_labels() {
     assert!(disasm_text.contains("L_0003:"));
     assert!(disasm_text.contains("SOZ L_0003"));
 }
+
+#[test]
+fn test_labels_and_lines_from_debug_info() {
+    let machine_code = vec![
+        (0b00100 << 11) | 2,   // start: POB value
+        (0b00101 << 11) | 1,   // loop: SOB loop
+        7,                     // value: RST 7
+    ];
+    let symbol = |name: &str, value| DebugSymbol { name: name.to_string(), value, symbol_type: SymbolType::Label, file: None, line: None };
+    let word = |line, expansion, kind| Some(SourceLocation { file: Some("main.asmod".to_string()), line, column: 5, expansion, kind });
+    let debug_info = DebugInfo {
+        words: vec![word(1, None, WordKind::Instruction), word(4, Some(0), WordKind::Instruction), word(3, None, WordKind::Data)],
+        symbols: vec![symbol("loop", 1), symbol("start", 0), symbol("value", 2)],
+        expansions: vec![MacroExpansion { name: "forever".to_string(), file: None, line: 2, parent: None }],
+        references: Vec::new(),
+    };
+
+    let result = disassemble_with_debug_info(&machine_code, IsaProfile::FULL, debug_info).unwrap();
+    let disasm_text = result.join("\n");
+    assert!(disasm_text.contains("start:"));
+    assert!(disasm_text.contains("POB value ; main.asmod:1:5"));
+    assert!(disasm_text.contains("loop:"));
+    assert!(disasm_text.contains("SOB loop ; main.asmod:4:5 in macro forever"));
+    assert!(!disasm_text.contains("L_0001"));
+}
@@ -0,0 +1,124 @@
+//! conditional assembly: JEŻELI blocks are replaced by the branch their condition selects
+
+use crate::error::AssemblerError;
+use crate::symbol_table::SymbolTable;
+use crate::operand::OperandResolver;
+use parseid::ast::{ConditionalBlock, Directive, ProgramElement};
+use parseid::is_constant_directive;
+use std::collections::BTreeMap;
+
+pub struct ConditionalProcessor {
+    operand_resolver: OperandResolver,
+}
+
+impl ConditionalProcessor {
+    pub fn new() -> Self {
+        Self {
+            operand_resolver: OperandResolver::new(),
+        }
+    }
+
+    /// conditions see the definitions and the STAŁA constants above them;
+    /// a STAŁA named like a definition is dropped, the definition takes its place
+    pub fn prune(&self, elements: &[ProgramElement], definitions: &BTreeMap<String, i32>) -> Result<Vec<ProgramElement>, AssemblerError> {
+        let mut scope = self.scope(definitions)?;
+        let mut pruned = Vec::with_capacity(elements.len());
+        scope.prune_into(elements, &mut pruned);
+        Ok(pruned)
+    }
+
+    /// constants known to conditions while a program is walked in order
+    pub fn scope<'a>(&'a self, definitions: &'a BTreeMap<String, i32>) -> Result<ConditionScope<'a>, AssemblerError> {
+        let mut constants = SymbolTable::new();
+        for (name, value) in definitions {
+            constants.define_constant(name.clone(), *value)?;
+        }
+        Ok(ConditionScope {
+            processor: self,
+            definitions,
+            constants,
+        })
+    }
+
+    /// constants built from labels or $ are only known after layout, conditions cannot use them
+    fn define_constant(&self, directive: &Directive, constants: &mut SymbolTable) -> Result<(), AssemblerError> {
+        // malformed and duplicate constants are reported by the second pass
+        if directive.arguments.len() != 2 || constants.resolve(&directive.arguments[0]).is_some() {
+            return Ok(());
+        }
+        let expression = directive.expressions[1].as_ref();
+        if expression.is_some_and(|expression| expression.uses_current_address()) {
+            return Ok(());
+        }
+
+        match self.operand_resolver.resolve_value(&directive.arguments[1], expression, constants, 0, directive.line) {
+            Ok(value) => constants.define_constant(directive.arguments[0].clone(), value),
+            Err(AssemblerError::UndefinedSymbol { .. }) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn condition(&self, conditional: &ConditionalBlock, constants: &SymbolTable) -> Result<bool, AssemblerError> {
+        let condition = &conditional.condition;
+        if condition.uses_current_address() {
+            return Err(AssemblerError::InvalidDirectiveArguments {
+                directive: "JEŻELI".to_string(),
+                reason: "condition cannot use $".to_string(),
+                line: conditional.line,
+            });
+        }
+        let value = self.operand_resolver.resolve_value(&condition.to_string(), Some(condition), constants, 0, conditional.line)?;
+        Ok(value != 0)
+    }
+}
+
+impl Default for ConditionalProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// definitions and the STAŁA constants met so far
+pub struct ConditionScope<'a> {
+    processor: &'a ConditionalProcessor,
+    definitions: &'a BTreeMap<String, i32>,
+    constants: SymbolTable,
+}
+
+impl ConditionScope<'_> {
+    /// errors are left for the macro expansion walking the program again to report
+    fn prune_into(&mut self, elements: &[ProgramElement], pruned: &mut Vec<ProgramElement>) {
+        for element in elements {
+            match element {
+                ProgramElement::Conditional(conditional) => match self.select(conditional) {
+                    Ok(branch) => self.prune_into(branch, pruned),
+                    Err(_) => pruned.push(element.clone()),
+                },
+                _ if self.keep(element).unwrap_or(true) => pruned.push(element.clone()),
+                _ => {}
+            }
+        }
+    }
+
+    /// a STAŁA becomes visible to the conditions below it, false for one overridden by a definition
+    pub fn keep(&mut self, element: &ProgramElement) -> Result<bool, AssemblerError> {
+        if let ProgramElement::Directive(dir) = element {
+            if is_constant_directive(&dir.name) {
+                if dir.arguments.first().is_some_and(|name| self.definitions.contains_key(name)) {
+                    return Ok(false);
+                }
+                self.processor.define_constant(dir, &mut self.constants)?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// branch the condition selects
+    pub fn select<'b>(&self, conditional: &'b ConditionalBlock) -> Result<&'b [ProgramElement], AssemblerError> {
+        if self.processor.condition(conditional, &self.constants)? {
+            Ok(&conditional.then_branch)
+        } else {
+            Ok(&conditional.else_branch)
+        }
+    }
+}
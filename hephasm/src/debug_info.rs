@@ -0,0 +1,439 @@
+//! where the words of an assembled program came from
+
+use crate::layout::Layout;
+use crate::symbol_table::SymbolTable;
+use crate::types::SymbolType;
+use parseid::ast::{AddressingMode, Operand, ProgramElement};
+use parseid::{is_constant_directive, is_string_directive};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+/// source of every word, the symbols and the macro expansions words came from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DebugInfo {
+    /// indexed by address, None for words between sections no element was placed in
+    pub words: Vec<Option<SourceLocation>>,
+    /// sorted by name
+    pub symbols: Vec<DebugSymbol>,
+    /// in order of expansion, parents before the expansions nested in them
+    pub expansions: Vec<MacroExpansion>,
+    /// uses of symbols in operands and directive arguments, in program order
+    pub references: Vec<SymbolReference>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceLocation {
+    /// None when the program was not read from a file
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    /// index into expansions, None outside of macros
+    pub expansion: Option<usize>,
+    pub kind: WordKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WordKind {
+    /// instruction, or the prefix word of a far one
+    Instruction,
+    /// RST, RPA and other directive output
+    Data,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugSymbol {
+    pub name: String,
+    pub value: i32,
+    pub symbol_type: SymbolType,
+    pub file: Option<String>,
+    /// None for constants defined from outside the program
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolReference {
+    pub name: String,
+    pub file: Option<String>,
+    pub line: usize,
+}
+
+/// one macro call, line and file are the ones of the call
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroExpansion {
+    pub name: String,
+    pub file: Option<String>,
+    pub line: usize,
+    /// expansion whose body holds the call
+    pub parent: Option<usize>,
+}
+
+impl DebugInfo {
+    pub(crate) fn build(
+        elements: &[ProgramElement],
+        origins: &[Option<usize>],
+        layout: &Layout,
+        symbol_table: &SymbolTable,
+        expansions: &[MacroExpansion]
+    ) -> Self {
+        let mut words = vec![None; layout.end() as usize];
+        let mut definitions = HashMap::new();
+        let mut references = Vec::new();
+
+        for (index, element) in elements.iter().enumerate() {
+            let (line, column, kind) = match element {
+                ProgramElement::Instruction(inst) => (inst.line, inst.column, WordKind::Instruction),
+                ProgramElement::Directive(dir) => (dir.line, dir.column, WordKind::Data),
+                ProgramElement::LabelDefinition(label) => {
+                    definitions.entry(label.name.as_str()).or_insert(label.line);
+                    continue;
+                }
+                _ => continue,
+            };
+
+            // elements without words may sit past the end of the program
+            let start = layout.addresses[index] as usize;
+            for word in words.iter_mut().skip(start).take(layout.sizes[index] as usize) {
+                *word = Some(SourceLocation {
+                    file: None,
+                    line,
+                    column,
+                    expansion: origins[index],
+                    kind,
+                });
+            }
+
+            if let ProgramElement::Directive(dir) = element {
+                if is_constant_directive(&dir.name) {
+                    if let Some(name) = dir.arguments.first() {
+                        definitions.entry(name.as_str()).or_insert(dir.line);
+                    }
+                }
+            }
+            // a parameter used in a macro body refers to the symbol where the macro is called
+            let reference_line = origins[index]
+                .and_then(|expansion| Self::outermost(expansions, expansion))
+                .map_or(line, |call| call.line);
+            references.extend(Self::referenced_symbols(element).into_iter()
+                .filter(|name| symbol_table.resolve(name).is_some())
+                .map(|name| SymbolReference {
+                    name: name.to_string(),
+                    file: None,
+                    line: reference_line,
+                }));
+        }
+
+        let mut symbols: Vec<DebugSymbol> = symbol_table.iter()
+            .map(|(name, symbol)| DebugSymbol {
+                name: name.to_string(),
+                value: symbol.value,
+                symbol_type: symbol.symbol_type.clone(),
+                file: None,
+                // constants given to the assembler are placed at line 0
+                line: definitions.get(name).copied().filter(|&line| line > 0),
+            })
+            .collect();
+        symbols.sort_by(|a, b| a.name.cmp(&b.name));
+
+        DebugInfo {
+            words,
+            symbols,
+            expansions: expansions.to_vec(),
+            references,
+        }
+    }
+
+    fn outermost(expansions: &[MacroExpansion], mut expansion: usize) -> Option<&MacroExpansion> {
+        while let Some(parent) = expansions.get(expansion)?.parent {
+            expansion = parent;
+        }
+        expansions.get(expansion)
+    }
+
+    /// names an element's operand or arguments may refer to, not checked against the symbol table
+    fn referenced_symbols(element: &ProgramElement) -> Vec<&str> {
+        match element {
+            ProgramElement::Instruction(inst) => inst.operand.as_ref().map(Self::operand_symbols).unwrap_or_default(),
+            ProgramElement::Directive(dir) if is_string_directive(&dir.name) => Vec::new(),
+            ProgramElement::Directive(dir) => {
+                // STAŁA NAME = value does not refer to NAME
+                let skip = usize::from(is_constant_directive(&dir.name));
+                dir.arguments.iter().zip(&dir.expressions).skip(skip)
+                    .flat_map(|(argument, expression)| match expression {
+                        Some(expression) => expression.symbols(),
+                        None => vec![argument.as_str()],
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn operand_symbols(operand: &Operand) -> Vec<&str> {
+        if let Some(expression) = &operand.expression {
+            return expression.symbols();
+        }
+        match &operand.addressing_mode {
+            AddressingMode::Indexed { address, .. } => vec![address.as_str()],
+            AddressingMode::BaseRegister { offset, .. } => vec![offset.as_str()],
+            AddressingMode::Relative => vec![operand.value.trim_start_matches('+')],
+            _ => vec![operand.value.as_str()],
+        }
+    }
+
+    pub fn location(&self, address: u16) -> Option<&SourceLocation> {
+        self.words.get(address as usize)?.as_ref()
+    }
+
+    /// name of the label at an address, the first one by name if there are several
+    pub fn label_at(&self, address: u16) -> Option<&str> {
+        self.symbols.iter()
+            .find(|symbol| symbol.symbol_type == SymbolType::Label && symbol.value == address as i32)
+            .map(|symbol| symbol.name.as_str())
+    }
+
+    /// macro calls leading to an expansion, outermost first
+    pub fn macro_chain(&self, expansion: usize) -> Vec<&MacroExpansion> {
+        let mut chain = Vec::new();
+        let mut current = self.expansions.get(expansion);
+        while let Some(expansion) = current {
+            chain.push(expansion);
+            current = expansion.parent.and_then(|parent| self.expansions.get(parent));
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// outer -> inner, names of the macros in the chain
+    pub fn macro_trace(&self, expansion: usize) -> String {
+        self.macro_chain(expansion).iter()
+            .map(|expansion| expansion.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+
+    /// replaces program lines with files and lines within them, e.g. from a parseid SourceMap
+    pub fn locate_files<F>(&mut self, locate: F)
+    where
+        F: Fn(usize) -> Option<(String, usize)>,
+    {
+        for word in self.words.iter_mut().flatten() {
+            if let Some((file, line)) = locate(word.line) {
+                word.file = Some(file);
+                word.line = line;
+            }
+        }
+        for expansion in &mut self.expansions {
+            if let Some((file, line)) = locate(expansion.line) {
+                expansion.file = Some(file);
+                expansion.line = line;
+            }
+        }
+        for symbol in &mut self.symbols {
+            if let Some((file, line)) = symbol.line.and_then(&locate) {
+                symbol.file = Some(file);
+                symbol.line = Some(line);
+            }
+        }
+        for reference in &mut self.references {
+            if let Some((file, line)) = locate(reference.line) {
+                reference.file = Some(file);
+                reference.line = line;
+            }
+        }
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    /// file:line:column, line:column without a file
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// header of the text form, the sidecar file written next to a binary
+const HEADER: &str = "; asmodeus debug info 1";
+
+impl fmt::Display for DebugInfo {
+    /// one record per line:
+    /// `file <id> <path>`, `symbol <name> <label|variable|constant> <value> <file> <line>`,
+    /// `reference <name> <file> <line>`, `expansion <name> <file> <line> <parent>`,
+    /// `word <address> <code|data> <file> <line> <column> <expansion>`;
+    /// expansions are numbered in order, a missing file, line or index is written as `-`;
+    /// words come in address order, without the ones between sections
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut files: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut file_order = Vec::new();
+        let file_names = self.words.iter().flatten().map(|word| &word.file)
+            .chain(self.expansions.iter().map(|expansion| &expansion.file))
+            .chain(self.symbols.iter().map(|symbol| &symbol.file))
+            .chain(self.references.iter().map(|reference| &reference.file));
+        for file in file_names.flatten() {
+            if !files.contains_key(file.as_str()) {
+                files.insert(file, file_order.len());
+                file_order.push(file.as_str());
+            }
+        }
+        let file_id = |file: &Option<String>| optional(file.as_deref().map(|file| files[file]));
+
+        writeln!(f, "{}", HEADER)?;
+        for (id, file) in file_order.iter().enumerate() {
+            writeln!(f, "file {} {}", id, file)?;
+        }
+        for symbol in &self.symbols {
+            let symbol_type = match symbol.symbol_type {
+                SymbolType::Label => "label",
+                SymbolType::Variable => "variable",
+                SymbolType::Constant => "constant",
+                SymbolType::External => "external",
+            };
+            writeln!(f, "symbol {} {} {} {} {}", symbol.name, symbol_type, symbol.value, file_id(&symbol.file), optional(symbol.line))?;
+        }
+        for reference in &self.references {
+            writeln!(f, "reference {} {} {}", reference.name, file_id(&reference.file), reference.line)?;
+        }
+        for expansion in &self.expansions {
+            writeln!(f, "expansion {} {} {} {}", expansion.name, file_id(&expansion.file), expansion.line, optional(expansion.parent))?;
+        }
+        for (address, word) in self.words.iter().enumerate() {
+            let Some(word) = word else {
+                continue;
+            };
+            let kind = match word.kind {
+                WordKind::Instruction => "code",
+                WordKind::Data => "data",
+            };
+            writeln!(f, "word {} {} {} {} {} {}", address, kind, file_id(&word.file), word.line, word.column, optional(word.expansion))?;
+        }
+        Ok(())
+    }
+}
+
+fn optional(value: Option<usize>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
+
+impl FromStr for DebugInfo {
+    type Err = String;
+
+    /// reads the text form written by Display, errors name the line at fault
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut lines = text.lines().enumerate();
+        if lines.next().map(|(_, line)| line.trim()) != Some(HEADER) {
+            return Err("line 1: not an asmodeus debug info file".to_string());
+        }
+
+        let mut files: Vec<String> = Vec::new();
+        let mut debug_info = DebugInfo::default();
+        for (index, line) in lines {
+            let record = Record::parse(line, &files).map_err(|reason| format!("line {}: {}", index + 1, reason))?;
+            match record {
+                None => {}
+                Some(Record::File(path)) => files.push(path),
+                Some(Record::Symbol(symbol)) => debug_info.symbols.push(symbol),
+                Some(Record::Reference(reference)) => debug_info.references.push(reference),
+                Some(Record::Expansion(expansion)) => debug_info.expansions.push(expansion),
+                Some(Record::Word(address, word)) => {
+                    // addresses between sections are left out
+                    if address < debug_info.words.len() {
+                        return Err(format!("line {}: expected word {} or above, found {}", index + 1, debug_info.words.len(), address));
+                    }
+                    debug_info.words.resize(address, None);
+                    debug_info.words.push(Some(word));
+                }
+            }
+        }
+        Ok(debug_info)
+    }
+}
+
+enum Record {
+    File(String),
+    Symbol(DebugSymbol),
+    Reference(SymbolReference),
+    Expansion(MacroExpansion),
+    Word(usize, SourceLocation),
+}
+
+impl Record {
+    /// None for blank lines and ; comments
+    fn parse(line: &str, files: &[String]) -> Result<Option<Record>, String> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            return Ok(None);
+        }
+
+        let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+        if kind == "file" {
+            // paths may contain spaces
+            let (id, path) = rest.split_once(' ').ok_or("expected file id and path")?;
+            if number(id)? != files.len() {
+                return Err(format!("expected file {}", files.len()));
+            }
+            return Ok(Some(Record::File(path.to_string())));
+        }
+
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        let record = match (kind, fields.as_slice()) {
+            ("symbol", [name, symbol_type, value, file, line]) => Record::Symbol(DebugSymbol {
+                name: name.to_string(),
+                value: value.parse().map_err(|_| format!("invalid value: {}", value))?,
+                symbol_type: match *symbol_type {
+                    "label" => SymbolType::Label,
+                    "variable" => SymbolType::Variable,
+                    "constant" => SymbolType::Constant,
+                    "external" => SymbolType::External,
+                    other => return Err(format!("unknown symbol type: {}", other)),
+                },
+                file: file_name(file, files)?,
+                line: optional_number(line)?,
+            }),
+            ("reference", [name, file, line]) => Record::Reference(SymbolReference {
+                name: name.to_string(),
+                file: file_name(file, files)?,
+                line: number(line)?,
+            }),
+            ("expansion", [name, file, line, parent]) => Record::Expansion(MacroExpansion {
+                name: name.to_string(),
+                file: file_name(file, files)?,
+                line: number(line)?,
+                parent: optional_number(parent)?,
+            }),
+            ("word", [address, kind, file, line, column, expansion]) => Record::Word(number(address)?, SourceLocation {
+                file: file_name(file, files)?,
+                line: number(line)?,
+                column: number(column)?,
+                expansion: optional_number(expansion)?,
+                kind: match *kind {
+                    "code" => WordKind::Instruction,
+                    "data" => WordKind::Data,
+                    other => return Err(format!("unknown word kind: {}", other)),
+                },
+            }),
+            ("symbol" | "reference" | "expansion" | "word", _) => return Err(format!("wrong number of fields in {} record", kind)),
+            (other, _) => return Err(format!("unknown record: {}", other)),
+        };
+        Ok(Some(record))
+    }
+}
+
+fn number(field: &str) -> Result<usize, String> {
+    field.parse().map_err(|_| format!("invalid number: {}", field))
+}
+
+fn optional_number(field: &str) -> Result<Option<usize>, String> {
+    if field == "-" {
+        return Ok(None);
+    }
+    number(field).map(Some)
+}
+
+fn file_name(field: &str, files: &[String]) -> Result<Option<String>, String> {
+    match optional_number(field)? {
+        None => Ok(None),
+        Some(id) => files.get(id).cloned().map(Some).ok_or_else(|| format!("unknown file: {}", id)),
+    }
+}
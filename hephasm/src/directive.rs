@@ -1,8 +1,12 @@
-//! directive processing (RST, RPA, etc.)
+//! directive processing (RST, RPA, NAPIS, STAŁA, ISA, etc.)
 
 use crate::error::AssemblerError;
-use crate::operand::OperandResolver;
-use parseid::ast::Directive;
+use crate::operand::{OperandResolver, Validator};
+use crate::suggestion::closest;
+use crate::symbol_table::SymbolTable;
+use parseid::ast::{Directive, ProgramElement};
+use asmodeus_shared::IsaProfile;
+use lexariel::DIRECTIVES;
 
 pub struct DirectiveProcessor {
     operand_resolver: OperandResolver,
@@ -15,33 +19,233 @@ impl DirectiveProcessor {
         }
     }
 
-    pub fn assemble_directive(&self, directive: &Directive, memory: &mut [u16], current_address: usize) -> Result<(), AssemblerError> {
+    /// writes the directive's words at current_address, unless they are not the size it was laid out with
+    /// (an RPA count the second pass could not evaluate), returns how many were written
+    pub fn assemble_directive(
+        &self,
+        directive: &Directive,
+        memory: &mut [u16],
+        current_address: usize,
+        size: usize,
+        symbol_table: &SymbolTable
+    ) -> Result<usize, AssemblerError> {
+        let words = self.data_words(directive, symbol_table, current_address as u16)?;
+        // a section may end in directives without words, placed past the last word of memory used
+        if words.len() != size || words.is_empty() {
+            return Ok(0);
+        }
+        memory[current_address..current_address + size].copy_from_slice(&words);
+        Ok(size)
+    }
+
+    /// number of memory words the directive occupies;
+    /// an RPA count may only refer to labels defined before it
+    pub fn directive_size(&self, directive: &Directive, symbol_table: &SymbolTable, current_address: u16) -> Result<usize, AssemblerError> {
         match directive.name.to_uppercase().as_str() {
+            // values may refer to labels defined further down
+            "RST" => Ok(directive.arguments.len().max(1)),
+            "RPA" if directive.arguments.len() == 2 => {
+                Ok(self.argument_word(directive, 0, false, symbol_table, current_address)? as usize)
+            }
+            _ => Ok(self.data_words(directive, symbol_table, current_address)?.len()),
+        }
+    }
+
+    /// words the directive places in memory, empty for directives without code
+    fn data_words(&self, directive: &Directive, symbol_table: &SymbolTable, current_address: u16) -> Result<Vec<u16>, AssemblerError> {
+        let words = match directive.name.to_uppercase().as_str() {
             "RST" => {
-                let value = if directive.arguments.is_empty() {
-                    0
+                // RST 1, 3, end-start - one word per value
+                if directive.arguments.is_empty() {
+                    vec![0]
                 } else {
-                    let signed_value = self.operand_resolver.parse_signed_number(&directive.arguments[0], directive.line)?;
-                    signed_value as u16
-                };
-                memory[current_address] = value;
+                    (0..directive.arguments.len())
+                        .map(|index| self.argument_word(directive, index, true, symbol_table, current_address))
+                        .collect::<Result<Vec<u16>, AssemblerError>>()?
+                }
             }
             "RPA" => {
-                memory[current_address] = 0;
+                // RPA, RPA count or RPA count, fill
+                match directive.arguments.len() {
+                    0 => vec![0],
+                    1 => vec![0; self.argument_word(directive, 0, false, symbol_table, current_address)? as usize],
+                    2 => {
+                        let count = self.argument_word(directive, 0, false, symbol_table, current_address)?;
+                        let fill = self.argument_word(directive, 1, true, symbol_table, current_address)?;
+                        vec![fill; count as usize]
+                    }
+                    _ => {
+                        return Err(AssemblerError::InvalidDirectiveArguments {
+                            directive: directive.name.clone(),
+                            reason: "expected a count and an optional fill value".to_string(),
+                            line: directive.line,
+                        });
+                    }
+                }
+            }
+            "NAPIS" | "NAPISZ" | "NAPISD" => Self::string_words(directive),
+            "NAZWA_LOKALNA" => {
+                // removed when the macro is expanded
+                return Err(AssemblerError::InvalidDirectiveArguments {
+                    directive: directive.name.clone(),
+                    reason: "local names can only be declared inside a macro".to_string(),
+                    line: directive.line,
+                });
             }
-            "MAKRO" | "KONM" | "NAZWA_LOKALNA" => {
+            "MAKRO" | "KONM" => {
                 // handled in macro processing
                 // valid but dont produce machine code
+                Vec::new()
+            }
+            "ISA" => {
+                // handled before code generation
+                Vec::new()
+            }
+            "DOŁĄCZ" | "DOLACZ" => {
+                // spliced in by parseid when assembling from a file
+                return Err(AssemblerError::InvalidDirectiveArguments {
+                    directive: directive.name.clone(),
+                    reason: "files can only be included when assembling a file".to_string(),
+                    line: directive.line,
+                });
+            }
+            "STAŁA" | "STALA" => {
+                // bound in the symbol table by the second pass
+                Vec::new()
+            }
+            "SEKCJA" | "ADRES" => {
+                // placed by the second pass
+                Vec::new()
+            }
+            "EKSPORTUJ" | "IMPORTUJ" => {
+                // resolved by the linker
+                Vec::new()
             }
             _ => {
                 // unknown directive
                 return Err(AssemblerError::InvalidOpcode {
                     opcode: directive.name.clone(),
                     line: directive.line,
+                    suggestion: closest(&directive.name, DIRECTIVES.iter().copied()),
                 });
             }
+        };
+        Ok(words)
+    }
+
+    /// value bound by STAŁA name = value, has to fit in a word (-32768..=65535)
+    pub fn constant_value(&self, directive: &Directive, symbol_table: &SymbolTable, current_address: u16) -> Result<i32, AssemblerError> {
+        if directive.arguments.len() != 2 {
+            return Err(AssemblerError::InvalidDirectiveArguments {
+                directive: directive.name.clone(),
+                reason: "expected a name and a value".to_string(),
+                line: directive.line,
+            });
+        }
+
+        let expression = directive.expressions[1].as_ref();
+        let value = self.operand_resolver.resolve_value(&directive.arguments[1], expression, symbol_table, current_address, directive.line)?;
+        if value < i16::MIN as i32 || value > u16::MAX as i32 {
+            return Err(AssemblerError::ExpressionOutOfRange {
+                expression: directive.arguments[1].clone(),
+                value: value as i64,
+                line: directive.line,
+            });
         }
-        Ok(())
+        Ok(value)
+    }
+
+    /// SEKCJA name or SEKCJA name, address
+    pub fn section<'a>(&self, directive: &'a Directive, symbol_table: &SymbolTable, current_address: u16) -> Result<(&'a str, Option<u16>), AssemblerError> {
+        match directive.arguments.len() {
+            1 | 2 if directive.expressions[0].is_none() && Validator::new().is_identifier(&directive.arguments[0]) => {
+                let address = match directive.arguments.len() {
+                    2 => Some(self.address_argument(directive, 1, symbol_table, current_address)?),
+                    _ => None,
+                };
+                Ok((directive.arguments[0].as_str(), address))
+            }
+            _ => Err(AssemblerError::InvalidDirectiveArguments {
+                directive: directive.name.clone(),
+                reason: "expected a section name and an optional address".to_string(),
+                line: directive.line,
+            }),
+        }
+    }
+
+    /// ADRES address - may only refer to constants and labels defined before it
+    pub fn origin(&self, directive: &Directive, symbol_table: &SymbolTable, current_address: u16) -> Result<u16, AssemblerError> {
+        if directive.arguments.len() != 1 {
+            return Err(AssemblerError::InvalidDirectiveArguments {
+                directive: directive.name.clone(),
+                reason: "expected an address".to_string(),
+                line: directive.line,
+            });
+        }
+        self.address_argument(directive, 0, symbol_table, current_address)
+    }
+
+    fn address_argument(&self, directive: &Directive, index: usize, symbol_table: &SymbolTable, current_address: u16) -> Result<u16, AssemblerError> {
+        let address = self.argument_word(directive, index, false, symbol_table, current_address)?;
+        if address > 2047 {
+            return Err(AssemblerError::AddressOutOfBounds {
+                address,
+                line: directive.line,
+                column: None,
+            });
+        }
+        Ok(address)
+    }
+
+    fn argument_word(
+        &self,
+        directive: &Directive,
+        index: usize,
+        allow_negative: bool,
+        symbol_table: &SymbolTable,
+        current_address: u16
+    ) -> Result<u16, AssemblerError> {
+        let expression = directive.expressions.get(index).and_then(Option::as_ref);
+        self.operand_resolver.resolve_word(&directive.arguments[index], expression, symbol_table, current_address, allow_negative, directive.line)
+    }
+
+    /// one word per character, NAPISZ appends a zero terminator, NAPISD prepends the length
+    fn string_words(directive: &Directive) -> Vec<u16> {
+        let text = directive.arguments.first().map(String::as_str).unwrap_or_default();
+        let mut words: Vec<u16> = text.chars().map(|ch| ch as u16).collect();
+
+        match directive.name.to_uppercase().as_str() {
+            "NAPISZ" => words.push(0),
+            "NAPISD" => words.insert(0, words.len() as u16),
+            _ => {}
+        }
+
+        words
+    }
+
+    /// profile selected by the ISA directive and its line, applies to the whole program
+    pub fn isa_profile(&self, elements: &[ProgramElement]) -> Result<Option<(IsaProfile, usize)>, AssemblerError> {
+        let mut profile = None;
+
+        for element in elements {
+            if let ProgramElement::Directive(directive) = element {
+                if !directive.name.eq_ignore_ascii_case("ISA") {
+                    continue;
+                }
+                if profile.is_some() {
+                    return Err(AssemblerError::DuplicateIsaDirective { line: directive.line });
+                }
+
+                let name = directive.arguments.first().map(String::as_str).unwrap_or_default();
+                let parsed = name.parse::<IsaProfile>().map_err(|_| AssemblerError::UnknownIsaProfile {
+                    profile: name.to_string(),
+                    line: directive.line,
+                })?;
+                profile = Some((parsed, directive.line));
+            }
+        }
+
+        Ok(profile)
     }
 }
 
@@ -4,24 +4,271 @@ use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum AssemblerError {
-    #[error("Undefined symbol: {symbol} at line {line}")]
-    UndefinedSymbol { symbol: String, line: usize },
+    #[error("Undefined symbol: {symbol} at line {line}{}", did_you_mean(.suggestion))]
+    UndefinedSymbol { symbol: String, line: usize, suggestion: Option<String> },
     #[error("Duplicate symbol definition: {symbol} at line {line}")]
     DuplicateSymbol { symbol: String, line: usize },
-    #[error("Invalid opcode: {opcode} at line {line}")]
-    InvalidOpcode { opcode: String, line: usize },
+    #[error("Circular constant definition: {cycle} at line {line}")]
+    CircularConstant { cycle: String, line: usize },
+    #[error("Invalid opcode: {opcode} at line {line}{}", did_you_mean(.suggestion))]
+    InvalidOpcode { opcode: String, line: usize, suggestion: Option<String> },
     #[error("Invalid number format: {value} at line {line}")]
     InvalidNumber { value: String, line: usize },
     #[error("Address out of bounds: {address} (max 2047) at line {line}")]
-    AddressOutOfBounds { address: u16, line: usize },
+    AddressOutOfBounds { address: u16, line: usize, column: Option<usize> },
+    #[error("Relative operand {operand} is {distance} words from the instruction, out of range (-127..128) at line {line}")]
+    RelativeOutOfRange { operand: String, distance: i32, line: usize },
+    #[error("Invalid register: {register} (must be R0-R7) at line {line}")]
+    InvalidRegister { register: String, line: usize },
     #[error("Invalid addressing mode for instruction {instruction}: {mode} at line {line}")]
     InvalidAddressingMode { instruction: String, mode: String, line: usize },
-    #[error("Macro not found: {name} at line {line}")]
-    MacroNotFound { name: String, line: usize },
+    #[error("Macro not found: {name} at line {line}{}", did_you_mean(.suggestion))]
+    MacroNotFound { name: String, line: usize, suggestion: Option<String> },
     #[error("Macro parameter count mismatch for {name}: expected {expected}, found {found} at line {line}")]
     MacroParameterMismatch { name: String, expected: usize, found: usize, line: usize },
-    #[error("Memory overflow: program too large for available memory")]
-    MemoryOverflow,
-    #[error("Extended instruction '{instruction}' not enabled at line {line}. Use --extended flag to enable extended instruction set")]
+    #[error("Macro argument {argument} cannot be used for {parameter} at line {line}")]
+    InvalidMacroArgument { parameter: String, argument: String, line: usize },
+    #[error("Duplicate macro definition: {name} at line {line}")]
+    DuplicateMacro { name: String, line: usize },
+    #[error("Recursive macro call: {cycle} at line {line}")]
+    RecursiveMacro { cycle: String, line: usize },
+    #[error("Macro calls nested deeper than {limit} levels: {chain} at line {line}")]
+    MacroDepthExceeded { chain: String, limit: usize, line: usize },
+    #[error("Invalid arguments for directive {directive} at line {line}: {reason}")]
+    InvalidDirectiveArguments { directive: String, reason: String, line: usize },
+    #[error("Expression {expression} = {value} is out of range at line {line}")]
+    ExpressionOutOfRange { expression: String, value: i64, line: usize },
+    #[error("Division by zero in expression {expression} at line {line}")]
+    DivisionByZero { expression: String, line: usize },
+    #[error("Expression {expression} cannot be relocated at line {line}")]
+    InvalidRelocation { expression: String, line: usize },
+    #[error("Sections {first} and {second} overlap at address {address} at line {line}")]
+    SectionOverlap { first: String, second: String, address: u16, line: usize },
+    #[error("ADRES places code over {} already used in section {section} at line {line}", address_range(*start, *last))]
+    AddressOverlap { section: String, start: u16, last: u16, line: usize },
+    #[error("Memory overflow: program too large for available memory at line {line}")]
+    MemoryOverflow { line: usize },
+    #[error("Extended instruction '{instruction}' not enabled at line {line}. Use --extended flag (or a profile with +muldiv) to enable extended instruction set")]
     ExtendedInstructionNotEnabled { instruction: String, line: usize },
+    #[error("Bitwise instruction '{instruction}' not enabled at line {line}. Use --bits flag (or a profile with +bits) to enable bitwise instruction set")]
+    BitwiseInstructionNotEnabled { instruction: String, line: usize },
+    #[error("Instruction '{instruction}' is not part of instruction set profile '{profile}' at line {line}")]
+    InstructionNotInProfile { instruction: String, profile: String, line: usize },
+    #[error("Unknown instruction set profile: {profile} at line {line}")]
+    UnknownIsaProfile { profile: String, line: usize },
+    #[error("Instruction set profile already selected by ISA directive at line {line}")]
+    DuplicateIsaDirective { line: usize },
+    #[error("Argument {argument} of '{instruction}' needs the far form (DAL prefix), which is not part of instruction set profile '{profile}' at line {line}")]
+    FarFormNotInProfile { instruction: String, argument: u16, profile: String, line: usize },
+    #[error("ISA {profile} goes beyond instruction set profile '{allowed}' given to the assembler at line {line}")]
+    IsaProfileNotAllowed { profile: String, allowed: String, line: usize },
+}
+
+impl AssemblerError {
+    /// line the error refers to, for moving it into another file's numbering
+    pub fn line_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            AssemblerError::UndefinedSymbol { line, .. }
+            | AssemblerError::DuplicateSymbol { line, .. }
+            | AssemblerError::CircularConstant { line, .. }
+            | AssemblerError::InvalidOpcode { line, .. }
+            | AssemblerError::InvalidNumber { line, .. }
+            | AssemblerError::AddressOutOfBounds { line, .. }
+            | AssemblerError::RelativeOutOfRange { line, .. }
+            | AssemblerError::InvalidRegister { line, .. }
+            | AssemblerError::InvalidAddressingMode { line, .. }
+            | AssemblerError::MacroNotFound { line, .. }
+            | AssemblerError::MacroParameterMismatch { line, .. }
+            | AssemblerError::InvalidMacroArgument { line, .. }
+            | AssemblerError::DuplicateMacro { line, .. }
+            | AssemblerError::RecursiveMacro { line, .. }
+            | AssemblerError::MacroDepthExceeded { line, .. }
+            | AssemblerError::InvalidDirectiveArguments { line, .. }
+            | AssemblerError::ExpressionOutOfRange { line, .. }
+            | AssemblerError::DivisionByZero { line, .. }
+            | AssemblerError::InvalidRelocation { line, .. }
+            | AssemblerError::SectionOverlap { line, .. }
+            | AssemblerError::AddressOverlap { line, .. }
+            | AssemblerError::ExtendedInstructionNotEnabled { line, .. }
+            | AssemblerError::BitwiseInstructionNotEnabled { line, .. }
+            | AssemblerError::InstructionNotInProfile { line, .. }
+            | AssemblerError::UnknownIsaProfile { line, .. }
+            | AssemblerError::DuplicateIsaDirective { line }
+            | AssemblerError::FarFormNotInProfile { line, .. }
+            | AssemblerError::IsaProfileNotAllowed { line, .. }
+            | AssemblerError::MemoryOverflow { line } => Some(line),
+        }
+    }
+
+    /// line the error refers to, as numbered in the assembled program
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            AssemblerError::UndefinedSymbol { line, .. }
+            | AssemblerError::DuplicateSymbol { line, .. }
+            | AssemblerError::CircularConstant { line, .. }
+            | AssemblerError::InvalidOpcode { line, .. }
+            | AssemblerError::InvalidNumber { line, .. }
+            | AssemblerError::AddressOutOfBounds { line, .. }
+            | AssemblerError::RelativeOutOfRange { line, .. }
+            | AssemblerError::InvalidRegister { line, .. }
+            | AssemblerError::InvalidAddressingMode { line, .. }
+            | AssemblerError::MacroNotFound { line, .. }
+            | AssemblerError::MacroParameterMismatch { line, .. }
+            | AssemblerError::InvalidMacroArgument { line, .. }
+            | AssemblerError::DuplicateMacro { line, .. }
+            | AssemblerError::RecursiveMacro { line, .. }
+            | AssemblerError::MacroDepthExceeded { line, .. }
+            | AssemblerError::InvalidDirectiveArguments { line, .. }
+            | AssemblerError::ExpressionOutOfRange { line, .. }
+            | AssemblerError::DivisionByZero { line, .. }
+            | AssemblerError::InvalidRelocation { line, .. }
+            | AssemblerError::SectionOverlap { line, .. }
+            | AssemblerError::AddressOverlap { line, .. }
+            | AssemblerError::ExtendedInstructionNotEnabled { line, .. }
+            | AssemblerError::BitwiseInstructionNotEnabled { line, .. }
+            | AssemblerError::InstructionNotInProfile { line, .. }
+            | AssemblerError::UnknownIsaProfile { line, .. }
+            | AssemblerError::DuplicateIsaDirective { line }
+            | AssemblerError::FarFormNotInProfile { line, .. }
+            | AssemblerError::IsaProfileNotAllowed { line, .. }
+            | AssemblerError::MemoryOverflow { line } => Some(*line),
+        }
+    }
+
+    /// text of the source line the error is about (symbol, mnemonic, expression), for pointing at it
+    pub fn subject(&self) -> Option<&str> {
+        match self {
+            AssemblerError::UndefinedSymbol { symbol, .. }
+            | AssemblerError::DuplicateSymbol { symbol, .. } => Some(symbol),
+            AssemblerError::InvalidOpcode { opcode, .. } => Some(opcode),
+            AssemblerError::InvalidNumber { value, .. } => Some(value),
+            AssemblerError::RelativeOutOfRange { operand, .. } => Some(operand),
+            AssemblerError::InvalidRegister { register, .. } => Some(register),
+            AssemblerError::MacroNotFound { name, .. }
+            | AssemblerError::MacroParameterMismatch { name, .. }
+            | AssemblerError::DuplicateMacro { name, .. } => Some(name),
+            AssemblerError::InvalidMacroArgument { argument, .. } => Some(argument),
+            AssemblerError::InvalidDirectiveArguments { directive, .. } => Some(directive),
+            AssemblerError::ExpressionOutOfRange { expression, .. }
+            | AssemblerError::DivisionByZero { expression, .. }
+            | AssemblerError::InvalidRelocation { expression, .. } => Some(expression),
+            AssemblerError::InvalidAddressingMode { instruction, .. }
+            | AssemblerError::ExtendedInstructionNotEnabled { instruction, .. }
+            | AssemblerError::BitwiseInstructionNotEnabled { instruction, .. }
+            | AssemblerError::InstructionNotInProfile { instruction, .. }
+            | AssemblerError::FarFormNotInProfile { instruction, .. } => Some(instruction),
+            AssemblerError::UnknownIsaProfile { profile, .. }
+            | AssemblerError::IsaProfileNotAllowed { profile, .. } => Some(profile),
+            AssemblerError::CircularConstant { .. }
+            | AssemblerError::AddressOutOfBounds { .. }
+            | AssemblerError::RecursiveMacro { .. }
+            | AssemblerError::MacroDepthExceeded { .. }
+            | AssemblerError::SectionOverlap { .. }
+            | AssemblerError::AddressOverlap { .. }
+            | AssemblerError::MemoryOverflow { .. }
+            | AssemblerError::DuplicateIsaDirective { .. } => None,
+        }
+    }
+
+    /// column of the operand at fault, for errors without a subject to look for in the line
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            AssemblerError::AddressOutOfBounds { column, .. } => *column,
+            _ => None,
+        }
+    }
+
+    /// points the error at the operand written at column, unless it already points somewhere
+    pub(crate) fn at_column(mut self, operand_column: usize) -> Self {
+        if let AssemblerError::AddressOutOfBounds { column: column @ None, .. } = &mut self {
+            *column = Some(operand_column);
+        }
+        self
+    }
+
+    /// name the misspelled one was probably meant to be, filled in once the whole program is known
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            AssemblerError::UndefinedSymbol { suggestion, .. }
+            | AssemblerError::InvalidOpcode { suggestion, .. }
+            | AssemblerError::MacroNotFound { suggestion, .. } => suggestion.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// stable code of the kind of error (E03xx), kept when messages are reworded
+    pub fn code(&self) -> &'static str {
+        match self {
+            AssemblerError::UndefinedSymbol { .. } => "E0301",
+            AssemblerError::DuplicateSymbol { .. } => "E0302",
+            AssemblerError::CircularConstant { .. } => "E0303",
+            AssemblerError::InvalidOpcode { .. } => "E0304",
+            AssemblerError::InvalidNumber { .. } => "E0305",
+            AssemblerError::AddressOutOfBounds { .. } => "E0306",
+            AssemblerError::RelativeOutOfRange { .. } => "E0307",
+            AssemblerError::InvalidRegister { .. } => "E0308",
+            AssemblerError::InvalidAddressingMode { .. } => "E0309",
+            AssemblerError::MacroNotFound { .. } => "E0310",
+            AssemblerError::MacroParameterMismatch { .. } => "E0311",
+            AssemblerError::InvalidMacroArgument { .. } => "E0312",
+            AssemblerError::DuplicateMacro { .. } => "E0313",
+            AssemblerError::RecursiveMacro { .. } => "E0314",
+            AssemblerError::MacroDepthExceeded { .. } => "E0315",
+            AssemblerError::InvalidDirectiveArguments { .. } => "E0316",
+            AssemblerError::ExpressionOutOfRange { .. } => "E0317",
+            AssemblerError::DivisionByZero { .. } => "E0318",
+            AssemblerError::InvalidRelocation { .. } => "E0319",
+            AssemblerError::SectionOverlap { .. } => "E0320",
+            AssemblerError::MemoryOverflow { .. } => "E0321",
+            AssemblerError::ExtendedInstructionNotEnabled { .. } => "E0322",
+            AssemblerError::BitwiseInstructionNotEnabled { .. } => "E0323",
+            AssemblerError::InstructionNotInProfile { .. } => "E0324",
+            AssemblerError::UnknownIsaProfile { .. } => "E0325",
+            AssemblerError::DuplicateIsaDirective { .. } => "E0326",
+            AssemblerError::FarFormNotInProfile { .. } => "E0327",
+            AssemblerError::IsaProfileNotAllowed { .. } => "E0328",
+            AssemblerError::AddressOverlap { .. } => "E0329",
+        }
+    }
+}
+
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(name) => format!(" (did you mean {}?)", name),
+        None => String::new(),
+    }
+}
+
+/// errors of linking object files, modules are named as given to the linker
+#[derive(Error, Debug, PartialEq)]
+pub enum LinkError {
+    #[error("Undefined symbol: {symbol} imported by {module}")]
+    UndefinedSymbol { symbol: String, module: String },
+    #[error("Duplicate symbol: {symbol} exported by {first} and {second}")]
+    DuplicateSymbol { symbol: String, first: String, second: String },
+    #[error("Invalid relocation at {offset} in {module}")]
+    InvalidRelocation { offset: u16, module: String },
+    #[error("Memory overflow: linked program takes {size} words (max 2048)")]
+    MemoryOverflow { size: usize },
+}
+
+impl LinkError {
+    /// E04xx, see AssemblerError::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            LinkError::UndefinedSymbol { .. } => "E0401",
+            LinkError::DuplicateSymbol { .. } => "E0402",
+            LinkError::InvalidRelocation { .. } => "E0403",
+            LinkError::MemoryOverflow { .. } => "E0404",
+        }
+    }
+}
+
+/// "address 5" or "addresses 5-9"
+fn address_range(start: u16, last: u16) -> String {
+    if start == last {
+        format!("address {}", start)
+    } else {
+        format!("addresses {}-{}", start, last)
+    }
 }
@@ -3,78 +3,343 @@
 use crate::error::AssemblerError;
 use crate::symbol_table::SymbolTable;
 use crate::macro_processor::MacroProcessor;
+use crate::conditional::ConditionalProcessor;
 use crate::instruction::InstructionAssembler;
 use crate::operand::OperandResolver;
 use crate::directive::DirectiveProcessor;
-use crate::passes::{FirstPass, SecondPass, ThirdPass};
-use parseid::ast::Program;
+use crate::passes::{FirstPass, SecondPass, ThirdPass, LintPass};
+use crate::debug_info::DebugInfo;
+use crate::layout::Segment;
+use crate::lint::{Lint, Warning};
+use crate::object::{ObjectFile, ExportedSymbol, Relocation, RelocationTarget};
+use crate::relocation::Relocator;
+use crate::suggestion::closest;
+use crate::types::SymbolType;
+use parseid::ast::{Program, ProgramElement};
+use parseid::{is_export_directive, is_import_directive};
+use asmodeus_shared::{IsaProfile, IsaExtension};
+use lexariel::KEYWORDS;
+use std::collections::{BTreeMap, HashSet};
+
+/// relocations, exported and imported symbols of an object file
+type Linkage = (Vec<Relocation>, Vec<ExportedSymbol>, Vec<String>);
 
 pub struct Assembler {
     symbol_table: SymbolTable,
     macro_processor: MacroProcessor,
+    conditional_processor: ConditionalProcessor,
     instruction_assembler: InstructionAssembler,
     operand_resolver: OperandResolver,
     directive_processor: DirectiveProcessor,
-    memory: Vec<u16>,
-    current_address: u16,
+    profile: IsaProfile,
+    /// the profile was chosen from outside (--isa), ISA directives may only narrow it
+    profile_locked: bool,
+    /// NAME=value given from outside, override STAŁA NAME in the source
+    definitions: BTreeMap<String, i32>,
+    /// where the sections of the last assembled program were placed
+    segments: Vec<Segment>,
+    /// lints checked after a program assembles
+    lints: HashSet<Lint>,
+    /// what they found in the last assembled program
+    warnings: Vec<Warning>,
 }
 
 impl Assembler {
     pub fn new() -> Self {
-        Self {
-            symbol_table: SymbolTable::new(),
-            macro_processor: MacroProcessor::new(),
-            instruction_assembler: InstructionAssembler::new(),
-            operand_resolver: OperandResolver::new(),
-            directive_processor: DirectiveProcessor::new(),
-            memory: vec![0; 2048],
-            current_address: 0,
-        }
+        Self::new_with_profile(IsaProfile::default())
     }
 
     pub fn new_with_extended(extended_mode: bool) -> Self {
+        if extended_mode {
+            Self::new_with_profile(IsaProfile::default().with(IsaExtension::MulDiv))
+        } else {
+            Self::new_with_profile(IsaProfile::default())
+        }
+    }
+
+    pub fn new_with_profile(profile: IsaProfile) -> Self {
         Self {
             symbol_table: SymbolTable::new(),
             macro_processor: MacroProcessor::new(),
-            instruction_assembler: InstructionAssembler::new_with_extended(extended_mode),
+            conditional_processor: ConditionalProcessor::new(),
+            instruction_assembler: InstructionAssembler::new_with_profile(profile),
             operand_resolver: OperandResolver::new(),
             directive_processor: DirectiveProcessor::new(),
-            memory: vec![0; 2048],
-            current_address: 0,
+            profile,
+            profile_locked: false,
+            definitions: BTreeMap::new(),
+            segments: Vec::new(),
+            lints: Lint::ALL.into_iter().filter(|lint| lint.is_enabled_by_default()).collect(),
+            warnings: Vec::new(),
         }
     }
 
+    /// profile of the last assembled program (ISA directive overrides the one given at construction)
+    pub fn profile(&self) -> IsaProfile {
+        self.instruction_assembler.profile()
+    }
+
+    /// keeps programs within the profile given at construction: an ISA directive adding
+    /// an extension to it is an error, one leaving extensions out still applies
+    pub fn lock_profile(&mut self) {
+        self.profile_locked = true;
+    }
+
+    /// runs of words of the last assembled program, in the order their sections were started;
+    /// the machine code holds zeros between them
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// warnings about the last assembled program, ordered by line; they never stop it from assembling
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// turns a lint on, the ones reported as info are off unless asked for
+    pub fn warn(&mut self, lint: Lint) {
+        self.lints.insert(lint);
+    }
+
+    /// turns a lint off
+    pub fn allow(&mut self, lint: Lint) {
+        self.lints.remove(&lint);
+    }
+
+    /// constant visible to JEŻELI conditions and the program, kept across assemble calls
+    pub fn define_constant(&mut self, name: impl Into<String>, value: i32) {
+        self.definitions.insert(name.into(), value);
+    }
+
+    /// how deep macro calls may be nested, DEFAULT_MAX_MACRO_DEPTH unless set
+    pub fn set_max_macro_depth(&mut self, max_depth: usize) {
+        self.macro_processor.set_max_depth(max_depth);
+    }
+
     pub fn assemble(&mut self, program: &Program) -> Result<Vec<u16>, AssemblerError> {
+        self.assemble_with_debug_info(program).map(|(machine_code, _)| machine_code)
+    }
+
+    /// machine code and where each of its words came from, lines as numbered in the program
+    pub fn assemble_with_debug_info(&mut self, program: &Program) -> Result<(Vec<u16>, DebugInfo), AssemblerError> {
+        self.assemble_collecting_errors(program).map_err(|mut errors| errors.remove(0))
+    }
+
+    /// like assemble_with_debug_info, but reports every error found, ordered by line
+    pub fn assemble_collecting_errors(&mut self, program: &Program) -> Result<(Vec<u16>, DebugInfo), Vec<AssemblerError>> {
+        let (machine_code, debug_info, _) = self.assemble_module(program, false)?;
+        Ok((machine_code, debug_info))
+    }
+
+    /// module for the Linker: code assembled at address 0, the words to patch when it is placed
+    /// elsewhere and the symbols shared with other modules through EKSPORTUJ and IMPORTUJ
+    pub fn assemble_object(&mut self, program: &Program) -> Result<ObjectFile, AssemblerError> {
+        self.assemble_object_collecting_errors(program).map_err(|mut errors| errors.remove(0))
+    }
+
+    /// like assemble_object, but reports every error found, ordered by line
+    pub fn assemble_object_collecting_errors(&mut self, program: &Program) -> Result<ObjectFile, Vec<AssemblerError>> {
+        let (code, _, linkage) = self.assemble_module(program, true)?;
+        let (relocations, exports, imports) = linkage.unwrap_or_default();
+        Ok(ObjectFile {
+            code,
+            relocations,
+            exports,
+            imports,
+        })
+    }
+
+    /// relocations, exports and imports are collected for object files only;
+    /// errors ordered by line, with what misspelled names were probably meant to be
+    fn assemble_module(&mut self, program: &Program, object: bool) -> Result<(Vec<u16>, DebugInfo, Option<Linkage>), Vec<AssemblerError>> {
+        self.run_passes(program, object).map_err(|errors| self.with_suggestions(Self::by_line(errors)))
+    }
+
+    /// every pass goes on past the elements in error, the errors are in the order they were found
+    fn run_passes(&mut self, program: &Program, object: bool) -> Result<(Vec<u16>, DebugInfo, Option<Linkage>), Vec<AssemblerError>> {
         self.reset();
+        let mut errors = Vec::new();
+
+        // first pass: select conditional branches, collect macro definitions and expand macro calls
+        let (expanded_program, origins) = FirstPass::execute(&mut self.macro_processor, &self.conditional_processor, &self.definitions, program, &mut errors)
+            .map_err(|e| vec![e])?;
 
-        // first pass: collect macro definitions and expand macro calls
-        let expanded_program = FirstPass::execute(&mut self.macro_processor, program)?;
+        let profile = match self.directive_processor.isa_profile(&expanded_program) {
+            Ok(Some((profile, line))) if self.profile_locked && !self.profile.includes(profile) => {
+                errors.push(AssemblerError::IsaProfileNotAllowed {
+                    profile: profile.to_string(),
+                    allowed: self.profile.to_string(),
+                    line,
+                });
+                self.profile
+            }
+            Ok(Some((profile, _))) => profile,
+            Ok(None) => self.profile,
+            Err(e) => {
+                errors.push(e);
+                self.profile
+            }
+        };
+        self.instruction_assembler = InstructionAssembler::new_with_profile(profile);
 
-        // second pass: build symbol table
-        SecondPass::execute(&mut self.symbol_table, &mut self.current_address, &expanded_program)?;
+        // addresses of other modules and of this one are known only once linked
+        let relocator = Relocator::new(&expanded_program);
+        let relocated = if object {
+            relocator.relocated_instructions(&expanded_program, &mut errors)
+        } else {
+            Self::check_no_imports(&expanded_program, &mut errors);
+            HashSet::new()
+        };
+
+        // second pass: build symbol table and place sections
+        let layout = match SecondPass::execute(&mut self.symbol_table, &self.operand_resolver, &self.directive_processor, &relocated, &expanded_program, &mut errors) {
+            Ok(layout) => layout,
+            Err(e) => {
+                errors.push(e);
+                return Err(errors);
+            }
+        };
 
         // third pass: generate machine code
-        self.current_address = 0;
-        ThirdPass::execute(
-            &mut self.memory,
-            &mut self.current_address,
+        let machine_code = ThirdPass::execute(
             &self.symbol_table,
             &self.instruction_assembler,
             &self.operand_resolver,
             &self.directive_processor,
-            &expanded_program
-        )?;
+            &layout,
+            &expanded_program,
+            &mut errors
+        );
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let linkage = if object {
+            let relocations = relocator.relocations(&expanded_program, &layout).map_err(|e| vec![e])?;
+            let (exports, imports) = self.shared_symbols(&relocator, &expanded_program).map_err(|e| vec![e])?;
+            Some((relocations, exports, imports))
+        } else {
+            None
+        };
+
+        let debug_info = DebugInfo::build(
+            &expanded_program,
+            &origins,
+            &layout,
+            &self.symbol_table,
+            self.macro_processor.expansions()
+        );
+        self.warnings = LintPass::execute(&self.lints, &expanded_program, &origins, &layout, &machine_code, &debug_info, object);
+        self.segments = layout.segments;
+        Ok((machine_code, debug_info, linkage))
+    }
+
+    /// in the order of the lines they point at
+    fn by_line(mut errors: Vec<AssemblerError>) -> Vec<AssemblerError> {
+        errors.sort_by_key(|error| error.line().unwrap_or(usize::MAX));
+        errors
+    }
+
+    /// what misspelled names were probably meant to be: symbols of the program and its macros;
+    /// a word the parser took for a macro call but closest to a mnemonic (POBB) is a misspelled instruction
+    fn with_suggestions(&self, errors: Vec<AssemblerError>) -> Vec<AssemblerError> {
+        errors.into_iter()
+            .map(|error| match error {
+                AssemblerError::UndefinedSymbol { symbol, line, .. } => {
+                    let suggestion = closest(&symbol, self.symbol_table.iter().map(|(name, _)| name));
+                    AssemblerError::UndefinedSymbol { symbol, line, suggestion }
+                }
+                AssemblerError::MacroNotFound { name, line, .. } => {
+                    match closest(&name, self.macro_processor.names().chain(KEYWORDS.iter().copied())) {
+                        Some(mnemonic) if KEYWORDS.contains(&mnemonic.as_str()) => {
+                            AssemblerError::InvalidOpcode { opcode: name, line, suggestion: Some(mnemonic) }
+                        }
+                        suggestion => AssemblerError::MacroNotFound { name, line, suggestion },
+                    }
+                }
+                error => error,
+            })
+            .collect()
+    }
+
+    /// IMPORTUJ leaves symbols for the linker to fill in
+    fn check_no_imports(elements: &[ProgramElement], errors: &mut Vec<AssemblerError>) {
+        for element in elements {
+            if let ProgramElement::Directive(dir) = element {
+                if is_import_directive(&dir.name) {
+                    errors.push(AssemblerError::InvalidDirectiveArguments {
+                        directive: dir.name.clone(),
+                        reason: "symbols can only be imported when assembling an object file".to_string(),
+                        line: dir.line,
+                    });
+                }
+            }
+        }
+    }
+
+    /// symbols named by EKSPORTUJ, in order, and the ones named by IMPORTUJ
+    fn shared_symbols(&self, relocator: &Relocator, elements: &[ProgramElement]) -> Result<(Vec<ExportedSymbol>, Vec<String>), AssemblerError> {
+        let mut exports: Vec<ExportedSymbol> = Vec::new();
+        let mut imports: Vec<String> = Vec::new();
+
+        for element in elements {
+            let ProgramElement::Directive(dir) = element else {
+                continue;
+            };
+            if is_import_directive(&dir.name) {
+                for name in &dir.arguments {
+                    if !imports.contains(name) {
+                        imports.push(name.clone());
+                    }
+                }
+                continue;
+            }
+            if !is_export_directive(&dir.name) {
+                continue;
+            }
+
+            for name in &dir.arguments {
+                let symbol = self.symbol_table.resolve(name).ok_or_else(|| AssemblerError::UndefinedSymbol {
+                    symbol: name.clone(),
+                    line: dir.line,
+                    suggestion: None,
+                })?;
+                if symbol.symbol_type == SymbolType::External {
+                    return Err(AssemblerError::InvalidDirectiveArguments {
+                        directive: dir.name.clone(),
+                        reason: format!("{} is imported and cannot be exported", name),
+                        line: dir.line,
+                    });
+                }
+                let relocatable = match relocator.symbol_target(name, dir.line)? {
+                    None => false,
+                    Some(RelocationTarget::Module) => true,
+                    // STAŁA alias = ext + 1
+                    Some(RelocationTarget::Symbol(_)) => {
+                        return Err(AssemblerError::InvalidRelocation {
+                            expression: name.clone(),
+                            line: dir.line,
+                        });
+                    }
+                };
+                if !exports.iter().any(|export| export.name == *name) {
+                    exports.push(ExportedSymbol {
+                        name: name.clone(),
+                        value: symbol.value,
+                        relocatable,
+                    });
+                }
+            }
+        }
 
-        // return only the used portion of memory
-        let used_memory = self.current_address as usize;
-        Ok(self.memory[0..used_memory].to_vec())
+        Ok((exports, imports))
     }
 
     fn reset(&mut self) {
         self.symbol_table.clear();
         self.macro_processor.clear();
-        self.memory.fill(0);
-        self.current_address = 0;
+        self.segments.clear();
+        self.warnings.clear();
     }
 }
 
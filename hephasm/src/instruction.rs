@@ -1,27 +1,36 @@
 //! instruction assembly and opcode mapping
 
 use crate::error::AssemblerError;
+use crate::suggestion::closest;
 use parseid::ast::{Instruction, AddressingMode};
-use asmodeus_shared::{addressing_mode_bits, encode_instruction};
+use asmodeus_shared::{addressing_mode_bits, encode_instruction, encode_far_instruction, requires_far_form, register_operations, shift_operations};
+use asmodeus_shared::{Opcode, IsaProfile, IsaExtension};
+use lexariel::KEYWORDS;
 
 pub struct InstructionAssembler {
-    extended_mode: bool,
+    profile: IsaProfile,
 }
 
 impl InstructionAssembler {
     pub fn new() -> Self {
         Self {
-            extended_mode: false,
+            profile: IsaProfile::default(),
         }
     }
 
-    pub fn new_with_extended(extended_mode: bool) -> Self {
+    pub fn new_with_profile(profile: IsaProfile) -> Self {
         Self {
-            extended_mode,
+            profile,
         }
     }
 
-    pub fn assemble_instruction(&self, instruction: &Instruction, argument: u16) -> Result<u16, AssemblerError> {
+    pub fn profile(&self) -> IsaProfile {
+        self.profile
+    }
+
+    /// one word, or a far prefix followed by the instruction when the argument needs more than 8 bits
+    /// or the far form is asked for
+    pub fn assemble_instruction(&self, instruction: &Instruction, argument: u16, far: bool) -> Result<Vec<u16>, AssemblerError> {
         let opcode = self.get_opcode(&instruction.opcode, instruction.line)?;
         
         let addressing_mode_bits = if let Some(operand) = &instruction.operand {
@@ -34,76 +43,182 @@ impl InstructionAssembler {
                 AddressingMode::RegisterIndirect => addressing_mode_bits::REGISTER_INDIRECT,
                 AddressingMode::BaseRegister { .. } => addressing_mode_bits::BASE_REGISTER,
                 AddressingMode::Relative => addressing_mode_bits::RELATIVE,
-                AddressingMode::Indexed { .. } => addressing_mode_bits::DIRECT,
+                // tab[R1] is R1[tab] written the other way round
+                AddressingMode::Indexed { .. } => addressing_mode_bits::BASE_REGISTER,
             }
         } else {
             addressing_mode_bits::DIRECT
         };
         
-        if argument > 255 {
+        // register operations carry sub-operation and register in the argument
+        let argument = match self.get_register_operation(&instruction.opcode) {
+            Some(operation) => {
+                match &instruction.operand {
+                    Some(operand) if operand.addressing_mode == AddressingMode::Register => {}
+                    operand => {
+                        return Err(AssemblerError::InvalidAddressingMode {
+                            instruction: instruction.opcode.clone(),
+                            mode: operand.as_ref()
+                                .map(|op| op.addressing_mode.to_string())
+                                .unwrap_or_else(|| "None".to_string()),
+                            line: instruction.line,
+                        });
+                    }
+                }
+                ((operation as u16) << 3) | argument
+            }
+            None => argument,
+        };
+
+        // shift operations carry sub-operation and shift count in the argument
+        let argument = match self.get_shift_operation(&instruction.opcode) {
+            Some(shift_operations::NOT) => (shift_operations::NOT as u16) << 4,
+            Some(operation) => {
+                match &instruction.operand {
+                    Some(operand) if operand.addressing_mode == AddressingMode::Immediate => {}
+                    operand => {
+                        return Err(AssemblerError::InvalidAddressingMode {
+                            instruction: instruction.opcode.clone(),
+                            mode: operand.as_ref()
+                                .map(|op| op.addressing_mode.to_string())
+                                .unwrap_or_else(|| "None".to_string()),
+                            line: instruction.line,
+                        });
+                    }
+                }
+                if argument > 15 {
+                    return Err(AssemblerError::InvalidNumber {
+                        value: argument.to_string(),
+                        line: instruction.line,
+                    });
+                }
+                ((operation as u16) << 4) | argument
+            }
+            None => argument,
+        };
+
+        // register numbers must fit in a single word, addresses in memory,
+        // a base register is packed above its address by the operand resolver
+        let max_argument = match addressing_mode_bits {
+            addressing_mode_bits::IMMEDIATE | addressing_mode_bits::BASE_REGISTER => u16::MAX,
+            addressing_mode_bits::REGISTER
+            | addressing_mode_bits::REGISTER_INDIRECT => 255,
+            _ => 2047,
+        };
+
+        if argument > max_argument {
             return Err(AssemblerError::AddressOutOfBounds {
                 address: argument,
                 line: instruction.line,
+                column: instruction.operand.as_ref().map(|operand| operand.column),
             });
         }
 
-        Ok(encode_instruction(opcode, addressing_mode_bits, argument))
+        if far || requires_far_form(argument) {
+            if !self.profile.allows(Opcode::DAL) {
+                return Err(AssemblerError::FarFormNotInProfile {
+                    instruction: instruction.opcode.clone(),
+                    argument,
+                    profile: self.profile.to_string(),
+                    line: instruction.line,
+                });
+            }
+            Ok(encode_far_instruction(opcode, addressing_mode_bits, argument).to_vec())
+        } else {
+            Ok(vec![encode_instruction(opcode, addressing_mode_bits, argument)])
+        }
     }
 
     pub fn get_opcode(&self, instruction: &str, line: usize) -> Result<u8, AssemblerError> {
-        match instruction.to_uppercase().as_str() {
-            "DOD" => Ok(0b00001),
-            "ODE" => Ok(0b00010),
-            "ŁAD" | "LAD" => Ok(0b00011),
-            "POB" => Ok(0b00100),
-            "SOB" => Ok(0b00101),
-            "SOM" => Ok(0b00110),
-            "SOZ" => Ok(0b10000),
-            "STP" => Ok(0b00111),
-            "DNS" => Ok(0b01000),
-            "PZS" => Ok(0b01001),
-            "SDP" => Ok(0b01010),
-            "CZM" => Ok(0b01011),
-            "MSK" => Ok(0b01100),
-            "PWR" => Ok(0b01101),
-            "WPR" | "WEJSCIE" => Ok(0b01110),
-            "WYJ" | "WYJSCIE" => Ok(0b01111),
+        let opcode = match instruction.to_uppercase().as_str() {
+            "DOD" => 0b00001,
+            "ODE" => 0b00010,
+            "ŁAD" | "LAD" => 0b00011,
+            "POB" => 0b00100,
+            "SOB" => 0b00101,
+            "SOM" => 0b00110,
+            "SOZ" => 0b10000,
+            "SOC" => 0b11011,
+            "SOV" => 0b11100,
+            "SOP" => 0b11101,
+            "SNZ" => 0b11110,
+            "STP" => 0b00111,
+            "DNS" => 0b01000,
+            "PZS" => 0b01001,
+            "SDP" => 0b01010,
+            "CZM" => 0b01011,
+            "MSK" => 0b01100,
+            "PWR" => 0b01101,
+            "WPR" | "WEJSCIE" => 0b01110,
+            "WYJ" | "WYJSCIE" => 0b01111,
+
+            // register operations
+            "INR" | "DER" => 0b10100,
+
+            // subroutines
+            "WYW" => 0b10101,
+            "POW" => 0b10110,
 
             // extended opcodes
-            "MNO" => {
-                if self.extended_mode {
-                    Ok(0b10001)
-                } else {
-                    Err(AssemblerError::ExtendedInstructionNotEnabled {
-                        instruction: instruction.to_string(),
-                        line,
-                    })
-                }
-            },
-            "DZI" => {
-                if self.extended_mode {
-                    Ok(0b10010)
-                } else {
-                    Err(AssemblerError::ExtendedInstructionNotEnabled {
-                        instruction: instruction.to_string(),
-                        line,
-                    })
-                }
-            },
-            "MOD" => {
-                if self.extended_mode {
-                    Ok(0b10011)
-                } else {
-                    Err(AssemblerError::ExtendedInstructionNotEnabled {
-                        instruction: instruction.to_string(),
-                        line,
-                    })
-                }
-            },
-            _ => Err(AssemblerError::InvalidOpcode {
-                opcode: instruction.to_string(),
+            "MNO" => 0b10001,
+            "DZI" => 0b10010,
+            "MOD" => 0b10011,
+
+            // bitwise opcodes
+            "AND" => 0b10111,
+            "OR" => 0b11000,
+            "XOR" => 0b11001,
+            "NOT" | "SHL" | "SAL" | "SHR" | "SAR" => 0b11010,
+
+            _ => {
+                return Err(AssemblerError::InvalidOpcode {
+                    opcode: instruction.to_string(),
+                    line,
+                    suggestion: closest(instruction, KEYWORDS.iter().copied()),
+                });
+            }
+        };
+
+        self.check_profile(opcode, instruction, line)?;
+        Ok(opcode)
+    }
+
+    /// opcodes outside of the selected instruction set profile are rejected
+    fn check_profile(&self, opcode: u8, instruction: &str, line: usize) -> Result<(), AssemblerError> {
+        let extension = match Opcode::from_u8(opcode).and_then(Opcode::extension) {
+            Some(extension) if !self.profile.has(extension) => extension,
+            _ => return Ok(()),
+        };
+
+        let instruction = instruction.to_string();
+        Err(match extension {
+            IsaExtension::MulDiv => AssemblerError::ExtendedInstructionNotEnabled { instruction, line },
+            IsaExtension::Bits => AssemblerError::BitwiseInstructionNotEnabled { instruction, line },
+            IsaExtension::Core => AssemblerError::InstructionNotInProfile {
+                instruction,
+                profile: self.profile.to_string(),
                 line,
-            }),
+            },
+        })
+    }
+
+    /// sub-operation for instructions sharing the register operations opcode
+    fn get_register_operation(&self, instruction: &str) -> Option<u8> {
+        match instruction.to_uppercase().as_str() {
+            "INR" => Some(register_operations::INR),
+            "DER" => Some(register_operations::DER),
+            _ => None,
+        }
+    }
+
+    /// sub-operation for instructions sharing the shift operations opcode
+    fn get_shift_operation(&self, instruction: &str) -> Option<u8> {
+        match instruction.to_uppercase().as_str() {
+            "SHL" | "SAL" => Some(shift_operations::SHL),
+            "SHR" => Some(shift_operations::SHR),
+            "SAR" => Some(shift_operations::SAR),
+            "NOT" => Some(shift_operations::NOT),
+            _ => None,
         }
     }
 }
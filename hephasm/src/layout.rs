@@ -0,0 +1,76 @@
+//! where the elements and sections of a program are placed in memory
+
+use crate::error::AssemblerError;
+use std::collections::HashSet;
+
+/// section the program starts in
+pub const DEFAULT_SECTION: &str = "kod";
+
+/// contiguous run of words of one section
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub section: String,
+    pub start: u16,
+    /// address after the last word
+    pub end: u16,
+    /// line of the SEKCJA or ADRES directive starting the segment, 0 for the start of the program
+    pub line: usize,
+}
+
+impl Segment {
+    pub fn len(&self) -> usize {
+        (self.end - self.start) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// result of the second pass, followed by the third
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Layout {
+    /// start address of every element
+    pub addresses: Vec<u16>,
+    /// words of every element
+    pub sizes: Vec<u16>,
+    /// instructions taking the two-word far form
+    pub far_instructions: HashSet<usize>,
+    /// non-empty segments in the order they were started
+    pub segments: Vec<Segment>,
+}
+
+impl Layout {
+    /// address after the highest word of the program
+    pub fn end(&self) -> u16 {
+        self.segments.iter().map(|segment| segment.end).max().unwrap_or(0)
+    }
+
+    /// segments sharing an address, e.g. a section placed inside another one, or ADRES going back
+    /// over words its own section already holds
+    pub fn check_overlaps(&self) -> Result<(), AssemblerError> {
+        let mut segments: Vec<&Segment> = self.segments.iter().collect();
+        segments.sort_by_key(|segment| segment.start);
+
+        for pair in segments.windows(2) {
+            let (first, second) = (pair[0], pair[1]);
+            if second.start < first.end && first.section == second.section {
+                return Err(AssemblerError::AddressOverlap {
+                    section: first.section.clone(),
+                    start: second.start,
+                    last: first.end.min(second.end) - 1,
+                    line: first.line.max(second.line),
+                });
+            }
+            if second.start < first.end {
+                return Err(AssemblerError::SectionOverlap {
+                    first: first.section.clone(),
+                    second: second.section.clone(),
+                    address: second.start,
+                    line: first.line.max(second.line),
+                });
+            }
+        }
+        Ok(())
+    }
+}
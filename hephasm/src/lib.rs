@@ -5,17 +5,32 @@ mod error;
 mod types;
 mod symbol_table;
 mod macro_processor;
+mod conditional;
+mod debug_info;
+mod layout;
+mod lint;
+mod object;
+mod relocation;
+mod linker;
 mod instruction;
 mod operand;
 mod directive;
+mod suggestion;
 mod ascii_art;
 mod passes;
 mod hephasm;
 
-pub use error::AssemblerError;
+pub use error::{AssemblerError, LinkError};
 pub use types::{Symbol, SymbolType, ExpandedMacro};
 pub use symbol_table::SymbolTable;
+pub use debug_info::{DebugInfo, DebugSymbol, SymbolReference, SourceLocation, WordKind, MacroExpansion};
+pub use object::{ObjectFile, Relocation, RelocationKind, RelocationTarget, ExportedSymbol};
+pub use linker::Linker;
+pub use layout::{Segment, DEFAULT_SECTION};
+pub use lint::{Lint, Warning};
+pub use macro_processor::DEFAULT_MAX_MACRO_DEPTH;
 pub use hephasm::Assembler;
+pub use asmodeus_shared::{IsaProfile, IsaExtension};
 
 use parseid::ast::Program;
 
@@ -31,6 +46,12 @@ pub fn assemble_source_extended(source: &str, extended_mode: bool) -> Result<Vec
     Ok(assembler.assemble(&program)?)
 }
 
+pub fn assemble_source_with_profile(source: &str, profile: IsaProfile) -> Result<Vec<u16>, Box<dyn std::error::Error>> {
+    let program = parseid::parse_source(source)?;
+    let mut assembler = Assembler::new_with_profile(profile);
+    Ok(assembler.assemble(&program)?)
+}
+
 pub fn assemble_program(program: &Program) -> Result<Vec<u16>, AssemblerError> {
     let mut assembler = Assembler::new();
     assembler.assemble(program)
@@ -41,6 +62,11 @@ pub fn assemble_program_extended(program: &Program, extended_mode: bool) -> Resu
     assembler.assemble(program)
 }
 
+pub fn assemble_program_with_profile(program: &Program, profile: IsaProfile) -> Result<Vec<u16>, AssemblerError> {
+    let mut assembler = Assembler::new_with_profile(profile);
+    assembler.assemble(program)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ascii_art::print_hephasm_logo;
@@ -0,0 +1,133 @@
+//! linking object files into one program
+
+use crate::error::LinkError;
+use crate::object::{ObjectFile, RelocationKind, RelocationTarget};
+use asmodeus_shared::{decode_far_instruction, encode_far_instruction, is_far_prefix};
+use std::collections::HashMap;
+
+pub struct Linker {
+    modules: Vec<(String, ObjectFile)>,
+}
+
+impl Linker {
+    pub fn new() -> Self {
+        Self {
+            modules: Vec::new(),
+        }
+    }
+
+    /// modules are placed one after another in the order they are added, the first one at address 0
+    pub fn add_module(&mut self, name: impl Into<String>, object: ObjectFile) {
+        self.modules.push((name.into(), object));
+    }
+
+    /// program image of all modules, or every undefined and duplicate symbol found
+    pub fn link(&self) -> Result<Vec<u16>, Vec<LinkError>> {
+        let mut bases = Vec::with_capacity(self.modules.len());
+        let mut size = 0;
+        for (_, object) in &self.modules {
+            bases.push(size as u16);
+            size += object.code.len();
+        }
+        if size > 2048 {
+            return Err(vec![LinkError::MemoryOverflow { size }]);
+        }
+
+        let mut errors = Vec::new();
+        let symbols = self.exported_symbols(&bases, &mut errors);
+        for (name, object) in &self.modules {
+            errors.extend(object.imports.iter()
+                .filter(|symbol| !symbols.contains_key(symbol.as_str()))
+                .map(|symbol| LinkError::UndefinedSymbol {
+                    symbol: symbol.clone(),
+                    module: name.clone(),
+                }));
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut image = Vec::with_capacity(size);
+        for ((name, object), &base) in self.modules.iter().zip(&bases) {
+            let mut code = object.code.clone();
+            for relocation in &object.relocations {
+                let value = match &relocation.target {
+                    RelocationTarget::Module => base,
+                    RelocationTarget::Symbol(symbol) => match symbols.get(symbol.as_str()) {
+                        Some(&(_, value)) => value,
+                        None => {
+                            errors.push(LinkError::UndefinedSymbol {
+                                symbol: symbol.clone(),
+                                module: name.clone(),
+                            });
+                            continue;
+                        }
+                    },
+                };
+                if !Self::relocate(&mut code, relocation.offset as usize, relocation.kind, value) {
+                    errors.push(LinkError::InvalidRelocation {
+                        offset: relocation.offset,
+                        module: name.clone(),
+                    });
+                }
+            }
+            image.extend(code);
+        }
+
+        if errors.is_empty() {
+            Ok(image)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// final value of every exported symbol and the module exporting it
+    fn exported_symbols(&self, bases: &[u16], errors: &mut Vec<LinkError>) -> HashMap<&str, (&str, u16)> {
+        let mut symbols: HashMap<&str, (&str, u16)> = HashMap::new();
+        for ((name, object), &base) in self.modules.iter().zip(bases) {
+            for symbol in &object.exports {
+                if let Some(&(first, _)) = symbols.get(symbol.name.as_str()) {
+                    errors.push(LinkError::DuplicateSymbol {
+                        symbol: symbol.name.clone(),
+                        first: first.to_string(),
+                        second: name.clone(),
+                    });
+                    continue;
+                }
+                // negative constants in two's complement
+                let value = if symbol.relocatable { base.wrapping_add(symbol.value as u16) } else { symbol.value as u16 };
+                symbols.insert(symbol.name.as_str(), (name.as_str(), value));
+            }
+        }
+        symbols
+    }
+
+    /// adds value to the relocated word or far argument, false when there is none at offset
+    fn relocate(code: &mut [u16], offset: usize, kind: RelocationKind, value: u16) -> bool {
+        match kind {
+            RelocationKind::Word => match code.get_mut(offset) {
+                Some(word) => {
+                    *word = word.wrapping_add(value);
+                    true
+                }
+                None => false,
+            },
+            RelocationKind::Far => match code.get(offset..offset + 2) {
+                Some(&[prefix, instruction]) if is_far_prefix(prefix) => {
+                    let (opcode, mode, argument) = decode_far_instruction(prefix, instruction);
+                    let [prefix, instruction] = encode_far_instruction(opcode, mode, argument.wrapping_add(value));
+                    code[offset] = prefix;
+                    code[offset + 1] = instruction;
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Default for Linker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
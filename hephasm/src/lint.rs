@@ -0,0 +1,104 @@
+//! warnings about programs that assemble but probably do not do what was meant
+
+use std::fmt;
+
+/// one kind of warning, turned on and off by name (-W unused-label, -A unused-label)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    /// instructions after STP, SOB or POW that no label or jump leads to
+    UnreachableCode,
+    /// labels defined but never used
+    UnusedLabel,
+    /// an instruction other than STP, SOB or POW followed by data
+    FallIntoData,
+    /// ŁAD writing into an instruction, off by default
+    SelfModifyingCode,
+    /// immediates over 8 bits, assembled in the two-word far form
+    WideImmediate,
+    /// program without a single STP
+    MissingStop,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 6] = [
+        Self::UnreachableCode,
+        Self::UnusedLabel,
+        Self::FallIntoData,
+        Self::SelfModifyingCode,
+        Self::WideImmediate,
+        Self::MissingStop,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::UnreachableCode => "unreachable-code",
+            Self::UnusedLabel => "unused-label",
+            Self::FallIntoData => "fall-into-data",
+            Self::SelfModifyingCode => "self-modifying-code",
+            Self::WideImmediate => "wide-immediate",
+            Self::MissingStop => "missing-stop",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|lint| lint.name().eq_ignore_ascii_case(name))
+    }
+
+    /// stable code, like the E codes of errors
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::UnreachableCode => "W0301",
+            Self::UnusedLabel => "W0302",
+            Self::FallIntoData => "W0303",
+            Self::SelfModifyingCode => "W0304",
+            Self::WideImmediate => "W0305",
+            Self::MissingStop => "W0306",
+        }
+    }
+
+    /// worth knowing about but often meant, reported only when asked for
+    pub fn is_info(self) -> bool {
+        matches!(self, Self::SelfModifyingCode)
+    }
+
+    pub fn is_enabled_by_default(self) -> bool {
+        !self.is_info()
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// something a lint found in the program, which still assembles
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    pub lint: Lint,
+    pub message: String,
+    /// None for warnings about the whole program (missing STP)
+    pub line: Option<usize>,
+    /// word of the line the warning is about, a label or a mnemonic
+    pub subject: Option<String>,
+}
+
+impl Warning {
+    pub(crate) fn new(lint: Lint, message: String, line: usize, subject: &str) -> Self {
+        Self {
+            lint,
+            message,
+            line: Some(line),
+            subject: Some(subject.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{} at line {}", self.message, line),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
@@ -1,113 +1,473 @@
 //! macro processing and expansion
 
 use crate::error::AssemblerError;
+use crate::conditional::ConditionScope;
+use crate::debug_info::MacroExpansion;
+use crate::operand::RegisterParser;
 use crate::types::ExpandedMacro;
 use parseid::ast::*;
+use parseid::is_string_directive;
 use std::collections::HashMap;
 
+/// arguments each parameter stands for, one unless the parameter is variadic
+type Substitutions = HashMap<String, Vec<Operand>>;
+
+/// expanded elements with the expansion each came from
+struct Expansion {
+    elements: Vec<ProgramElement>,
+    origins: Vec<Option<usize>>,
+    /// names of the macros being expanded, outermost first
+    chain: Vec<String>,
+    /// calls that could not be expanded are left out, and the rest of the program expanded
+    errors: Vec<AssemblerError>,
+}
+
 pub struct MacroProcessor {
     macros: HashMap<String, ExpandedMacro>,
+    /// every call expanded so far, its number makes NAZWA_LOKALNA names unique
+    expansions: Vec<MacroExpansion>,
+    /// macro calls may be nested this many levels deep
+    max_depth: usize,
+    register_parser: RegisterParser,
 }
 
+/// nesting limit unless set with set_max_depth
+pub const DEFAULT_MAX_MACRO_DEPTH: usize = 64;
+
 impl MacroProcessor {
     pub fn new() -> Self {
         Self {
             macros: HashMap::new(),
+            expansions: Vec::new(),
+            max_depth: DEFAULT_MAX_MACRO_DEPTH,
+            register_parser: RegisterParser::new(),
         }
     }
 
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
     pub fn clear(&mut self) {
         self.macros.clear();
+        self.expansions.clear();
     }
 
-    pub fn expand_macros(&mut self, program: &Program) -> Result<Vec<ProgramElement>, AssemblerError> {
-        let mut expanded = Vec::new();
+    /// names of the macros defined so far
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.macros.keys().map(String::as_str)
+    }
+
+    /// calls expanded by the last expand_macros, indexed by the origins it returned
+    pub fn expansions(&self) -> &[MacroExpansion] {
+        &self.expansions
+    }
+
+    /// elements with all calls expanded, and for each the expansion it came from;
+    /// conditions in macro bodies are decided after substitution, with the constants met so far
+    pub fn expand_macros(
+        &mut self,
+        program: &Program,
+        scope: &mut ConditionScope,
+        errors: &mut Vec<AssemblerError>
+    ) -> (Vec<ProgramElement>, Vec<Option<usize>>) {
+        let mut output = Expansion {
+            elements: Vec::new(),
+            origins: Vec::new(),
+            chain: Vec::new(),
+            errors: Vec::new(),
+        };
 
+        // definitions first, a macro can be called above the place it is defined
         for element in &program.elements {
-            match element {
-                ProgramElement::MacroDefinition(macro_def) => {
-                    // store macro definition
-                    self.macros.insert(
-                        macro_def.name.clone(),
-                        ExpandedMacro {
-                            parameters: macro_def.parameters.clone(),
-                            body: macro_def.body.clone(),
-                        },
-                    );
+            if let ProgramElement::MacroDefinition(macro_def) = element {
+                if self.macros.contains_key(&macro_def.name) {
+                    output.errors.push(AssemblerError::DuplicateMacro {
+                        name: macro_def.name.clone(),
+                        line: macro_def.line,
+                    });
+                    continue;
                 }
+                self.macros.insert(
+                    macro_def.name.clone(),
+                    ExpandedMacro {
+                        parameters: macro_def.parameters.clone(),
+                        defaults: macro_def.defaults.clone(),
+                        variadic: macro_def.variadic,
+                        body: macro_def.body.clone(),
+                    },
+                );
+            }
+        }
+
+        self.expand_elements(&program.elements, None, scope, &mut output);
+        errors.extend(output.errors);
+        (output.elements, output.origins)
+    }
+
+    /// expands calls, including the ones in macro bodies and in the branches conditions select
+    fn expand_elements(
+        &mut self,
+        elements: &[ProgramElement],
+        origin: Option<usize>,
+        scope: &mut ConditionScope,
+        output: &mut Expansion
+    ) {
+        for element in elements {
+            match element {
+                ProgramElement::MacroDefinition(_) => {}
                 ProgramElement::MacroCall(macro_call) => {
-                    let expanded_elements = self.expand_macro_call(macro_call)?;
-                    expanded.extend(expanded_elements);
+                    let body = match self.expand_macro_call(macro_call, &output.chain, origin) {
+                        Ok(body) => body,
+                        Err(e) => {
+                            output.errors.push(e);
+                            continue;
+                        }
+                    };
+                    let expansion = Some(self.expansions.len() - 1);
+                    output.chain.push(macro_call.name.clone());
+                    self.expand_elements(&body, expansion, scope, output);
+                    output.chain.pop();
                 }
+                ProgramElement::Conditional(conditional) => match scope.select(conditional) {
+                    Ok(branch) => self.expand_elements(branch, origin, scope, output),
+                    // neither branch is assembled
+                    Err(e) => output.errors.push(e),
+                },
                 _ => {
-                    // copy other elements as-is
-                    expanded.push(element.clone());
+                    // copy other elements as-is, a STAŁA the conditions cannot use is reported again by the second pass
+                    match scope.keep(element) {
+                        Ok(false) => {}
+                        Ok(true) | Err(_) => {
+                            output.elements.push(element.clone());
+                            output.origins.push(origin);
+                        }
+                    }
                 }
             }
         }
-
-        Ok(expanded)
     }
 
-    fn expand_macro_call(&self, macro_call: &MacroCall) -> Result<Vec<ProgramElement>, AssemblerError> {
+    /// body of one call with its arguments substituted, calls in it not expanded yet;
+    /// the call is recorded as the last expansion
+    fn expand_macro_call(&mut self, macro_call: &MacroCall, chain: &[String], parent: Option<usize>) -> Result<Vec<ProgramElement>, AssemblerError> {
+        if let Some(start) = chain.iter().position(|name| *name == macro_call.name) {
+            return Err(AssemblerError::RecursiveMacro {
+                cycle: Self::trace(&chain[start..], &macro_call.name),
+                line: macro_call.line,
+            });
+        }
+        if chain.len() >= self.max_depth {
+            return Err(AssemblerError::MacroDepthExceeded {
+                chain: Self::trace(chain, &macro_call.name),
+                limit: self.max_depth,
+                line: macro_call.line,
+            });
+        }
+
         let macro_def = self.macros.get(&macro_call.name)
             .ok_or_else(|| AssemblerError::MacroNotFound {
                 name: macro_call.name.clone(),
                 line: macro_call.line,
+                suggestion: None,
             })?;
 
-        if macro_def.parameters.len() != macro_call.arguments.len() {
+        // parameters without a default have to be given, extra arguments only go to a variadic one
+        let fixed = macro_def.parameters.len() - usize::from(macro_def.variadic);
+        let required = macro_def.defaults[..fixed].iter().take_while(|default| default.is_none()).count();
+        let found = macro_call.operands.len();
+        if found < required || (found > fixed && !macro_def.variadic) {
             return Err(AssemblerError::MacroParameterMismatch {
                 name: macro_call.name.clone(),
-                expected: macro_def.parameters.len(),
-                found: macro_call.arguments.len(),
+                expected: if found < required { required } else { fixed },
+                found,
                 line: macro_call.line,
             });
         }
 
         // parameter substitution map
-        let mut substitutions = HashMap::new();
-        for (param, arg) in macro_def.parameters.iter().zip(macro_call.arguments.iter()) {
-            substitutions.insert(param.clone(), arg.clone());
+        let mut substitutions = Substitutions::new();
+        for (index, param) in macro_def.parameters.iter().enumerate() {
+            let arguments = if macro_def.variadic && index == fixed {
+                macro_call.operands.get(fixed..).unwrap_or_default().to_vec()
+            } else {
+                macro_call.operands.get(index)
+                    .or(macro_def.defaults[index].as_ref())
+                    .cloned()
+                    .into_iter()
+                    .collect()
+            };
+            substitutions.insert(param.clone(), arguments);
+        }
+
+        // names listed by NAZWA_LOKALNA get a new name on every expansion: loop -> loop@1, loop@2, ...
+        let number = self.expansions.len() + 1;
+        for element in &macro_def.body {
+            if let ProgramElement::Directive(dir) = element {
+                if Self::is_local_names_directive(&dir.name) {
+                    if dir.arguments.is_empty() {
+                        return Err(AssemblerError::InvalidDirectiveArguments {
+                            directive: dir.name.clone(),
+                            reason: "expected at least one name".to_string(),
+                            line: dir.line,
+                        });
+                    }
+                    for name in &dir.arguments {
+                        substitutions.insert(name.clone(), vec![Operand {
+                            addressing_mode: AddressingMode::Direct,
+                            value: format!("{}@{}", name, number),
+                            expression: None,
+                            column: dir.column,
+                        }]);
+                    }
+                }
+            }
         }
 
         // substitute parameters in macro body
         let mut expanded = Vec::new();
         for element in &macro_def.body {
-            expanded.push(self.substitute_parameters(element, &substitutions));
+            if matches!(element, ProgramElement::Directive(dir) if Self::is_local_names_directive(&dir.name)) {
+                continue;
+            }
+            expanded.push(self.substitute_parameters(element, &substitutions)?);
         }
 
+        self.expansions.push(MacroExpansion {
+            name: macro_call.name.clone(),
+            file: None,
+            line: macro_call.line,
+            parent,
+        });
         Ok(expanded)
     }
 
-    fn substitute_parameters(&self, element: &ProgramElement, substitutions: &HashMap<String, String>) -> ProgramElement {
-        match element {
+    /// outer -> inner -> called
+    fn trace(chain: &[String], called: &str) -> String {
+        chain.iter()
+            .map(String::as_str)
+            .chain([called])
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+
+    fn is_local_names_directive(name: &str) -> bool {
+        name.to_uppercase() == "NAZWA_LOKALNA"
+    }
+
+    fn substitute_parameters(&self, element: &ProgramElement, substitutions: &Substitutions) -> Result<ProgramElement, AssemblerError> {
+        let substituted = match element {
+            ProgramElement::LabelDefinition(label) => {
+                let mut new_label = label.clone();
+                new_label.name = self.substitute_name(&label.name, substitutions, label.line)?;
+                ProgramElement::LabelDefinition(new_label)
+            }
             ProgramElement::Instruction(inst) => {
                 let mut new_inst = inst.clone();
                 if let Some(operand) = &mut new_inst.operand {
-                    operand.value = self.substitute_in_string(&operand.value, substitutions);
+                    self.substitute_in_operand(operand, substitutions, inst.line)?;
                 }
                 ProgramElement::Instruction(new_inst)
             }
+            ProgramElement::MacroCall(call) => {
+                // nested call passing on parameters, a variadic one as all of its arguments
+                let mut operands = Vec::new();
+                for operand in &call.operands {
+                    match Self::parameter(operand).and_then(|name| substitutions.get(name)) {
+                        Some(arguments) => operands.extend(arguments.iter().cloned()),
+                        None => {
+                            let mut operand = operand.clone();
+                            self.substitute_in_operand(&mut operand, substitutions, call.line)?;
+                            operands.push(operand);
+                        }
+                    }
+                }
+                let mut new_call = call.clone();
+                new_call.arguments = operands.iter().map(Operand::to_string).collect();
+                new_call.operands = operands;
+                ProgramElement::MacroCall(new_call)
+            }
             ProgramElement::Directive(dir) => {
+                // string text is taken literally
+                if is_string_directive(&dir.name) {
+                    return Ok(element.clone());
+                }
                 let mut new_dir = dir.clone();
-                for arg in &mut new_dir.arguments {
-                    *arg = self.substitute_in_string(arg, substitutions);
+                new_dir.arguments.clear();
+                new_dir.expressions.clear();
+                for (arg, expression) in dir.arguments.iter().zip(dir.expressions.iter()) {
+                    // RST values... gives one argument per value
+                    if let Some(arguments) = substitutions.get(arg).filter(|arguments| arguments.len() != 1 && expression.is_none()) {
+                        for argument in arguments {
+                            let value = self.argument_value(arg, argument, dir.line)?;
+                            new_dir.arguments.push(value.to_string());
+                            new_dir.expressions.push(Some(value).filter(|value| !value.is_simple()));
+                        }
+                        continue;
+                    }
+                    let (value, expression) = self.substitute_value(arg, expression.as_ref(), substitutions, dir.line)?;
+                    new_dir.arguments.push(value);
+                    new_dir.expressions.push(expression);
                 }
                 ProgramElement::Directive(new_dir)
             }
+            ProgramElement::Conditional(conditional) => {
+                let mut new_conditional = conditional.clone();
+                new_conditional.condition = self.substitute_in_expression(&conditional.condition, substitutions, conditional.line)?;
+                new_conditional.then_branch = conditional.then_branch.iter()
+                    .map(|element| self.substitute_parameters(element, substitutions))
+                    .collect::<Result<_, _>>()?;
+                new_conditional.else_branch = conditional.else_branch.iter()
+                    .map(|element| self.substitute_parameters(element, substitutions))
+                    .collect::<Result<_, _>>()?;
+                ProgramElement::Conditional(new_conditional)
+            }
             _ => element.clone(),
+        };
+        Ok(substituted)
+    }
+
+    /// name of the parameter a whole operand consists of (`POB param`)
+    fn parameter(operand: &Operand) -> Option<&str> {
+        let is_name = operand.addressing_mode == AddressingMode::Direct && operand.expression.is_none();
+        Some(operand.value.as_str()).filter(|_| is_name)
+    }
+
+    /// the one argument of a parameter, a variadic one has to stand for exactly one here
+    fn single<'a>(&self, name: &str, substitutions: &'a Substitutions, line: usize) -> Result<Option<&'a Operand>, AssemblerError> {
+        match substitutions.get(name).map(Vec::as_slice) {
+            None => Ok(None),
+            Some([argument]) => Ok(Some(argument)),
+            Some(arguments) => Err(AssemblerError::InvalidMacroArgument {
+                parameter: name.to_string(),
+                argument: arguments.iter().map(Operand::to_string).collect::<Vec<_>>().join(", "),
+                line,
+            }),
+        }
+    }
+
+    /// argument as a value in an expression: a number, label or expression, not `#5` or `[ptr]`
+    fn argument_value(&self, parameter: &str, argument: &Operand, line: usize) -> Result<Expression, AssemblerError> {
+        match (&argument.addressing_mode, &argument.expression) {
+            (AddressingMode::Direct, Some(expression)) => Ok(expression.clone()),
+            (AddressingMode::Direct, None) if !self.is_register(&argument.value) => Ok(Self::simple_expression(&argument.value)),
+            // -5, but not +label
+            (AddressingMode::Relative, None) if !argument.value.starts_with('+') => Ok(Expression::Number(argument.value.clone())),
+            _ => Err(AssemblerError::InvalidMacroArgument {
+                parameter: parameter.to_string(),
+                argument: argument.to_string(),
+                line,
+            }),
         }
     }
 
-    fn substitute_in_string(&self, s: &str, substitutions: &HashMap<String, String>) -> String {
-        // entire string matches a parameter = replace it
-        if let Some(value) = substitutions.get(s) {
-            return value.clone();
+    fn simple_expression(value: &str) -> Expression {
+        if value.starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+            Expression::Number(value.to_string())
+        } else {
+            Expression::Symbol(value.to_string())
+        }
+    }
+
+    /// whole operand, #param, [param], tab[param], R1[param], and param standing for a register
+    fn substitute_in_operand(&self, operand: &mut Operand, substitutions: &Substitutions, line: usize) -> Result<(), AssemblerError> {
+        match &mut operand.addressing_mode {
+            // the register part takes a name, the address part a value like tab+param
+            AddressingMode::Indexed { address, index: register }
+            | AddressingMode::BaseRegister { base: register, offset: address } => {
+                *register = self.substitute_name(register, substitutions, line)?;
+                let (value, expression) = self.substitute_value(address, operand.expression.as_ref(), substitutions, line)?;
+                *address = value;
+                operand.expression = expression;
+                operand.value = match &operand.addressing_mode {
+                    AddressingMode::Indexed { address, index } => format!("{}[{}]", address, index),
+                    AddressingMode::BaseRegister { base, offset } => format!("{}[{}]", base, offset),
+                    _ => unreachable!(),
+                };
+                return Ok(());
+            }
+            AddressingMode::Register | AddressingMode::RegisterIndirect => return Ok(()),
+            _ => {}
         }
-        
-        // otherwise return the original string
-        s.to_string()
+
+        if let Some(name) = Self::parameter(operand) {
+            // POB param called with #5, [ptr], R1, ... takes the argument's addressing mode
+            if let Some(argument) = self.single(name, substitutions, line)? {
+                // still written where the parameter was
+                *operand = Operand { column: operand.column, ..argument.clone() };
+            }
+            return Ok(());
+        }
+        if operand.addressing_mode == AddressingMode::Indirect && operand.expression.is_none() {
+            // [param] called with R1
+            if let Some(argument) = self.single(&operand.value, substitutions, line)? {
+                if argument.addressing_mode == AddressingMode::Register {
+                    operand.addressing_mode = AddressingMode::RegisterIndirect;
+                    operand.value = argument.value.clone();
+                    return Ok(());
+                }
+            }
+        }
+
+        let (value, expression) = self.substitute_value(&operand.value, operand.expression.as_ref(), substitutions, line)?;
+        operand.value = value;
+        operand.expression = expression;
+        Ok(())
+    }
+
+    /// value and its expression, as kept in operands and directive arguments
+    fn substitute_value(
+        &self,
+        value: &str,
+        expression: Option<&Expression>,
+        substitutions: &Substitutions,
+        line: usize
+    ) -> Result<(String, Option<Expression>), AssemblerError> {
+        let expression = match expression {
+            Some(expression) => expression.clone(),
+            None => Self::simple_expression(value),
+        };
+        let substituted = self.substitute_in_expression(&expression, substitutions, line)?;
+        if substituted == expression {
+            return Ok((value.to_string(), Some(expression).filter(|expression| !expression.is_simple())));
+        }
+        Ok((substituted.to_string(), Some(substituted).filter(|expression| !expression.is_simple())))
+    }
+
+    /// replaces parameter symbols inside tab+offset style expressions
+    fn substitute_in_expression(&self, expression: &Expression, substitutions: &Substitutions, line: usize) -> Result<Expression, AssemblerError> {
+        let substituted = match expression {
+            Expression::Symbol(name) => match self.single(name, substitutions, line)? {
+                Some(argument) => self.argument_value(name, argument, line)?,
+                None => expression.clone(),
+            },
+            Expression::Negate(inner) => Expression::Negate(Box::new(self.substitute_in_expression(inner, substitutions, line)?)),
+            Expression::Binary { operator, left, right } => Expression::Binary {
+                operator: *operator,
+                left: Box::new(self.substitute_in_expression(left, substitutions, line)?),
+                right: Box::new(self.substitute_in_expression(right, substitutions, line)?),
+            },
+            Expression::Number(_) | Expression::CurrentAddress => expression.clone(),
+        };
+        Ok(substituted)
+    }
+
+    /// label names and the parts of tab[index], R1[offset]
+    fn substitute_name(&self, name: &str, substitutions: &Substitutions, line: usize) -> Result<String, AssemblerError> {
+        match self.single(name, substitutions, line)? {
+            Some(argument) if argument.expression.is_none()
+                && matches!(argument.addressing_mode, AddressingMode::Direct | AddressingMode::Register) => Ok(argument.value.clone()),
+            Some(argument) => Err(AssemblerError::InvalidMacroArgument {
+                parameter: name.to_string(),
+                argument: argument.to_string(),
+                line,
+            }),
+            None => Ok(name.to_string()),
+        }
+    }
+
+    fn is_register(&self, value: &str) -> bool {
+        self.register_parser.parse_register(value, 0).is_ok()
     }
 }
 
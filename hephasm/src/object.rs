@@ -0,0 +1,158 @@
+//! relocatable object files, the modules a Linker places into one program
+
+use std::fmt;
+use std::str::FromStr;
+
+/// code of one module assembled at address 0, with what has to change when it is placed elsewhere
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectFile {
+    pub code: Vec<u16>,
+    pub relocations: Vec<Relocation>,
+    /// EKSPORTUJ - symbols other modules may refer to
+    pub exports: Vec<ExportedSymbol>,
+    /// IMPORTUJ - symbols defined by other modules
+    pub imports: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relocation {
+    /// address of the word within the module, of the prefix for a far instruction
+    pub offset: u16,
+    pub kind: RelocationKind,
+    pub target: RelocationTarget,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RelocationKind {
+    /// 16-bit argument of a far instruction, split between the prefix and the instruction
+    Far,
+    /// whole data word (RST label)
+    Word,
+}
+
+/// address added to the relocated value
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelocationTarget {
+    /// where the module itself is placed
+    Module,
+    /// imported symbol
+    Symbol(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportedSymbol {
+    pub name: String,
+    /// address within the module for labels, the value of a constant
+    pub value: i32,
+    /// moves with the module (labels), unlike constants
+    pub relocatable: bool,
+}
+
+/// header of the text form
+const HEADER: &str = "; asmodeus object 1";
+
+/// words per code record
+const WORDS_PER_RECORD: usize = 8;
+
+impl fmt::Display for ObjectFile {
+    /// one record per line:
+    /// `import <name>`, `export <name> <relocatable|absolute> <value>`,
+    /// `code <address> <hex words>`, `relocation <offset> <far|word> <symbol>`;
+    /// a relocation against the module itself has `-` for the symbol
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        for name in &self.imports {
+            writeln!(f, "import {}", name)?;
+        }
+        for symbol in &self.exports {
+            let kind = if symbol.relocatable { "relocatable" } else { "absolute" };
+            writeln!(f, "export {} {} {}", symbol.name, kind, symbol.value)?;
+        }
+        for (record, words) in self.code.chunks(WORDS_PER_RECORD).enumerate() {
+            let words = words.iter().map(|word| format!("{:04X}", word)).collect::<Vec<_>>().join(" ");
+            writeln!(f, "code {} {}", record * WORDS_PER_RECORD, words)?;
+        }
+        for relocation in &self.relocations {
+            let kind = match relocation.kind {
+                RelocationKind::Far => "far",
+                RelocationKind::Word => "word",
+            };
+            let target = match &relocation.target {
+                RelocationTarget::Module => "-",
+                RelocationTarget::Symbol(name) => name.as_str(),
+            };
+            writeln!(f, "relocation {} {} {}", relocation.offset, kind, target)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ObjectFile {
+    type Err = String;
+
+    /// reads the text form written by Display, errors name the line at fault
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut lines = text.lines().enumerate();
+        if lines.next().map(|(_, line)| line.trim()) != Some(HEADER) {
+            return Err("line 1: not an asmodeus object file".to_string());
+        }
+
+        let mut object = ObjectFile::default();
+        for (index, line) in lines {
+            object.read_record(line).map_err(|reason| format!("line {}: {}", index + 1, reason))?;
+        }
+
+        if let Some(relocation) = object.relocations.iter().find(|relocation| relocation.offset as usize >= object.code.len()) {
+            return Err(format!("relocation at {} is outside the code", relocation.offset));
+        }
+        Ok(object)
+    }
+}
+
+impl ObjectFile {
+    /// blank lines and ; comments are skipped
+    fn read_record(&mut self, line: &str) -> Result<(), String> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            [] => {}
+            [comment, ..] if comment.starts_with(';') => {}
+            ["import", name] => self.imports.push(name.to_string()),
+            ["export", name, kind, value] => self.exports.push(ExportedSymbol {
+                name: name.to_string(),
+                value: value.parse().map_err(|_| format!("invalid value: {}", value))?,
+                relocatable: match *kind {
+                    "relocatable" => true,
+                    "absolute" => false,
+                    other => return Err(format!("unknown symbol kind: {}", other)),
+                },
+            }),
+            ["code", address, words @ ..] => {
+                if number(address)? as usize != self.code.len() {
+                    return Err(format!("expected code at {}, found {}", self.code.len(), address));
+                }
+                for word in words {
+                    self.code.push(u16::from_str_radix(word, 16).map_err(|_| format!("invalid word: {}", word))?);
+                }
+            }
+            ["relocation", offset, kind, target] => self.relocations.push(Relocation {
+                offset: number(offset)?,
+                kind: match *kind {
+                    "far" => RelocationKind::Far,
+                    "word" => RelocationKind::Word,
+                    other => return Err(format!("unknown relocation kind: {}", other)),
+                },
+                target: match *target {
+                    "-" => RelocationTarget::Module,
+                    name => RelocationTarget::Symbol(name.to_string()),
+                },
+            }),
+            [kind @ ("import" | "export" | "relocation"), ..] => return Err(format!("wrong number of fields in {} record", kind)),
+            [other, ..] => return Err(format!("unknown record: {}", other)),
+        }
+        Ok(())
+    }
+}
+
+fn number(field: &str) -> Result<u16, String> {
+    field.parse().map_err(|_| format!("invalid number: {}", field))
+}
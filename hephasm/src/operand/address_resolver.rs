@@ -2,7 +2,9 @@
 
 use crate::error::AssemblerError;
 use crate::symbol_table::SymbolTable;
+use crate::types::SymbolType;
 use super::{NumberParser, Validator};
+use asmodeus_shared::relative_argument;
 
 pub struct AddressResolver;
 
@@ -29,6 +31,7 @@ impl AddressResolver {
                     Err(AssemblerError::UndefinedSymbol {
                         symbol: value.to_string(),
                         line,
+                        suggestion: None,
                     })
                 }
                 Err(e) => Err(e),
@@ -36,89 +39,54 @@ impl AddressResolver {
         }
     }
 
-    pub fn resolve_indirect(
-        &self, 
-        value: &str, 
-        line: usize, 
-        number_parser: &NumberParser, 
-        validator: &Validator
-    ) -> Result<u16, AssemblerError> {
-        // indirect addressing, parse as number or fail with undefined symbol
-        match number_parser.parse_number(value, line) {
-            Ok(num) => Ok(num),
-            Err(_) if validator.is_identifier(value) => {
-                Err(AssemblerError::UndefinedSymbol {
-                    symbol: value.to_string(),
-                    line,
-                })
-            }
-            Err(e) => Err(e),
-        }
-    }
-
-    pub fn resolve_multiple_indirect(
-        &self, 
-        value: &str, 
-        line: usize, 
-        number_parser: &NumberParser, 
-        validator: &Validator
-    ) -> Result<u16, AssemblerError> {
-        // multiple indirect addressing, parse as number or fail with undefined symbol
-        match number_parser.parse_number(value, line) {
-            Ok(num) => Ok(num),
-            Err(_) if validator.is_identifier(value) => {
-                Err(AssemblerError::UndefinedSymbol {
-                    symbol: value.to_string(),
-                    line,
-                })
-            }
-            Err(e) => Err(e),
-        }
-    }
-
+    /// +3 and -2 count from the instruction, +label goes to the label wherever the code is placed;
+    /// encoded as the signed offset from the word after the instruction
     pub fn resolve_relative(
         &self, 
         value: &str, 
-        current_address: u16, 
-        line: usize, 
-        number_parser: &NumberParser
-    ) -> Result<u16, AssemblerError> {
-        // relative addressing, calculate offset from current position
-        let offset = number_parser.parse_signed_number(value, line)?;
-        let target = (current_address as i32) + offset;
-        if target < 0 || target > 2047 {
-            return Err(AssemblerError::AddressOutOfBounds {
-                address: target as u16,
-                line,
-            });
-        }
-        Ok(target as u16)
-    }
-
-    pub fn resolve_indexed(
-        &self, 
-        address: &str, 
         symbol_table: &SymbolTable, 
+        current_address: u16, 
         line: usize, 
         number_parser: &NumberParser, 
         validator: &Validator
     ) -> Result<u16, AssemblerError> {
-        // indexed addressing, try to resolve base address as symbol first
-        if let Some(addr) = symbol_table.get_address(address) {
-            Ok(addr)
-        } else {
-            match number_parser.parse_number(address, line) {
-                Ok(num) => Ok(num),
-                Err(_) if validator.is_identifier(address) => {
-                    Err(AssemblerError::UndefinedSymbol {
-                        symbol: address.to_string(),
+        let target = match value.strip_prefix('+').filter(|name| validator.is_identifier(name)) {
+            Some(name) => match symbol_table.resolve(name) {
+                Some(symbol) if symbol.symbol_type == SymbolType::Constant => current_address as i32 + symbol.value,
+                // the distance to another module is only known once linked
+                Some(symbol) if symbol.symbol_type == SymbolType::External => {
+                    return Err(AssemblerError::InvalidRelocation {
+                        expression: value.to_string(),
                         line,
-                    })
+                    });
                 }
-                Err(e) => Err(e),
-            }
+                Some(symbol) => symbol.value,
+                None => {
+                    return Err(AssemblerError::UndefinedSymbol {
+                        symbol: name.to_string(),
+                        line,
+                        suggestion: None,
+                    });
+                }
+            },
+            None => current_address as i32 + number_parser.parse_signed_number(value, line)?,
+        };
+        if !(0..2048).contains(&target) {
+            return Err(AssemblerError::AddressOutOfBounds {
+                address: target.max(0) as u16,
+                line,
+                column: None,
+            });
         }
+
+        // relative operands never take the far form
+        relative_argument(current_address + 1, target as u16).ok_or_else(|| AssemblerError::RelativeOutOfRange {
+            operand: value.to_string(),
+            distance: target - current_address as i32,
+            line,
+        })
     }
+
 }
 
 impl Default for AddressResolver {
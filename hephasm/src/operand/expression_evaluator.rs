@@ -0,0 +1,111 @@
+//! constant expression evaluation
+
+use crate::error::AssemblerError;
+use crate::symbol_table::SymbolTable;
+use parseid::ast::{Expression, BinaryOperator};
+use super::NumberParser;
+
+pub struct ExpressionEvaluator;
+
+impl ExpressionEvaluator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// symbols are looked up in symbol_table, $ is current_address;
+    /// intermediate results have to fit in 32 bits
+    pub fn evaluate(
+        &self,
+        expression: &Expression,
+        symbol_table: &SymbolTable,
+        current_address: u16,
+        line: usize,
+        number_parser: &NumberParser
+    ) -> Result<i32, AssemblerError> {
+        match expression {
+            Expression::Number(value) => number_parser.parse_signed_number(value, line),
+            Expression::Symbol(name) => {
+                symbol_table.get_value(name)
+                    .ok_or_else(|| AssemblerError::UndefinedSymbol {
+                        symbol: name.clone(),
+                        line,
+                        suggestion: None,
+                    })
+            }
+            Expression::CurrentAddress => Ok(current_address as i32),
+            Expression::Negate(inner) => {
+                let value = self.evaluate(inner, symbol_table, current_address, line, number_parser)?;
+                Self::checked(expression, -(value as i64), line)
+            }
+            Expression::Binary { operator, left, right } => {
+                let left_value = self.evaluate(left, symbol_table, current_address, line, number_parser)? as i64;
+                let right_value = self.evaluate(right, symbol_table, current_address, line, number_parser)? as i64;
+
+                let result = match operator {
+                    BinaryOperator::Add => left_value + right_value,
+                    BinaryOperator::Subtract => left_value - right_value,
+                    BinaryOperator::Multiply => left_value * right_value,
+                    BinaryOperator::Divide | BinaryOperator::Modulo if right_value == 0 => {
+                        return Err(AssemblerError::DivisionByZero {
+                            expression: expression.to_string(),
+                            line,
+                        });
+                    }
+                    BinaryOperator::Divide => left_value / right_value,
+                    BinaryOperator::Modulo => left_value % right_value,
+                    BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight if right_value < 0 => {
+                        return Err(AssemblerError::ExpressionOutOfRange {
+                            expression: right.to_string(),
+                            value: right_value,
+                            line,
+                        });
+                    }
+                    // wide enough for the value a long shift overflows to, saturating past 64 bits
+                    BinaryOperator::ShiftLeft => {
+                        let shifted = (left_value as i128) << right_value.min(64);
+                        i64::try_from(shifted).unwrap_or(if shifted < 0 { i64::MIN } else { i64::MAX })
+                    }
+                    BinaryOperator::ShiftRight => left_value >> right_value.min(63),
+                    BinaryOperator::And => left_value & right_value,
+                    BinaryOperator::Or => left_value | right_value,
+                    // comparisons give 1 or 0
+                    BinaryOperator::Equal => (left_value == right_value) as i64,
+                    BinaryOperator::NotEqual => (left_value != right_value) as i64,
+                    BinaryOperator::Less => (left_value < right_value) as i64,
+                    BinaryOperator::LessEqual => (left_value <= right_value) as i64,
+                    BinaryOperator::Greater => (left_value > right_value) as i64,
+                    BinaryOperator::GreaterEqual => (left_value >= right_value) as i64,
+                };
+
+                Self::checked(expression, result, line)
+            }
+        }
+    }
+
+    /// value as a 16-bit word, negative values down to -32768 are stored in two's complement
+    pub fn to_word(&self, expression: &Expression, value: i32, allow_negative: bool, line: usize) -> Result<u16, AssemblerError> {
+        let minimum = if allow_negative { i16::MIN as i32 } else { 0 };
+        if value < minimum || value > u16::MAX as i32 {
+            return Err(AssemblerError::ExpressionOutOfRange {
+                expression: expression.to_string(),
+                value: value as i64,
+                line,
+            });
+        }
+        Ok(value as u16)
+    }
+
+    fn checked(expression: &Expression, value: i64, line: usize) -> Result<i32, AssemblerError> {
+        i32::try_from(value).map_err(|_| AssemblerError::ExpressionOutOfRange {
+            expression: expression.to_string(),
+            value,
+            line,
+        })
+    }
+}
+
+impl Default for ExpressionEvaluator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
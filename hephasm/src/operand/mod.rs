@@ -1,21 +1,25 @@
 //! operand resolution and value parsing
 
 mod address_resolver;
+mod expression_evaluator;
 mod number_parser;
 mod register_parser;
 mod validator;
 
 pub use address_resolver::AddressResolver;
+pub use expression_evaluator::ExpressionEvaluator;
 pub use number_parser::NumberParser;
 pub use register_parser::RegisterParser;
 pub use validator::Validator;
 
 use crate::error::AssemblerError;
 use crate::symbol_table::SymbolTable;
-use parseid::ast::{AddressingMode, Operand};
+use parseid::ast::{AddressingMode, Expression, Operand};
+use asmodeus_shared::encode_base_register;
 
 pub struct OperandResolver {
     address_resolver: AddressResolver,
+    expression_evaluator: ExpressionEvaluator,
     number_parser: NumberParser,
     register_parser: RegisterParser,
     validator: Validator,
@@ -25,6 +29,7 @@ impl OperandResolver {
     pub fn new() -> Self {
         Self {
             address_resolver: AddressResolver::new(),
+            expression_evaluator: ExpressionEvaluator::new(),
             number_parser: NumberParser::new(),
             register_parser: RegisterParser::new(),
             validator: Validator::new(),
@@ -32,18 +37,22 @@ impl OperandResolver {
     }
 
     pub fn resolve_symbol_to_address(&self, operand: &Operand, symbol_table: &SymbolTable, current_address: u16, line: usize) -> Result<u16, AssemblerError> {
+        let expression = operand.expression.as_ref();
+
         match &operand.addressing_mode {
-            AddressingMode::Direct => {
-                self.address_resolver.resolve_direct(&operand.value, symbol_table, line, &self.number_parser, &self.validator)
+            // labels, constants, tab+3 and $ in every mode taking an address
+            AddressingMode::Direct | AddressingMode::Indirect | AddressingMode::MultipleIndirect => {
+                self.resolve_address(&operand.value, expression, symbol_table, current_address, line)
             }
             AddressingMode::Immediate => {
-                self.number_parser.parse_number(&operand.value, line)
-            }
-            AddressingMode::Indirect => {
-                self.address_resolver.resolve_indirect(&operand.value, line, &self.number_parser, &self.validator)
-            }
-            AddressingMode::MultipleIndirect => {
-                self.address_resolver.resolve_multiple_indirect(&operand.value, line, &self.number_parser, &self.validator)
+                // POB #SIZE, #tab, #(tab) and #tab+1 - symbols of every kind stand in for their value
+                let expression = match expression {
+                    Some(expression) => expression.clone(),
+                    None if self.validator.is_identifier(&operand.value) => Expression::Symbol(operand.value.clone()),
+                    None => return self.number_parser.parse_number(&operand.value, line),
+                };
+                let value = self.expression_evaluator.evaluate(&expression, symbol_table, current_address, line, &self.number_parser)?;
+                self.expression_evaluator.to_word(&expression, value, false, line)
             }
             AddressingMode::Register => {
                 self.register_parser.parse_register(&operand.value, line)
@@ -51,20 +60,77 @@ impl OperandResolver {
             AddressingMode::RegisterIndirect => {
                 self.register_parser.parse_register(&operand.value, line)
             }
-            AddressingMode::BaseRegister { base: _, offset } => {
-                self.number_parser.parse_number(offset, line)
+            // R1[offset] and address[R1] both add an address to a register
+            AddressingMode::BaseRegister { base: register, offset: address }
+            | AddressingMode::Indexed { address, index: register } => {
+                let register = self.register_parser.parse_register(register, line)?;
+                let address = self.resolve_address(address, expression, symbol_table, current_address, line)?;
+                if address > 2047 {
+                    return Err(AssemblerError::AddressOutOfBounds { address, line, column: Some(operand.column) });
+                }
+                Ok(encode_base_register(register, address))
             }
             AddressingMode::Relative => {
-                self.address_resolver.resolve_relative(&operand.value, current_address, line, &self.number_parser)
+                self.address_resolver.resolve_relative(&operand.value, symbol_table, current_address, line, &self.number_parser, &self.validator)
+                    .map_err(|error| error.at_column(operand.column))
             }
-            AddressingMode::Indexed { address, index: _ } => {
-                self.address_resolver.resolve_indexed(address, symbol_table, line, &self.number_parser, &self.validator)
+        }
+    }
+
+    /// number, symbol (defined anywhere in the program) or expression
+    fn resolve_address(
+        &self,
+        value: &str,
+        expression: Option<&Expression>,
+        symbol_table: &SymbolTable,
+        current_address: u16,
+        line: usize
+    ) -> Result<u16, AssemblerError> {
+        match expression {
+            Some(expression) => {
+                let value = self.expression_evaluator.evaluate(expression, symbol_table, current_address, line, &self.number_parser)?;
+                self.expression_evaluator.to_word(expression, value, false, line)
             }
+            None => self.address_resolver.resolve_direct(value, symbol_table, line, &self.number_parser, &self.validator),
+        }
+    }
+
+    /// directive argument: plain number, symbol or expression, signed
+    pub fn resolve_value(
+        &self,
+        value: &str,
+        expression: Option<&Expression>,
+        symbol_table: &SymbolTable,
+        current_address: u16,
+        line: usize
+    ) -> Result<i32, AssemblerError> {
+        match expression {
+            Some(expression) => self.expression_evaluator.evaluate(expression, symbol_table, current_address, line, &self.number_parser),
+            None => match symbol_table.get_value(value) {
+                Some(value) => Ok(value),
+                None if self.validator.is_identifier(value) => Err(AssemblerError::UndefinedSymbol {
+                    symbol: value.to_string(),
+                    line,
+                    suggestion: None,
+                }),
+                None => self.number_parser.parse_signed_number(value, line),
+            },
         }
     }
 
-    pub fn parse_signed_number(&self, value: &str, line: usize) -> Result<i32, AssemblerError> {
-        self.number_parser.parse_signed_number(value, line)
+    /// resolve_value result as a memory word
+    pub fn resolve_word(
+        &self,
+        value: &str,
+        expression: Option<&Expression>,
+        symbol_table: &SymbolTable,
+        current_address: u16,
+        allow_negative: bool,
+        line: usize
+    ) -> Result<u16, AssemblerError> {
+        let resolved = self.resolve_value(value, expression, symbol_table, current_address, line)?;
+        let expression = expression.cloned().unwrap_or_else(|| Expression::Number(value.to_string()));
+        self.expression_evaluator.to_word(&expression, resolved, allow_negative, line)
     }
 }
 
@@ -8,17 +8,12 @@ impl RegisterParser {
     }
 
     pub fn parse_register(&self, value: &str, line: usize) -> Result<u16, AssemblerError> {
-        if !value.to_uppercase().starts_with('R') {
-            return Err(AssemblerError::InvalidNumber {
-                value: value.to_string(),
-                line,
-            });
-        }
-
-        let reg_num = &value[1..];
-        reg_num.parse::<u16>()
-            .map_err(|_| AssemblerError::InvalidNumber {
-                value: value.to_string(),
+        // R0 to R7, anything else named where a register belongs (tab[i]) is not one
+        value.strip_prefix(['R', 'r'])
+            .and_then(|number| number.parse::<u16>().ok())
+            .filter(|&register| register <= 7)
+            .ok_or_else(|| AssemblerError::InvalidRegister {
+                register: value.to_string(),
                 line,
             })
     }
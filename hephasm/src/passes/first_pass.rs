@@ -1,16 +1,44 @@
-//! first pass: macro expansion
+//! first pass: conditional assembly and macro expansion
 
 use crate::error::AssemblerError;
 use crate::macro_processor::MacroProcessor;
-use parseid::ast::{Program, ProgramElement};
+use crate::conditional::ConditionalProcessor;
+use parseid::ast::{Directive, Program, ProgramElement};
+use std::collections::BTreeMap;
 
 pub struct FirstPass;
 
 impl FirstPass {
+    /// elements to lay out, and for each the index of the macro expansion it came from;
+    /// calls and conditional blocks in error are left out
     pub fn execute(
         macro_processor: &mut MacroProcessor,
-        program: &Program
-    ) -> Result<Vec<ProgramElement>, AssemblerError> {
-        macro_processor.expand_macros(program)
+        conditional_processor: &ConditionalProcessor,
+        definitions: &BTreeMap<String, i32>,
+        program: &Program,
+        errors: &mut Vec<AssemblerError>
+    ) -> Result<(Vec<ProgramElement>, Vec<Option<usize>>), AssemblerError> {
+        // macros defined in a branch not taken do not exist
+        let selected = Program {
+            elements: conditional_processor.prune(&program.elements, definitions)?,
+        };
+        // conditions in macro bodies are decided after substitution
+        let mut scope = conditional_processor.scope(definitions)?;
+        let (expanded, expanded_origins) = macro_processor.expand_macros(&selected, &mut scope, errors);
+
+        // definitions become constants, in place of the STAŁA directives they override
+        let mut elements: Vec<ProgramElement> = definitions.iter()
+            .map(|(name, value)| ProgramElement::Directive(Directive {
+                name: "STAŁA".to_string(),
+                arguments: vec![name.clone(), value.to_string()],
+                expressions: vec![None, None],
+                line: 0,
+                column: 0,
+            }))
+            .collect();
+        let mut origins = vec![None; elements.len()];
+        elements.extend(expanded);
+        origins.extend(expanded_origins);
+        Ok((elements, origins))
     }
 }
@@ -0,0 +1,182 @@
+//! lint pass: warnings about the assembled program, run once it assembled without errors
+
+use crate::debug_info::{DebugInfo, WordKind};
+use crate::layout::Layout;
+use crate::lint::{Lint, Warning};
+use parseid::ast::{Instruction, ProgramElement};
+use asmodeus_shared::{addressing_mode_bits, decode_far_instruction, decode_instruction, relative_target, requires_far_form, Opcode};
+use std::collections::HashSet;
+
+pub struct LintPass;
+
+/// instruction of the expanded program, decoded from the machine code
+struct Decoded<'a> {
+    inst: &'a Instruction,
+    address: u16,
+    size: u16,
+    opcode: Option<Opcode>,
+    mode: u8,
+    argument: u16,
+}
+
+impl Decoded<'_> {
+    /// execution never goes on to the next word
+    fn is_terminator(&self) -> bool {
+        matches!(self.opcode, Some(Opcode::STP | Opcode::SOB | Opcode::POW))
+    }
+
+    /// where a jump or call goes, when known without running the program
+    fn jump_target(&self) -> Option<u16> {
+        let jumps = matches!(
+            self.opcode,
+            Some(Opcode::SOB | Opcode::SOM | Opcode::SOZ | Opcode::SOC | Opcode::SOV | Opcode::SOP | Opcode::SNZ | Opcode::WYW)
+        );
+        match self.mode {
+            _ if !jumps => None,
+            addressing_mode_bits::DIRECT => Some(self.argument),
+            addressing_mode_bits::RELATIVE => relative_target(self.address + self.size, self.argument),
+            _ => None,
+        }
+    }
+
+    fn mnemonic(&self) -> String {
+        self.inst.opcode.to_uppercase()
+    }
+}
+
+impl LintPass {
+    /// warnings of the enabled lints ordered by line, those about the whole program last;
+    /// object files are not expected to stop, they are called by other modules
+    pub fn execute(
+        lints: &HashSet<Lint>,
+        elements: &[ProgramElement],
+        origins: &[Option<usize>],
+        layout: &Layout,
+        machine_code: &[u16],
+        debug_info: &DebugInfo,
+        object: bool
+    ) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+
+        // elements taking up memory, in address order
+        let mut placed: Vec<usize> = (0..elements.len())
+            .filter(|&index| layout.sizes[index] > 0)
+            .filter(|&index| matches!(elements[index], ProgramElement::Instruction(_) | ProgramElement::Directive(_)))
+            .collect();
+        placed.sort_by_key(|&index| layout.addresses[index]);
+
+        let decoded: Vec<Option<Decoded>> = placed.iter()
+            .map(|&index| match &elements[index] {
+                ProgramElement::Instruction(inst) => Some(Self::decode(inst, layout.addresses[index], layout.sizes[index], machine_code)),
+                _ => None,
+            })
+            .collect();
+
+        // code after STP is reachable again where a label or a jump leads to it
+        let mut entries: HashSet<u16> = decoded.iter().flatten().filter_map(Decoded::jump_target).collect();
+        for (index, element) in elements.iter().enumerate() {
+            if let ProgramElement::LabelDefinition(_) = element {
+                entries.insert(layout.addresses[index]);
+            }
+        }
+
+        for (position, current) in decoded.iter().enumerate() {
+            let Some(current) = current else {
+                continue;
+            };
+            let next = placed.get(position + 1)
+                .filter(|&&index| layout.addresses[index] == current.address + current.size)
+                .map(|&index| (&elements[index], &decoded[position + 1]));
+
+            match next {
+                Some((_, Some(next))) if current.is_terminator() && !entries.contains(&next.address) => {
+                    warnings.push(Warning::new(
+                        Lint::UnreachableCode,
+                        format!("Unreachable code after {}", current.mnemonic()),
+                        next.inst.line,
+                        &next.inst.opcode,
+                    ));
+                }
+                Some((ProgramElement::Directive(dir), None)) if !current.is_terminator() => {
+                    warnings.push(Warning::new(
+                        Lint::FallIntoData,
+                        format!("Execution falls from {} into data ({})", current.mnemonic(), dir.name.to_uppercase()),
+                        current.inst.line,
+                        &current.inst.opcode,
+                    ));
+                }
+                _ => {}
+            }
+
+            let writes_code = current.opcode == Some(Opcode::LAD)
+                && current.mode == addressing_mode_bits::DIRECT
+                && debug_info.location(current.argument).is_some_and(|location| location.kind == WordKind::Instruction);
+            if writes_code {
+                let target = debug_info.label_at(current.argument)
+                    .map_or_else(|| format!("address {}", current.argument), str::to_string);
+                warnings.push(Warning::new(
+                    Lint::SelfModifyingCode,
+                    format!("Self-modifying code: {} writes into the instruction at {}", current.mnemonic(), target),
+                    current.inst.line,
+                    &current.inst.opcode,
+                ));
+            }
+
+            if current.mode == addressing_mode_bits::IMMEDIATE && requires_far_form(current.argument) {
+                let operand = current.inst.operand.as_ref().map_or(current.inst.opcode.as_str(), |operand| operand.value.as_str());
+                warnings.push(Warning::new(
+                    Lint::WideImmediate,
+                    format!("Immediate value {} does not fit in 8 bits, {} takes two words", current.argument, current.mnemonic()),
+                    current.inst.line,
+                    operand,
+                ));
+            }
+        }
+
+        // labels written in macro bodies would be reported once per call
+        let referenced: HashSet<&str> = debug_info.references.iter().map(|reference| reference.name.as_str()).collect();
+        for (index, element) in elements.iter().enumerate() {
+            if let ProgramElement::LabelDefinition(label) = element {
+                // execution starts at address 0, a label there names the program
+                if origins[index].is_none() && layout.addresses[index] != 0 && !referenced.contains(label.name.as_str()) {
+                    warnings.push(Warning::new(
+                        Lint::UnusedLabel,
+                        format!("Label {} is never used", label.name),
+                        label.line,
+                        &label.name,
+                    ));
+                }
+            }
+        }
+
+        let stops = decoded.iter().flatten().any(|inst| inst.opcode == Some(Opcode::STP));
+        if !object && !stops && decoded.iter().any(Option::is_some) {
+            warnings.push(Warning {
+                lint: Lint::MissingStop,
+                message: "Program has no STP instruction, execution runs past its end".to_string(),
+                line: None,
+                subject: None,
+            });
+        }
+
+        warnings.retain(|warning| lints.contains(&warning.lint));
+        warnings.sort_by_key(|warning| warning.line.unwrap_or(usize::MAX));
+        warnings
+    }
+
+    /// opcode, addressing mode and argument as assembled, far prefix joined with the instruction
+    fn decode<'a>(inst: &'a Instruction, address: u16, size: u16, machine_code: &[u16]) -> Decoded<'a> {
+        let (opcode, mode, argument) = match size {
+            2 => decode_far_instruction(machine_code[address as usize], machine_code[address as usize + 1]),
+            _ => decode_instruction(machine_code[address as usize]),
+        };
+        Decoded {
+            inst,
+            address,
+            size,
+            opcode: Opcode::from_u8(opcode),
+            mode,
+            argument,
+        }
+    }
+}
@@ -3,7 +3,9 @@
 mod first_pass;
 mod second_pass;  
 mod third_pass;
+mod lint_pass;
 
 pub use first_pass::FirstPass;
 pub use second_pass::SecondPass;
 pub use third_pass::ThirdPass;
+pub use lint_pass::LintPass;
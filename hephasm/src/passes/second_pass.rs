@@ -1,55 +1,326 @@
-//! second pass: symbol table building
+//! second pass: symbol table building and placing sections
 
 use crate::error::AssemblerError;
 use crate::symbol_table::SymbolTable;
 use crate::types::SymbolType;
-use parseid::ast::ProgramElement;
+use crate::operand::OperandResolver;
+use crate::directive::DirectiveProcessor;
+use asmodeus_shared::requires_far_form;
+use parseid::ast::{Directive, ProgramElement};
+use crate::layout::{Layout, Segment, DEFAULT_SECTION};
+use parseid::{is_constant_directive, is_import_directive, is_origin_directive, is_section_directive};
+use std::collections::{HashMap, HashSet};
 
 pub struct SecondPass;
 
+/// state of one section while laying out the program
+struct Section<'a> {
+    name: &'a str,
+    /// given by SEKCJA name, address
+    address: Option<u16>,
+    /// where its next word goes
+    current: u16,
+    /// address after its highest word
+    end: u16,
+}
+
 impl SecondPass {
+    /// elements in error take no words and are reported in errors,
+    /// only a program not fitting in memory cannot be laid out at all
     pub fn execute(
         symbol_table: &mut SymbolTable,
-        current_address: &mut u16,
-        elements: &[ProgramElement]
-    ) -> Result<(), AssemblerError> {
-        *current_address = 0;
+        operand_resolver: &OperandResolver,
+        directive_processor: &DirectiveProcessor,
+        relocated: &HashSet<usize>,
+        elements: &[ProgramElement],
+        errors: &mut Vec<AssemblerError>
+    ) -> Result<Layout, AssemblerError> {
+        // instructions whose argument does not fit in 8 bits take two words (far form),
+        // as do the ones the linker relocates;
+        // growing an instruction can only move labels up, so marking settles after a few rounds
+        let mut far_instructions = relocated.clone();
+        // sections without an address follow the one before them, which may grow the same way
+        let mut section_starts: HashMap<String, u16> = HashMap::new();
+
+        loop {
+            symbol_table.clear();
+            // every round finds the same errors, the last one reports them
+            let mut round_errors = Vec::new();
+            let (layout, sections) = Self::layout(symbol_table, directive_processor, &far_instructions, &section_starts, elements, &mut round_errors)?;
+
+            let mut changed = false;
+            for (index, element) in elements.iter().enumerate() {
+                if let ProgramElement::Instruction(inst) = element {
+                    if far_instructions.contains(&index) {
+                        continue;
+                    }
+                    if let Some(operand) = &inst.operand {
+                        // unresolved operands are reported by the third pass
+                        if let Ok(argument) = operand_resolver.resolve_symbol_to_address(operand, symbol_table, layout.addresses[index], inst.line) {
+                            if requires_far_form(argument) {
+                                far_instructions.insert(index);
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let mut previous_end = 0;
+            for section in &sections {
+                if section.address.is_none() {
+                    let start = section_starts.entry(section.name.to_string()).or_insert(0);
+                    if previous_end > *start {
+                        *start = previous_end;
+                        changed = true;
+                    }
+                }
+                previous_end = section.end;
+            }
+
+            if !changed {
+                errors.extend(round_errors);
+                if let Err(e) = layout.check_overlaps() {
+                    errors.push(e);
+                }
+                return Ok(layout);
+            }
+        }
+    }
+
+    /// assigns addresses to labels and values to constants, with sections starting at the given addresses
+    fn layout<'a>(
+        symbol_table: &mut SymbolTable,
+        directive_processor: &DirectiveProcessor,
+        far_instructions: &HashSet<usize>,
+        section_starts: &HashMap<String, u16>,
+        elements: &'a [ProgramElement],
+        errors: &mut Vec<AssemblerError>
+    ) -> Result<(Layout, Vec<Section<'a>>), AssemblerError> {
+        let start = section_starts.get(DEFAULT_SECTION).copied().unwrap_or(0);
+        let mut sections = vec![Section { name: DEFAULT_SECTION, address: None, current: start, end: start }];
+        let mut current = 0;
+        let mut layout = Layout {
+            addresses: Vec::with_capacity(elements.len()),
+            sizes: Vec::with_capacity(elements.len()),
+            far_instructions: far_instructions.clone(),
+            segments: Vec::new(),
+        };
+        let mut segment = Segment { section: DEFAULT_SECTION.to_string(), start, end: start, line: 0 };
+        let mut constants: Vec<(&Directive, u16)> = Vec::new();
+        // sizes using a constant not bound yet, with the line of that constant
+        let mut deferred: Vec<(AssemblerError, usize)> = Vec::new();
+
+        for (index, element) in elements.iter().enumerate() {
+            let address = sections[current].current;
+            layout.addresses.push(address);
+            let mut size = 0;
 
-        for element in elements {
             match element {
                 ProgramElement::LabelDefinition(label) => {
-                    symbol_table.define(
-                        label.name.clone(),
-                        *current_address,
-                        SymbolType::Label,
-                    ).map_err(|mut e| {
+                    if let Err(mut e) = symbol_table.define(label.name.clone(), address, SymbolType::Label) {
                         if let AssemblerError::DuplicateSymbol { line, .. } = &mut e {
                             *line = label.line;
                         }
-                        e
-                    })?;
+                        errors.push(e);
+                    }
                 }
                 ProgramElement::Instruction(_) => {
-                    *current_address += 1;
-                    if *current_address > 2048 {
-                        return Err(AssemblerError::MemoryOverflow);
+                    size = if far_instructions.contains(&index) { 2 } else { 1 };
+                }
+                ProgramElement::Directive(dir) if is_section_directive(&dir.name) => {
+                    match directive_processor.section(dir, symbol_table, address) {
+                        Ok((name, address)) => {
+                            current = match sections.iter().position(|section| section.name == name) {
+                                Some(existing) => {
+                                    if address.is_some() {
+                                        errors.push(AssemblerError::InvalidDirectiveArguments {
+                                            directive: dir.name.clone(),
+                                            reason: format!("the address of section {} can only be given where it first appears", name),
+                                            line: dir.line,
+                                        });
+                                    }
+                                    existing
+                                }
+                                None => {
+                                    let start = address.or_else(|| section_starts.get(name).copied()).unwrap_or(0);
+                                    sections.push(Section { name, address, current: start, end: start });
+                                    sections.len() - 1
+                                }
+                            };
+                            let start = sections[current].current;
+                            Self::start_segment(&mut layout, &mut segment, name, start, dir.line);
+                        }
+                        Err(e) => errors.push(e),
                     }
                 }
-                ProgramElement::Directive(dir) => {
-                    match dir.name.to_uppercase().as_str() {
-                        "RST" | "RPA" => {
-                            *current_address += 1;
+                ProgramElement::Directive(dir) if is_origin_directive(&dir.name) => {
+                    match directive_processor.origin(dir, symbol_table, address) {
+                        Ok(origin) => {
+                            sections[current].current = origin;
+                            Self::start_segment(&mut layout, &mut segment, sections[current].name, origin, dir.line);
+                        }
+                        Err(e) => errors.push(e),
+                    }
+                }
+                ProgramElement::Directive(dir) if is_import_directive(&dir.name) => {
+                    // placed by the linker, 0 within the module
+                    for name in &dir.arguments {
+                        if let Err(mut e) = symbol_table.define(name.clone(), 0, SymbolType::External) {
+                            if let AssemblerError::DuplicateSymbol { line, .. } = &mut e {
+                                *line = dir.line;
+                            }
+                            errors.push(e);
                         }
-                        _ => {}
                     }
-                    if *current_address > 2048 {
-                        return Err(AssemblerError::MemoryOverflow);
+                }
+                ProgramElement::Directive(dir) if is_constant_directive(&dir.name) => {
+                    if constants.iter().any(|(other, _)| other.arguments[0] == dir.arguments[0]) {
+                        errors.push(AssemblerError::DuplicateSymbol {
+                            symbol: dir.arguments[0].clone(),
+                            line: dir.line,
+                        });
+                    } else {
+                        // usable by RPA counts below as soon as everything it refers to is known
+                        constants.push((dir, address));
+                        Self::define_constants(symbol_table, directive_processor, &constants, false, errors);
+                    }
+                }
+                ProgramElement::Directive(dir) => {
+                    match directive_processor.directive_size(dir, symbol_table, address) {
+                        Ok(words) => size = words,
+                        Err(e) => {
+                            let pending = match &e {
+                                AssemblerError::UndefinedSymbol { symbol, .. } => {
+                                    constants.iter().find(|(other, _)| other.arguments[0] == *symbol).map(|(other, _)| other.line)
+                                }
+                                _ => None,
+                            };
+                            match pending {
+                                Some(constant_line) => deferred.push((e, constant_line)),
+                                None => errors.push(e),
+                            }
+                        }
                     }
                 }
                 _ => {}
             }
+
+            // RPA 5000 must not wrap the u16 address
+            let end = address as usize + size;
+            if end > 2048 {
+                // only instructions and data take up memory
+                let line = match element {
+                    ProgramElement::Instruction(inst) => inst.line,
+                    ProgramElement::Directive(dir) => dir.line,
+                    _ => 0,
+                };
+                return Err(AssemblerError::MemoryOverflow { line });
+            }
+            if size > 0 {
+                let section = &mut sections[current];
+                section.current = end as u16;
+                section.end = section.end.max(end as u16);
+                segment.end = end as u16;
+            }
+            layout.sizes.push(size as u16);
+        }
+        Self::start_segment(&mut layout, &mut segment, DEFAULT_SECTION, 0, 0);
+
+        // constants may refer to labels and constants further down
+        Self::define_constants(symbol_table, directive_processor, &constants, true, errors);
+        // a constant in error is reported once, not again at every use
+        for (e, constant_line) in deferred {
+            if !errors.iter().any(|other| other.line() == Some(constant_line)) {
+                errors.push(e);
+            }
+        }
+
+        Ok((layout, sections))
+    }
+
+    /// closes the segment being filled, keeping it if any words went into it
+    fn start_segment(layout: &mut Layout, segment: &mut Segment, section: &str, start: u16, line: usize) {
+        let next = Segment { section: section.to_string(), start, end: start, line };
+        let finished = std::mem::replace(segment, next);
+        if !finished.is_empty() {
+            layout.segments.push(finished);
+        }
+    }
+
+    /// binds STAŁA constants not yet in the table;
+    /// unless strict, the ones referring to symbols not defined yet are left for later
+    fn define_constants(
+        symbol_table: &mut SymbolTable,
+        directive_processor: &DirectiveProcessor,
+        constants: &[(&Directive, u16)],
+        strict: bool,
+        errors: &mut Vec<AssemblerError>
+    ) {
+        for index in 0..constants.len() {
+            match Self::define_constant(symbol_table, directive_processor, constants, index, &mut Vec::new()) {
+                Ok(()) => {}
+                Err(AssemblerError::UndefinedSymbol { .. }) if !strict => {}
+                Err(e) => {
+                    // bound to 0 so its uses are not reported as undefined too
+                    if let Some(name) = constants[index].0.arguments.first() {
+                        let _ = symbol_table.define_constant(name.clone(), 0);
+                    }
+                    if !errors.contains(&e) {
+                        errors.push(e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// defines the constants a value depends on first, chain holds the ones being defined
+    fn define_constant(
+        symbol_table: &mut SymbolTable,
+        directive_processor: &DirectiveProcessor,
+        constants: &[(&Directive, u16)],
+        index: usize,
+        chain: &mut Vec<usize>
+    ) -> Result<(), AssemblerError> {
+        let (directive, address) = constants[index];
+        let name = &directive.arguments[0];
+
+        if symbol_table.resolve(name).is_some_and(|symbol| symbol.symbol_type == SymbolType::Constant) {
+            return Ok(());
+        }
+
+        // A = B + 1, B = A * 2
+        if let Some(start) = chain.iter().position(|&other| other == index) {
+            let cycle = chain[start..].iter()
+                .chain([&index])
+                .map(|&other| constants[other].0.arguments[0].as_str())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(AssemblerError::CircularConstant {
+                cycle,
+                line: directive.line,
+            });
+        }
+
+        let dependencies = match directive.expressions.get(1).and_then(Option::as_ref) {
+            Some(expression) => expression.symbols(),
+            None => directive.arguments.get(1).map(|value| vec![value.as_str()]).unwrap_or_default(),
+        };
+
+        chain.push(index);
+        for dependency in dependencies {
+            if let Some(other) = constants.iter().position(|(other, _)| other.arguments[0] == dependency) {
+                Self::define_constant(symbol_table, directive_processor, constants, other, chain)?;
+            }
         }
+        chain.pop();
 
-        Ok(())
+        let value = directive_processor.constant_value(directive, symbol_table, address)?;
+        symbol_table.define_constant(name.clone(), value).map_err(|mut e| {
+            if let AssemblerError::DuplicateSymbol { line, .. } = &mut e {
+                *line = directive.line;
+            }
+            e
+        })
     }
 }
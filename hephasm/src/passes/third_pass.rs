@@ -5,42 +5,51 @@ use crate::symbol_table::SymbolTable;
 use crate::instruction::InstructionAssembler;
 use crate::operand::OperandResolver;
 use crate::directive::DirectiveProcessor;
+use crate::layout::Layout;
 use parseid::ast::ProgramElement;
 
 pub struct ThirdPass;
 
 impl ThirdPass {
+    /// memory up to the highest word used, every element written at the address the second pass gave it;
+    /// elements in error are left as zeros
     pub fn execute(
-        memory: &mut Vec<u16>,
-        current_address: &mut u16,
         symbol_table: &SymbolTable,
         instruction_assembler: &InstructionAssembler,
         operand_resolver: &OperandResolver,
         directive_processor: &DirectiveProcessor,
-        elements: &[ProgramElement]
-    ) -> Result<(), AssemblerError> {
-        *current_address = 0;
+        layout: &Layout,
+        elements: &[ProgramElement],
+        errors: &mut Vec<AssemblerError>
+    ) -> Vec<u16> {
+        let mut memory = vec![0; layout.end() as usize];
 
-        for element in elements {
+        for (index, element) in elements.iter().enumerate() {
+            let address = layout.addresses[index];
             match element {
                 ProgramElement::Instruction(inst) => {
-                    let argument = if let Some(operand) = &inst.operand {
-                        operand_resolver.resolve_symbol_to_address(operand, symbol_table, *current_address, inst.line)?
-                    } else {
-                        0
+                    let argument = match &inst.operand {
+                        Some(operand) => operand_resolver.resolve_symbol_to_address(operand, symbol_table, address, inst.line),
+                        None => Ok(0),
                     };
 
-                    let machine_code = instruction_assembler.assemble_instruction(inst, argument)?;
-                    memory[*current_address as usize] = machine_code;
-                    *current_address += 1;
+                    // one word, or two for the far form
+                    let far = layout.far_instructions.contains(&index);
+                    match argument.and_then(|argument| instruction_assembler.assemble_instruction(inst, argument, far)) {
+                        Ok(words) => {
+                            for (offset, word) in words.into_iter().enumerate() {
+                                memory[address as usize + offset] = word;
+                            }
+                        }
+                        Err(e) => errors.push(e),
+                    }
                 }
                 ProgramElement::Directive(dir) => {
-                    directive_processor.assemble_directive(dir, memory, *current_address as usize)?;
-                    match dir.name.to_uppercase().as_str() {
-                        "RST" | "RPA" => {
-                            *current_address += 1;
+                    // a directive the second pass could not size has its error reported there already
+                    if let Err(e) = directive_processor.assemble_directive(dir, &mut memory, address as usize, layout.sizes[index] as usize, symbol_table) {
+                        if !errors.contains(&e) {
+                            errors.push(e);
                         }
-                        _ => {}
                     }
                 }
                 ProgramElement::LabelDefinition(_) => {
@@ -50,6 +59,6 @@ impl ThirdPass {
             }
         }
 
-        Ok(())
+        memory
     }
 }
@@ -0,0 +1,223 @@
+//! which words of a module depend on where it is placed or on symbols of other modules
+
+use crate::error::AssemblerError;
+use crate::layout::Layout;
+use crate::object::{Relocation, RelocationKind, RelocationTarget};
+use parseid::ast::{AddressingMode, BinaryOperator, Directive, Expression, Operand, ProgramElement};
+use parseid::{is_constant_directive, is_import_directive, is_origin_directive, is_section_directive};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// how many times each target is added into a value, None standing for the module's own address
+type Weights<'a> = BTreeMap<Option<&'a str>, i32>;
+
+pub(crate) struct Relocator<'a> {
+    labels: HashSet<&'a str>,
+    constants: HashMap<&'a str, &'a Directive>,
+    imports: HashSet<&'a str>,
+}
+
+impl<'a> Relocator<'a> {
+    pub fn new(elements: &'a [ProgramElement]) -> Self {
+        let mut relocator = Self {
+            labels: HashSet::new(),
+            constants: HashMap::new(),
+            imports: HashSet::new(),
+        };
+
+        for element in elements {
+            match element {
+                ProgramElement::LabelDefinition(label) => {
+                    relocator.labels.insert(label.name.as_str());
+                }
+                ProgramElement::Directive(dir) if is_constant_directive(&dir.name) && dir.arguments.len() == 2 => {
+                    relocator.constants.entry(dir.arguments[0].as_str()).or_insert(dir);
+                }
+                ProgramElement::Directive(dir) if is_import_directive(&dir.name) => {
+                    relocator.imports.extend(dir.arguments.iter().map(String::as_str));
+                }
+                _ => {}
+            }
+        }
+
+        relocator
+    }
+
+    /// instructions whose argument the linker has to patch, kept in the far form so any address fits
+    pub fn relocated_instructions(&self, elements: &[ProgramElement], errors: &mut Vec<AssemblerError>) -> HashSet<usize> {
+        let mut relocated = HashSet::new();
+        for (index, element) in elements.iter().enumerate() {
+            match element {
+                ProgramElement::Instruction(inst) => {
+                    if let Some(operand) = &inst.operand {
+                        match self.operand_target(operand, inst.line) {
+                            Ok(Some(_)) => {
+                                relocated.insert(index);
+                            }
+                            Ok(None) => {}
+                            Err(e) => errors.push(e),
+                        }
+                    }
+                }
+                // a module is placed as a whole, wherever the linker puts it
+                ProgramElement::Directive(dir) if is_origin_directive(&dir.name) || (is_section_directive(&dir.name) && dir.arguments.len() > 1) => {
+                    errors.push(AssemblerError::InvalidDirectiveArguments {
+                        directive: dir.name.clone(),
+                        reason: "fixed addresses cannot be used in object files".to_string(),
+                        line: dir.line,
+                    });
+                }
+                _ => {}
+            }
+        }
+        relocated
+    }
+
+    /// relocation of every word holding an address
+    pub fn relocations(&self, elements: &[ProgramElement], layout: &Layout) -> Result<Vec<Relocation>, AssemblerError> {
+        let mut relocations = Vec::new();
+
+        for (index, element) in elements.iter().enumerate() {
+            let address = layout.addresses[index];
+            match element {
+                ProgramElement::Instruction(inst) => {
+                    let target = match &inst.operand {
+                        Some(operand) => self.operand_target(operand, inst.line)?,
+                        None => None,
+                    };
+                    if let Some(target) = target {
+                        relocations.push(Relocation { offset: address, kind: RelocationKind::Far, target });
+                    }
+                }
+                ProgramElement::Directive(dir) if is_constant_directive(&dir.name) && dir.arguments.len() == 2 => {
+                    // STAŁA twice = start * 2 has no value once the module is moved
+                    self.argument_target(dir, 1)?;
+                }
+                ProgramElement::Directive(dir) if dir.name.eq_ignore_ascii_case("RST") => {
+                    for argument in 0..dir.arguments.len() {
+                        if let Some(target) = self.argument_target(dir, argument)? {
+                            relocations.push(Relocation { offset: address + argument as u16, kind: RelocationKind::Word, target });
+                        }
+                    }
+                }
+                ProgramElement::Directive(dir) if dir.name.eq_ignore_ascii_case("RPA") && !dir.arguments.is_empty() => {
+                    // the count decides the layout, only the fill value can be patched
+                    if self.argument_target(dir, 0)?.is_some() {
+                        return Err(AssemblerError::InvalidRelocation {
+                            expression: dir.arguments[0].clone(),
+                            line: dir.line,
+                        });
+                    }
+                    if dir.arguments.len() == 2 {
+                        if let Some(target) = self.argument_target(dir, 1)? {
+                            relocations.extend((address..address + layout.sizes[index]).map(|offset| Relocation {
+                                offset,
+                                kind: RelocationKind::Word,
+                                target: target.clone(),
+                            }));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(relocations)
+    }
+
+    /// whether an exported symbol moves with the module, imported symbols cannot be exported
+    pub fn symbol_target(&self, name: &str, line: usize) -> Result<Option<RelocationTarget>, AssemblerError> {
+        self.target(name, None, line)
+    }
+
+    fn operand_target(&self, operand: &Operand, line: usize) -> Result<Option<RelocationTarget>, AssemblerError> {
+        match &operand.addressing_mode {
+            // registers and distances from the instruction stay the same
+            AddressingMode::Register
+            | AddressingMode::RegisterIndirect
+            | AddressingMode::Relative => Ok(None),
+            // the register sits above the address bits, so patching adds to the address alone
+            AddressingMode::BaseRegister { offset: address, .. }
+            | AddressingMode::Indexed { address, .. } => self.target(address, operand.expression.as_ref(), line),
+            _ => self.target(&operand.value, operand.expression.as_ref(), line),
+        }
+    }
+
+    fn argument_target(&self, directive: &Directive, index: usize) -> Result<Option<RelocationTarget>, AssemblerError> {
+        let expression = directive.expressions.get(index).and_then(Option::as_ref);
+        self.target(&directive.arguments[index], expression, directive.line)
+    }
+
+    /// label + 3 moves with the module, ext - 1 with ext, end - start not at all
+    fn target(&self, value: &str, expression: Option<&Expression>, line: usize) -> Result<Option<RelocationTarget>, AssemblerError> {
+        let weights = match expression {
+            Some(expression) => self.expression_weights(expression, &mut Vec::new()),
+            None => Some(self.symbol_weights(value, &mut Vec::new())),
+        };
+        let invalid = || AssemblerError::InvalidRelocation {
+            expression: expression.map_or_else(|| value.to_string(), Expression::to_string),
+            line,
+        };
+
+        let mut targets = weights.ok_or_else(invalid)?.into_iter().filter(|&(_, weight)| weight != 0);
+        match (targets.next(), targets.next()) {
+            (None, _) => Ok(None),
+            (Some((target, 1)), None) => Ok(Some(match target {
+                Some(name) => RelocationTarget::Symbol(name.to_string()),
+                None => RelocationTarget::Module,
+            })),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// None when an address is multiplied, shifted or compared
+    fn expression_weights<'b>(&'b self, expression: &'b Expression, chain: &mut Vec<&'b str>) -> Option<Weights<'b>> {
+        match expression {
+            Expression::Number(_) => Some(Weights::new()),
+            Expression::Symbol(name) => Some(self.symbol_weights(name, chain)),
+            Expression::CurrentAddress => Some(Weights::from([(None, 1)])),
+            Expression::Negate(inner) => {
+                let mut weights = self.expression_weights(inner, chain)?;
+                weights.values_mut().for_each(|weight| *weight = -*weight);
+                Some(weights)
+            }
+            Expression::Binary { operator, left, right } => {
+                let mut weights = self.expression_weights(left, chain)?;
+                let right = self.expression_weights(right, chain)?;
+                let sign = match operator {
+                    BinaryOperator::Add => 1,
+                    BinaryOperator::Subtract => -1,
+                    _ if weights.values().chain(right.values()).all(|&weight| weight == 0) => return Some(Weights::new()),
+                    _ => return None,
+                };
+                for (target, weight) in right {
+                    *weights.entry(target).or_insert(0) += sign * weight;
+                }
+                Some(weights)
+            }
+        }
+    }
+
+    /// a constant weighs as much as its value, chain guards against circular constants reported elsewhere
+    fn symbol_weights<'b>(&'b self, name: &'b str, chain: &mut Vec<&'b str>) -> Weights<'b> {
+        if self.imports.contains(name) {
+            return Weights::from([(Some(name), 1)]);
+        }
+        if self.labels.contains(name) {
+            return Weights::from([(None, 1)]);
+        }
+
+        match self.constants.get(name) {
+            Some(directive) if !chain.contains(&name) => {
+                chain.push(name);
+                let weights = match &directive.expressions[1] {
+                    Some(expression) => self.expression_weights(expression, chain),
+                    None => Some(self.symbol_weights(&directive.arguments[1], chain)),
+                };
+                chain.pop();
+                // reported where the constant is defined
+                weights.unwrap_or_default()
+            }
+            _ => Weights::new(),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-//! symbol table management for labels & variables
+//! symbol table management for labels, variables & constants
 
 use crate::error::AssemblerError;
 use crate::types::{Symbol, SymbolType};
@@ -23,7 +23,19 @@ impl SymbolTable {
                 line: 0 // will be filled by caller
             });
         }
-        self.symbols.insert(name, Symbol { address, symbol_type });
+        self.symbols.insert(name, Symbol { value: address as i32, symbol_type });
+        Ok(())
+    }
+
+    /// STAŁA value, -32768..=65535
+    pub fn define_constant(&mut self, name: String, value: i32) -> Result<(), AssemblerError> {
+        if self.symbols.contains_key(&name) {
+            return Err(AssemblerError::DuplicateSymbol { 
+                symbol: name, 
+                line: 0 // will be filled by caller
+            });
+        }
+        self.symbols.insert(name, Symbol { value, symbol_type: SymbolType::Constant });
         Ok(())
     }
 
@@ -31,8 +43,18 @@ impl SymbolTable {
         self.symbols.get(name)
     }
 
+    /// negative constants come back in two's complement
     pub fn get_address(&self, name: &str) -> Option<u16> {
-        self.symbols.get(name).map(|s| s.address)
+        self.symbols.get(name).map(|s| s.value as u16)
+    }
+
+    pub fn get_value(&self, name: &str) -> Option<i32> {
+        self.symbols.get(name).map(|s| s.value)
+    }
+
+    /// in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Symbol)> {
+        self.symbols.iter().map(|(name, symbol)| (name.as_str(), symbol))
     }
 
     pub fn clear(&mut self) {
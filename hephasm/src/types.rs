@@ -1,10 +1,11 @@
 //! types for hephasm assembler
 
-use parseid::ast::ProgramElement;
+use parseid::ast::{Operand, ProgramElement};
 
 #[derive(Debug, Clone)]
 pub struct Symbol {
-    pub address: u16,
+    /// address of a label or variable, value of a constant
+    pub value: i32,
     pub symbol_type: SymbolType,
 }
 
@@ -12,10 +13,17 @@ pub struct Symbol {
 pub enum SymbolType {
     Label,
     Variable,
+    Constant,
+    /// IMPORTUJ name - defined by another module, 0 until linked
+    External,
 }
 
 #[derive(Debug, Clone)]
 pub struct ExpandedMacro {
     pub parameters: Vec<String>,
+    /// default argument of each parameter, parallel to parameters
+    pub defaults: Vec<Option<Operand>>,
+    /// last parameter takes all remaining arguments
+    pub variadic: bool,
     pub body: Vec<ProgramElement>,
 }
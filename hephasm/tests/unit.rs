@@ -8,4 +8,11 @@ mod unit {
     pub mod macro_tests;
     pub mod error_tests;
     pub mod number_format_tests;
+    pub mod expression_tests;
+    pub mod constant_tests;
+    pub mod conditional_tests;
+    pub mod debug_info_tests;
+    pub mod linker_tests;
+    pub mod section_tests;
+    pub mod lint_tests;
 }
@@ -1,4 +1,4 @@
-use hephasm::assemble_source;
+use hephasm::{assemble_source, AssemblerError};
 
 #[test]
 fn test_immediate_addressing() {
@@ -23,16 +23,56 @@ fn test_register_addressing() {
 #[test]
 fn test_indexed_addressing() {
     let machine_code = assemble_source(r#"
+        POB array[R1]
+        STP
+        array: RST 0
+    "#).unwrap();
+    
+    // R1 sits above the address of array (3), so the argument takes the far form
+    assert_eq!(machine_code.len(), 4);
+    let argument = (1u16 << 11) | 3;
+    assert_eq!(machine_code[0], (0b11111u16 << 11) | (argument >> 8));
+    assert_eq!(machine_code[1], (0b00100u16 << 11) | (0b110u16 << 8) | (argument & 0xFF));
+}
+
+#[test]
+fn test_indexed_addressing_requires_register_index() {
+    let error = assemble_source(r#"
         array: RST 0
         POB array[i]
         i: RST 1
+    "#).unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::InvalidRegister { register, .. }) if register == "i"));
+}
+
+#[test]
+fn test_base_register_offset_expression() {
+    let machine_code = assemble_source(r#"
+        POB R0[tab + 2]
+        STP
+        tab: RPA 4
     "#).unwrap();
     
-    assert_eq!(machine_code.len(), 3);
+    // R0 leaves the register bits clear, so the short form fits
+    let expected = (0b00100u16 << 11) | (0b110u16 << 8) | 4;
+    assert_eq!(machine_code[0], expected);
+}
+
+#[test]
+fn test_indirect_addressing_with_forward_labels() {
+    let machine_code = assemble_source(r#"
+        POB [ptr]
+        DOD [[handle]]
+        STP
+        ptr: RST value
+        handle: RST ptr
+        value: RST 7
+    "#).unwrap();
     
-    // POB array[i] should use direct addressing mode with address of array (0)
-    let expected = (0b00100u16 << 11) | (0b000u16 << 8) | 0;
-    assert_eq!(machine_code[1], expected);
+    let expected_pob = (0b00100u16 << 11) | (0b010u16 << 8) | 3;
+    let expected_dod = (0b00001u16 << 11) | (0b011u16 << 8) | 4;
+    assert_eq!(machine_code[0], expected_pob);
+    assert_eq!(machine_code[1], expected_dod);
 }
 
 #[test]
@@ -45,8 +85,8 @@ fn test_relative_addressing() {
     
     assert_eq!(machine_code.len(), 3);
     
-    // SOB +2 from address 0 should jump to address 2, using relative addressing mode
-    let expected_sob = (0b00101u16 << 11) | (0b111u16 << 8) | 2;
+    // SOB +2 from address 0 jumps to address 2, encoded as offset 1 from the next word
+    let expected_sob = (0b00101u16 << 11) | (0b111u16 << 8) | 1;
     assert_eq!(machine_code[0], expected_sob);
 }
 
@@ -60,8 +100,8 @@ fn test_negative_relative_addressing() {
     
     assert_eq!(machine_code.len(), 2);
     
-    // SOB -1 from address 1 should jump to address 0, using relative addressing mode
-    let expected_sob = (0b00101u16 << 11) | (0b111u16 << 8) | 0;
+    // SOB -1 from address 1 jumps to address 0, encoded as offset -2 from the next word
+    let expected_sob = (0b00101u16 << 11) | (0b111u16 << 8) | 0xFE;
     assert_eq!(machine_code[1], expected_sob);
 }
 
@@ -103,3 +143,85 @@ fn test_mixed_addressing_modes() {
     let expected_stp = (0b00111u16 << 11) | (0b000u16 << 8) | 0;
     assert_eq!(machine_code[5], expected_stp);
 }
+
+#[test]
+fn test_far_direct_addressing() {
+    // label beyond the 8-bit argument range needs the two-word far form
+    let source = format!("SOB far\n{}far: STP", "RST 0\n".repeat(300));
+    let machine_code = assemble_source(&source).unwrap();
+
+    // prefix + SOB, 300 data words, STP
+    assert_eq!(machine_code.len(), 303);
+
+    let far_address = 302u16;
+    assert_eq!(machine_code[0], (0b11111u16 << 11) | (far_address >> 8));
+    assert_eq!(machine_code[1], (0b00101u16 << 11) | (far_address & 0xFF));
+    assert_eq!(machine_code[302], 0b00111u16 << 11);
+}
+
+#[test]
+fn test_far_immediate_addressing() {
+    let machine_code = assemble_source("POB #1000").unwrap();
+    assert_eq!(machine_code.len(), 2);
+
+    assert_eq!(machine_code[0], (0b11111u16 << 11) | (1000 >> 8));
+    assert_eq!(machine_code[1], (0b00100u16 << 11) | (0b001u16 << 8) | (1000 & 0xFF));
+}
+
+#[test]
+fn test_far_form_shifts_following_labels() {
+    // every far instruction grows by one word, moving labels after it
+    let source = format!("POB #300\nPOB #400\nSOB end\n{}end: STP", "RST 0\n".repeat(251));
+    let machine_code = assemble_source(&source).unwrap();
+
+    // with a short SOB end would be at 256, so SOB becomes far too: 2 + 2 + 2 + 251 + 1
+    assert_eq!(machine_code.len(), 258);
+
+    let end_address = 257u16;
+    assert_eq!(machine_code[4], (0b11111u16 << 11) | (end_address >> 8));
+    assert_eq!(machine_code[5], (0b00101u16 << 11) | (end_address & 0xFF));
+}
+
+#[test]
+fn test_short_form_near_addresses() {
+    let machine_code = assemble_source("POB #255\nSOB 255").unwrap();
+    assert_eq!(machine_code.len(), 2);
+}
+
+#[test]
+fn test_label_relative_addressing() {
+    let machine_code = assemble_source(r#"
+        loop:
+        DOD 100
+        SOZ +end
+        SOB +loop
+        end: STP
+    "#).unwrap();
+    
+    // offsets from the word after the jump: 3 - 2 = 1 and 0 - 3 = -3
+    assert_eq!(machine_code[1], (0b10000u16 << 11) | (0b111u16 << 8) | 1);
+    assert_eq!(machine_code[2], (0b00101u16 << 11) | (0b111u16 << 8) | 0xFD);
+}
+
+#[test]
+fn test_relative_addressing_range() {
+    // 128 words ahead is the furthest an offset reaches, 127 back
+    assert!(assemble_source("SOB +128\nRPA 127\nSTP").is_ok());
+    assert!(assemble_source("start: RPA 127\nSOB +start").is_ok());
+
+    let error = assemble_source("SOB +129\nRPA 128\nSTP").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::RelativeOutOfRange { operand, distance: 129, line: 1 }) if operand == "+129"
+    ));
+
+    let error = assemble_source("start: RPA 200\nSOB +start").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::RelativeOutOfRange { distance: -200, line: 2, .. })));
+
+    // pointing at the operand, not the mnemonic
+    let error = assemble_source("SOB -1").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::AddressOutOfBounds { column: Some(5), .. })));
+
+    let error = assemble_source("SOB +nowhere").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::UndefinedSymbol { symbol, .. }) if symbol == "nowhere"));
+}
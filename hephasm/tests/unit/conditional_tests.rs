@@ -0,0 +1,98 @@
+use hephasm::{assemble_source, Assembler, AssemblerError};
+
+const VERSIONED: &str = r#"
+    STAŁA VERSION = 1
+    JEŻELI VERSION > 1
+        POB #2
+    INACZEJ
+        POB #1
+    KONJ
+    STP
+"#;
+
+#[test]
+fn test_conditional_selects_branch() {
+    let machine_code = assemble_source(VERSIONED).unwrap();
+    assert_eq!(machine_code, vec![(0b00100 << 11) | (0b001 << 8) | 1, 0b00111 << 11]);
+
+    let machine_code = assemble_source("JEŻELI 0\nPOB #1\nKONJ\nSTP").unwrap();
+    assert_eq!(machine_code, vec![0b00111 << 11]);
+}
+
+#[test]
+fn test_definition_overrides_constant() {
+    let program = parseid::parse_source(VERSIONED).unwrap();
+    let mut assembler = Assembler::new();
+    assembler.define_constant("VERSION", 2);
+    let machine_code = assembler.assemble(&program).unwrap();
+    assert_eq!(machine_code, vec![(0b00100 << 11) | (0b001 << 8) | 2, 0b00111 << 11]);
+
+    // definitions are ordinary constants for the rest of the program
+    let program = parseid::parse_source("JEŻELI FAST\nPOB #LIMIT\nKONJ").unwrap();
+    let mut assembler = Assembler::new();
+    assembler.define_constant("FAST", 1);
+    assembler.define_constant("LIMIT", 9);
+    assert_eq!(assembler.assemble(&program).unwrap(), vec![(0b00100 << 11) | (0b001 << 8) | 9]);
+}
+
+#[test]
+fn test_nested_conditionals_and_constants_in_branches() {
+    let machine_code = assemble_source(r#"
+        STAŁA TARGET = 2
+        JEŻELI TARGET >= 1
+            JEŻELI TARGET == 2
+                STAŁA WORD = 0x22
+            INACZEJ
+                STAŁA WORD = 0x11
+            KONJ
+        INACZEJ
+            STAŁA WORD = 0
+        KONJ
+        JEŻELI WORD != 0x22
+            RST 0
+        KONJ
+        RST WORD
+    "#).unwrap();
+    assert_eq!(machine_code, vec![0x22]);
+}
+
+#[test]
+fn test_conditionals_with_macros() {
+    // macros only exist in the selected branch, conditions in a body see the arguments
+    let machine_code = assemble_source(r#"
+        STAŁA EXTENDED = 0
+        JEŻELI EXTENDED
+            MAKRO load value
+                POB #1
+            KONM
+        INACZEJ
+            MAKRO load value
+                JEŻELI value > 5
+                    POB #5
+                INACZEJ
+                    POB #value
+                KONJ
+            KONM
+        KONJ
+        load 9
+        STP
+    "#).unwrap();
+    assert_eq!(machine_code, vec![(0b00100 << 11) | (0b001 << 8) | 5, 0b00111 << 11]);
+}
+
+#[test]
+fn test_condition_errors() {
+    let error = assemble_source("JEŻELI MISSING\nKONJ").unwrap_err();
+    assert_eq!(error.downcast_ref::<AssemblerError>(), Some(&AssemblerError::UndefinedSymbol {
+        symbol: "MISSING".to_string(),
+        line: 1,
+        suggestion: None,
+    }));
+
+    // labels get their addresses only after the branches are chosen
+    let error = assemble_source("start: STP\nJEŻELI start\nKONJ").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::UndefinedSymbol { line: 2, .. })));
+
+    let error = assemble_source("JEŻELI $ > 0\nKONJ").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::InvalidDirectiveArguments { line: 1, .. })));
+}
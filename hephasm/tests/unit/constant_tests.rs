@@ -0,0 +1,104 @@
+use hephasm::{assemble_source, AssemblerError};
+
+#[test]
+fn test_constant_in_immediate_operand() {
+    let machine_code = assemble_source(r#"
+        STAŁA SIZE = 16
+        STALA LIMIT 0x7F
+        POB #SIZE
+        DOD #LIMIT
+        STP
+    "#).unwrap();
+    assert_eq!(machine_code.len(), 3);
+    assert_eq!(machine_code[0], (0b00100 << 11) | (0b001 << 8) | 16);
+    assert_eq!(machine_code[1], (0b00001 << 11) | (0b001 << 8) | 0x7F);
+    assert_eq!(machine_code[2], 0b00111 << 11);
+}
+
+#[test]
+fn test_constant_in_data_directives() {
+    let machine_code = assemble_source(r#"
+        STAŁA COUNT = 3
+        STAŁA FILL = -1
+        tab: RPA COUNT, FILL
+        RST COUNT * 2, FILL
+    "#).unwrap();
+    assert_eq!(machine_code, vec![0xFFFF, 0xFFFF, 0xFFFF, 6, 0xFFFF]);
+}
+
+#[test]
+fn test_constant_forward_references() {
+    // constants may use labels and constants defined further down
+    let machine_code = assemble_source(r#"
+        STAŁA LENGTH = end - tab
+        STAŁA HALF = LENGTH / 2
+        POB #HALF
+        POB #LENGTH
+        STP
+        tab: RST 1, 2, 3, 4
+        end: RPA
+    "#).unwrap();
+    assert_eq!(machine_code[0], (0b00100 << 11) | (0b001 << 8) | 2);
+    assert_eq!(machine_code[1], (0b00100 << 11) | (0b001 << 8) | 4);
+}
+
+#[test]
+fn test_constant_as_address() {
+    let machine_code = assemble_source(r#"
+        STAŁA COUNTER = 100
+        POB COUNTER
+        ŁAD COUNTER+1
+    "#).unwrap();
+    assert_eq!(machine_code[0], (0b00100 << 11) | 100);
+    assert_eq!(machine_code[1], (0b00011 << 11) | 101);
+}
+
+#[test]
+fn test_constant_redefinition() {
+    let error = assemble_source("STAŁA A = 1\nSTAŁA A = 2").unwrap_err();
+    assert_eq!(error.downcast_ref::<AssemblerError>(), Some(&AssemblerError::DuplicateSymbol {
+        symbol: "A".to_string(),
+        line: 2,
+    }));
+
+    let error = assemble_source("start: STP\nSTAŁA start = 2").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::DuplicateSymbol { line: 2, .. })));
+
+    let error = assemble_source("STAŁA start = 2\nstart: STP").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::DuplicateSymbol { line: 2, .. })));
+}
+
+#[test]
+fn test_circular_constants() {
+    let error = assemble_source("STAŁA A = B + 1\nSTAŁA B = A * 2\nPOB #A").unwrap_err();
+    assert_eq!(error.downcast_ref::<AssemblerError>(), Some(&AssemblerError::CircularConstant {
+        cycle: "A -> B -> A".to_string(),
+        line: 1,
+    }));
+
+    let error = assemble_source("STAŁA A = A").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::CircularConstant { .. })));
+}
+
+#[test]
+fn test_constant_errors() {
+    let error = assemble_source("STAŁA A = missing").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::UndefinedSymbol { symbol, .. }) if symbol == "missing"
+    ));
+
+    let error = assemble_source("STAŁA BIG = 70000").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::ExpressionOutOfRange { value: 70000, .. })));
+
+    // immediate operands take no negative values
+    let error = assemble_source("STAŁA NEG = -1\nPOB #NEG").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::ExpressionOutOfRange { value: -1, .. })));
+
+    // RPA count may only use what is known at that point
+    let error = assemble_source("RPA N\nSTAŁA N = 2").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::UndefinedSymbol { .. })));
+
+    let error = assemble_source("STAŁA A = 1, 2").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::InvalidDirectiveArguments { .. })));
+}
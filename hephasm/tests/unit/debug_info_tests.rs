@@ -0,0 +1,149 @@
+use hephasm::{Assembler, DebugInfo, SourceLocation, SymbolType, WordKind};
+
+fn assemble_with_debug_info(source: &str) -> (Vec<u16>, DebugInfo) {
+    let program = parseid::parse_source(source).unwrap();
+    let mut assembler = Assembler::new();
+    assembler.assemble_with_debug_info(&program).unwrap()
+}
+
+#[test]
+fn test_words_map_to_source_lines() {
+    let (machine_code, debug_info) = assemble_with_debug_info("start: POB value\n\n    DOD value\nSTP\nvalue: RST 5\ntab: RPA 2");
+    assert_eq!(machine_code.len(), 6);
+    assert_eq!(debug_info.words.len(), 6);
+
+    let lines: Vec<usize> = debug_info.words.iter().flatten().map(|word| word.line).collect();
+    assert_eq!(lines, [1, 3, 4, 5, 6, 6]);
+    assert_eq!(debug_info.location(1), Some(&SourceLocation {
+        file: None,
+        line: 3,
+        column: 5,
+        expansion: None,
+        kind: WordKind::Instruction,
+    }));
+    assert_eq!(debug_info.location(4).unwrap().kind, WordKind::Data);
+    assert_eq!(debug_info.location(6), None);
+
+    assert_eq!(debug_info.label_at(0), Some("start"));
+    assert_eq!(debug_info.label_at(3), Some("value"));
+    assert_eq!(debug_info.label_at(1), None);
+    let names: Vec<&str> = debug_info.symbols.iter().map(|symbol| symbol.name.as_str()).collect();
+    assert_eq!(names, ["start", "tab", "value"]);
+}
+
+#[test]
+fn test_far_instruction_words_share_a_line() {
+    let (machine_code, debug_info) = assemble_with_debug_info("POB far\nSTP\nRPA 300\nfar: RST 1");
+    assert_eq!(machine_code.len(), debug_info.words.len());
+    assert_eq!(debug_info.location(0).unwrap().line, 1);
+    assert_eq!(debug_info.location(1).unwrap().line, 1);
+    assert_eq!(debug_info.location(2).unwrap().line, 2);
+}
+
+#[test]
+fn test_words_from_macro_expansions() {
+    let (_, debug_info) = assemble_with_debug_info(r#"
+MAKRO twice x
+    DOD x
+    DOD x
+KONM
+MAKRO outer y
+    twice y
+KONM
+    POB one
+    outer one
+    twice one
+one: RST 1
+"#);
+
+    assert_eq!(debug_info.expansions.len(), 3);
+    assert_eq!(debug_info.expansions[0].name, "outer");
+    assert_eq!(debug_info.expansions[0].line, 10);
+    assert_eq!(debug_info.expansions[1].parent, Some(0));
+    assert_eq!(debug_info.expansions[1].line, 7);
+    assert_eq!(debug_info.expansions[2].parent, None);
+
+    let origins: Vec<Option<usize>> = debug_info.words.iter().flatten().map(|word| word.expansion).collect();
+    assert_eq!(origins, [None, Some(1), Some(1), Some(2), Some(2), None]);
+    // body lines are the lines of the macro definition
+    assert_eq!(debug_info.location(1).unwrap().line, 3);
+    assert_eq!(debug_info.location(4).unwrap().line, 4);
+    assert_eq!(debug_info.macro_trace(1), "outer -> twice");
+}
+
+#[test]
+fn test_constants_in_debug_info() {
+    let program = parseid::parse_source("STAŁA SIZE = 4\nPOB #SIZE").unwrap();
+    let mut assembler = Assembler::new();
+    assembler.define_constant("MODE", -1);
+    let (_, debug_info) = assembler.assemble_with_debug_info(&program).unwrap();
+
+    let constants: Vec<(&str, i32)> = debug_info.symbols.iter()
+        .filter(|symbol| symbol.symbol_type == SymbolType::Constant)
+        .map(|symbol| (symbol.name.as_str(), symbol.value))
+        .collect();
+    assert_eq!(constants, [("MODE", -1), ("SIZE", 4)]);
+    // defined outside the program
+    assert_eq!(debug_info.symbols[0].line, None);
+    assert_eq!(debug_info.symbols[1].line, Some(1));
+    assert_eq!(debug_info.words.len(), 1);
+}
+
+#[test]
+fn test_symbol_definitions_and_references() {
+    let (_, debug_info) = assemble_with_debug_info(r#"
+MAKRO load x
+    POB x
+KONM
+STAŁA COUNT = 2
+start:
+    load value
+    DOD value + COUNT
+    ŁAD R1[tab + COUNT]
+    SOB start
+value: RST COUNT
+tab: RPA 2
+"#);
+
+    let defined = |name: &str| debug_info.symbols.iter().find(|symbol| symbol.name == name).and_then(|symbol| symbol.line);
+    assert_eq!(defined("COUNT"), Some(5));
+    assert_eq!(defined("start"), Some(6));
+    assert_eq!(defined("tab"), Some(12));
+
+    let references: Vec<(&str, usize)> = debug_info.references.iter()
+        .map(|reference| (reference.name.as_str(), reference.line))
+        .collect();
+    // uses in a macro body count at the call
+    assert_eq!(references, [
+        ("value", 7),
+        ("value", 8),
+        ("COUNT", 8),
+        ("tab", 9),
+        ("COUNT", 9),
+        ("start", 10),
+        ("COUNT", 11),
+    ]);
+}
+
+#[test]
+fn test_text_form_round_trip() {
+    let (_, mut debug_info) = assemble_with_debug_info("MAKRO m\n    DOD #1\nKONM\nstart: m\nSTP");
+    debug_info.locate_files(|line| Some((format!("dir with spaces/file{}.asmod", line % 2), line)));
+
+    let text = debug_info.to_string();
+    assert!(text.starts_with("; asmodeus debug info"));
+    assert!(text.contains("symbol start label 0 "));
+    assert!(text.contains("word 0 code "));
+    assert_eq!(text.parse::<DebugInfo>(), Ok(debug_info));
+}
+
+#[test]
+fn test_invalid_text_form() {
+    assert!("word 0 code - 1 1 -".parse::<DebugInfo>().is_err());
+
+    let error = "; asmodeus debug info 1\nword 0 code 3 1 1 -".parse::<DebugInfo>().unwrap_err();
+    assert_eq!(error, "line 2: unknown file: 3");
+
+    let error = "; asmodeus debug info 1\nword 1 code - 1 1 -\nword 1 code - 2 1 -".parse::<DebugInfo>().unwrap_err();
+    assert!(error.contains("expected word 2 or above"));
+}
@@ -13,3 +13,68 @@ fn test_rpa_directive() {
     assert_eq!(machine_code.len(), 1);
     assert_eq!(machine_code[0], 0);
 }
+
+#[test]
+fn test_string_directives() {
+    let machine_code = assemble_source(r#"NAPIS "AB""#).unwrap();
+    assert_eq!(machine_code, vec![65, 66]);
+    
+    let machine_code = assemble_source(r#"NAPISZ "AB""#).unwrap();
+    assert_eq!(machine_code, vec![65, 66, 0]);
+    
+    let machine_code = assemble_source(r#"NAPISD "A\n""#).unwrap();
+    assert_eq!(machine_code, vec![2, 65, 10]);
+}
+
+#[test]
+fn test_string_directive_layout() {
+    // label after the string lands past its last word
+    let machine_code = assemble_source(r#"
+        POB end
+        STP
+        msg: NAPISZ "xyz"
+        end: RST 7
+    "#).unwrap();
+    assert_eq!(machine_code[0], (0b00100 << 11) | 6);
+    assert_eq!(&machine_code[2..7], &[120, 121, 122, 0, 7]);
+}
+
+#[test]
+fn test_char_literal_operands() {
+    let machine_code = assemble_source("POB #'A'\nRST '\\n'").unwrap();
+    assert_eq!(machine_code, vec![(0b00100 << 11) | (0b001 << 8) | 65, 10]);
+}
+
+#[test]
+fn test_rst_value_list() {
+    let machine_code = assemble_source("RST 1, 3, 5, 4, 7").unwrap();
+    assert_eq!(machine_code, vec![1, 3, 5, 4, 7]);
+    
+    let machine_code = assemble_source("RST -1, 0x10, 'A'").unwrap();
+    assert_eq!(machine_code, vec![0xFFFF, 16, 65]);
+}
+
+#[test]
+fn test_rpa_count_and_fill() {
+    let machine_code = assemble_source("RPA 3\nRST 9").unwrap();
+    assert_eq!(machine_code, vec![0, 0, 0, 9]);
+    
+    let machine_code = assemble_source("RPA 4, 0xFF").unwrap();
+    assert_eq!(machine_code, vec![0xFF; 4]);
+}
+
+#[test]
+fn test_array_layout() {
+    // labels after arrays account for every word
+    let machine_code = assemble_source(r#"
+        POB last
+        STP
+        tab: RST 1, 2, 3
+        buf: RPA 10, -1
+        last: RST 42
+    "#).unwrap();
+    assert_eq!(machine_code[0], (0b00100 << 11) | 15);
+    assert_eq!(&machine_code[2..5], &[1, 2, 3]);
+    assert_eq!(&machine_code[5..15], &[0xFFFF; 10]);
+    assert_eq!(machine_code[15], 42);
+}
@@ -1,4 +1,4 @@
-use hephasm::assemble_source;
+use hephasm::{assemble_source, assemble_source_with_profile, Assembler, AssemblerError, IsaProfile, LinkError};
 
 #[test]
 fn test_error_undefined_symbol() {
@@ -50,3 +50,185 @@ fn test_error_macro_parameter_mismatch() {
     "#);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_error_invalid_register() {
+    let error = assemble_source("POB R8").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::InvalidRegister { .. })));
+}
+
+#[test]
+fn test_error_register_operation_without_register() {
+    let error = assemble_source("INR 5").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::InvalidAddressingMode { .. })));
+
+    let error = assemble_source("DER [R1]").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::InvalidAddressingMode { .. })));
+}
+
+#[test]
+fn test_error_bitwise_instruction_not_enabled() {
+    let error = assemble_source("AND #1").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::BitwiseInstructionNotEnabled { instruction, line: 1 }) if instruction == "AND"
+    ));
+
+    // extended mode does not enable bitwise set
+    let error = assemble_source_with_profile("NOT", "w+muldiv".parse().unwrap()).unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::BitwiseInstructionNotEnabled { .. })));
+}
+
+#[test]
+fn test_error_invalid_shift_operand() {
+    let error = assemble_source_with_profile("SHL 4", "w+bits".parse().unwrap()).unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::InvalidAddressingMode { .. })));
+
+    let error = assemble_source_with_profile("SHR #16", "w+bits".parse().unwrap()).unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::InvalidNumber { .. })));
+}
+
+#[test]
+fn test_error_instruction_not_in_profile() {
+    let error = assemble_source_with_profile("WYW 0", IsaProfile::CLASSIC_W).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::InstructionNotInProfile { instruction, profile, line: 1 }) if instruction == "WYW" && profile == "classic-w"
+    ));
+
+    // original Machine W instructions are always available
+    assert!(assemble_source_with_profile("POB #1\nSOZ 0\nSTP", IsaProfile::CLASSIC_W).is_ok());
+}
+
+#[test]
+fn test_error_isa_directive() {
+    // directive overrides the profile given to the assembler
+    let error = assemble_source_with_profile("ISA classic-w\nINR R1", IsaProfile::FULL).unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::InstructionNotInProfile { .. })));
+
+    let error = assemble_source("ISA w+floats\nSTP").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::UnknownIsaProfile { profile, line: 1 }) if profile == "w+floats"
+    ));
+
+    let error = assemble_source("ISA w\nISA full\nSTP").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::DuplicateIsaDirective { line: 2 })));
+
+    // a profile given from outside can be narrowed but not widened
+    let program = parseid::parse_source("ISA full\nSTP").unwrap();
+    let mut assembler = Assembler::new_with_profile(IsaProfile::CLASSIC_W);
+    assembler.lock_profile();
+    assert!(matches!(
+        assembler.assemble(&program),
+        Err(AssemblerError::IsaProfileNotAllowed { profile, allowed, line: 1 }) if profile == "full" && allowed == "classic-w"
+    ));
+
+    let program = parseid::parse_source("ISA classic-w\nINR R1").unwrap();
+    let mut assembler = Assembler::new_with_profile(IsaProfile::FULL);
+    assembler.lock_profile();
+    assert!(matches!(assembler.assemble(&program), Err(AssemblerError::InstructionNotInProfile { .. })));
+}
+
+#[test]
+fn test_error_array_directives() {
+    let error = assemble_source("RPA 2, 0, 1").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::InvalidDirectiveArguments { directive, line: 1, .. }) if directive == "RPA"
+    ));
+
+    let error = assemble_source("RPA -3").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::ExpressionOutOfRange { value: -3, .. })));
+
+    // reservation larger than memory
+    let error = assemble_source("STP\nRPA 2048").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::MemoryOverflow { line: 2 })));
+    assert!(assemble_source("STP\nRPA 2047").is_ok());
+}
+
+#[test]
+fn test_error_every_error_collected() {
+    let program = parseid::parse_source(r#"
+        start:
+        POB vaule
+        FOO 3
+        start:
+        DOD 1
+        STAŁA N = missing + 1
+        RPA N
+        STP
+    "#).unwrap();
+
+    let errors = Assembler::new().assemble_collecting_errors(&program).unwrap_err();
+    let lines: Vec<_> = errors.iter().map(AssemblerError::line).collect();
+    assert_eq!(lines, vec![Some(3), Some(4), Some(5), Some(7)]);
+    assert!(matches!(&errors[0], AssemblerError::UndefinedSymbol { symbol, .. } if symbol == "vaule"));
+    assert!(matches!(&errors[1], AssemblerError::MacroNotFound { name, .. } if name == "FOO"));
+    assert!(matches!(&errors[2], AssemblerError::DuplicateSymbol { symbol, .. } if symbol == "start"));
+    assert!(matches!(&errors[3], AssemblerError::UndefinedSymbol { symbol, .. } if symbol == "missing"));
+
+    // the first of them is what assemble reports
+    assert!(matches!(Assembler::new().assemble(&program), Err(AssemblerError::UndefinedSymbol { line: 3, .. })));
+}
+
+#[test]
+fn test_error_codes() {
+    let program = parseid::parse_source("POB vaule\nFOO 3\nSTP\n").unwrap();
+    let errors = Assembler::new().assemble_collecting_errors(&program).unwrap_err();
+    let codes: Vec<&str> = errors.iter().map(AssemblerError::code).collect();
+    assert_eq!(codes, ["E0301", "E0310"]);
+
+    assert_eq!(AssemblerError::MemoryOverflow { line: 1 }.code(), "E0321");
+    assert_eq!(LinkError::MemoryOverflow { size: 3000 }.code(), "E0404");
+}
+
+#[test]
+fn test_error_suggestions() {
+    let program = parseid::parse_source(r#"
+        MAKRO double x
+            POB x
+            DOD x
+        KONM
+        start:
+            POBB value
+            LADD value
+            wejsie
+            duble 2
+            SOB strat
+            DOD xyz
+        value: RST 2
+    "#).unwrap();
+
+    let errors = Assembler::new().assemble_collecting_errors(&program).unwrap_err();
+    let suggestions: Vec<Option<&str>> = errors.iter().map(AssemblerError::suggestion).collect();
+    // mnemonics (with the ASCII spellings), macros and labels; nothing close to xyz
+    assert_eq!(suggestions, [Some("POB"), Some("LAD"), Some("WEJSCIE"), Some("double"), Some("start"), None]);
+    assert_eq!(errors[4].to_string(), "Undefined symbol: strat at line 11 (did you mean start?)");
+    assert_eq!(errors[5].to_string(), "Undefined symbol: xyz at line 12");
+
+    // misspelled mnemonics are reported as such, not as calls of missing macros
+    assert_eq!(errors[0].to_string(), "Invalid opcode: POBB at line 7 (did you mean POB?)");
+    assert!(matches!(&errors[2], AssemblerError::InvalidOpcode { opcode, .. } if opcode == "wejsie"));
+    assert!(matches!(&errors[3], AssemblerError::MacroNotFound { name, .. } if name == "duble"));
+
+    // a transposition counts as one edit
+    let error = assemble_source("POB vaule\nSTP\nvalue: RST 1").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::UndefinedSymbol { suggestion: Some(name), .. }) if name == "value"));
+}
+
+#[test]
+fn test_error_suggestions_for_aliases() {
+    // Polish letters and their ASCII spellings, and the long aliases of WPR and WYJ
+    let cases = [("ŁADD", "ŁAD"), ("LADD", "LAD"), ("WEJSCE", "WEJSCIE"), ("WYJSICE", "WYJSCIE"), ("wyjscei", "WYJSCIE")];
+    for (misspelled, meant) in cases {
+        let error = assemble_source(&format!("{} 5\nSTP", misspelled)).unwrap_err();
+        assert!(
+            matches!(
+                error.downcast_ref::<AssemblerError>(),
+                Some(AssemblerError::InvalidOpcode { opcode, suggestion: Some(suggestion), .. }) if opcode == misspelled && suggestion == meant
+            ),
+            "{}: {}", misspelled, error
+        );
+    }
+}
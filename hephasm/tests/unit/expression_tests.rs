@@ -0,0 +1,127 @@
+use hephasm::{assemble_source, AssemblerError};
+
+#[test]
+fn test_label_arithmetic() {
+    let machine_code = assemble_source(r#"
+        POB tab+2
+        POB #end-tab
+        STP
+        tab: RST 10, 20, 30
+        end: RPA
+    "#).unwrap();
+    assert_eq!(machine_code[0], (0b00100 << 11) | 5);
+    assert_eq!(machine_code[1], (0b00100 << 11) | (0b001 << 8) | 3);
+}
+
+#[test]
+fn test_label_as_immediate() {
+    // plain, parenthesised and compound forms load the same address
+    let machine_code = assemble_source(r#"
+        POB #tab
+        POB #(tab)
+        POB #tab+0
+        STP
+        tab: RST 7
+    "#).unwrap();
+    let immediate: u16 = (0b00100 << 11) | (0b001 << 8) | 4;
+    assert_eq!(&machine_code[0..3], &[immediate, immediate, immediate]);
+
+    let error = assemble_source("POB #missing").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::UndefinedSymbol { .. })));
+    let error = assemble_source("POB #-1").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::InvalidNumber { .. })));
+}
+
+#[test]
+fn test_constant_arithmetic() {
+    let machine_code = assemble_source(r#"
+        POB #(1 << 4) | 3
+        POB #100 % 7 * 2
+        POB #0xF0 & 0x3C >> 2
+        POB #'a' - 'A'
+        POB #-(2 - 7)
+    "#).unwrap();
+    let immediate = |value: u16| (0b00100 << 11) | (0b001 << 8) | value;
+    assert_eq!(machine_code, vec![immediate(19), immediate(4), immediate(0), immediate(32), immediate(5)]);
+}
+
+#[test]
+fn test_current_address() {
+    let machine_code = assemble_source(r#"
+        POB #1
+        SOB $
+        SOB $+2
+        here: RST $, $-here
+    "#).unwrap();
+    assert_eq!(machine_code[1], (0b00101 << 11) | 1);
+    assert_eq!(machine_code[2], (0b00101 << 11) | 4);
+    assert_eq!(&machine_code[3..5], &[3, 0]);
+}
+
+#[test]
+fn test_expressions_in_directives() {
+    let machine_code = assemble_source(r#"
+        size: RST end-tab, tab
+        tab: RST 1, 2, 3, 4
+        end: RPA (end-tab)/2, end-size
+    "#).unwrap();
+    assert_eq!(&machine_code[0..2], &[4, 2]);
+    assert_eq!(&machine_code[6..8], &[6, 6]);
+    assert_eq!(machine_code.len(), 8);
+}
+
+#[test]
+fn test_far_form_from_expression() {
+    // argument above 255 needs the two-word form
+    let machine_code = assemble_source("POB #200 + 100").unwrap();
+    assert_eq!(machine_code.len(), 2);
+    assert_eq!(machine_code[0], (0b11111 << 11) | 1);
+    assert_eq!(machine_code[1], (0b00100 << 11) | (0b001 << 8) | 44);
+}
+
+#[test]
+fn test_expressions_in_macros() {
+    let machine_code = assemble_source(r#"
+        MAKRO load_at base offset
+            POB base+offset
+        KONM
+        load_at tab 2
+        STP
+        tab: RST 5, 6, 7
+    "#).unwrap();
+    assert_eq!(machine_code[0], (0b00100 << 11) | 4);
+}
+
+#[test]
+fn test_expression_errors() {
+    let error = assemble_source("POB #1 / (2 - 2)").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::DivisionByZero { line: 1, .. })));
+    
+    let error = assemble_source("POB #2 - 5").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::ExpressionOutOfRange { expression, value: -3, line: 1 }) if expression == "2-5"
+    ));
+    
+    let error = assemble_source("RST 0x100 * 0x100").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::ExpressionOutOfRange { value: 65536, .. })));
+    
+    // the whole expression and the value it overflows to, not just the shift amount
+    let error = assemble_source("POB #1<<40").unwrap_err();
+    assert_eq!(error.to_string(), "Expression 1<<40 = 1099511627776 is out of range at line 1");
+    let error = assemble_source("POB #(2 << 20) + 1").unwrap_err();
+    assert_eq!(error.to_string(), "Expression (2<<20)+1 = 2097153 is out of range at line 1");
+    let error = assemble_source("POB #1 << -1").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::ExpressionOutOfRange { value: -1, .. })));
+    assert_eq!(assemble_source("POB #0x100 >> 40").unwrap()[0], (0b00100 << 11) | (0b001 << 8));
+    
+    let error = assemble_source("POB missing+1").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::UndefinedSymbol { symbol, .. }) if symbol == "missing"
+    ));
+    
+    // RPA count can only use labels defined before it
+    let error = assemble_source("RPA end\nend: STP").unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::UndefinedSymbol { .. })));
+}
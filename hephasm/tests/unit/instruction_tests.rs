@@ -1,4 +1,4 @@
-use hephasm::assemble_source;
+use hephasm::{assemble_source, assemble_source_with_profile};
 
 #[test]
 fn test_simple_instruction() {
@@ -63,3 +63,115 @@ fn test_soz_instruction() {
     let expected = (0b10000u16 << 11) | (0b000u16 << 8) | 100;
     assert_eq!(machine_code[0], expected);
 }
+
+#[test]
+fn test_flag_jump_instructions() {
+    let machine_code = assemble_source(r#"
+        SOC 10
+        SOV 20
+        SOP 30
+        SNZ 40
+    "#).unwrap();
+    assert_eq!(machine_code.len(), 4);
+
+    // SOC = 0b11011, SOV = 0b11100, SOP = 0b11101, SNZ = 0b11110, direct addressing
+    assert_eq!(machine_code[0], (0b11011u16 << 11) | 10);
+    assert_eq!(machine_code[1], (0b11100u16 << 11) | 20);
+    assert_eq!(machine_code[2], (0b11101u16 << 11) | 30);
+    assert_eq!(machine_code[3], (0b11110u16 << 11) | 40);
+}
+
+#[test]
+fn test_register_load_store_instructions() {
+    let machine_code = assemble_source(r#"
+        POB R1
+        ŁAD R2
+        POB [R3]
+    "#).unwrap();
+    assert_eq!(machine_code.len(), 3);
+
+    // register addressing mode = 0b100, register indirect = 0b101, argument = register number
+    assert_eq!(machine_code[0], (0b00100u16 << 11) | (0b100u16 << 8) | 1);
+    assert_eq!(machine_code[1], (0b00011u16 << 11) | (0b100u16 << 8) | 2);
+    assert_eq!(machine_code[2], (0b00100u16 << 11) | (0b101u16 << 8) | 3);
+}
+
+#[test]
+fn test_register_increment_decrement_instructions() {
+    let machine_code = assemble_source(r#"
+        INR R1
+        DER R7
+    "#).unwrap();
+    assert_eq!(machine_code.len(), 2);
+
+    // register operations opcode = 0b10100, register mode, sub-operation in bits 7-3, register in bits 2-0
+    assert_eq!(machine_code[0], (0b10100u16 << 11) | (0b100u16 << 8) | 1);
+    assert_eq!(machine_code[1], (0b10100u16 << 11) | (0b100u16 << 8) | (0b00001 << 3) | 7);
+}
+
+#[test]
+fn test_subroutine_instructions() {
+    let machine_code = assemble_source(r#"
+        WYW routine
+        STP
+        routine:
+        POW
+    "#).unwrap();
+    assert_eq!(machine_code.len(), 3);
+
+    // WYW opcode = 0b10101, direct addressing, argument = routine (2)
+    assert_eq!(machine_code[0], (0b10101u16 << 11) | 2);
+    // POW opcode = 0b10110, no operand
+    assert_eq!(machine_code[2], 0b10110u16 << 11);
+}
+
+#[test]
+fn test_bitwise_instructions() {
+    let machine_code = assemble_source_with_profile(r#"
+        AND #0x0F
+        OR mask
+        XOR R3
+        STP
+        mask: RST 0x80
+    "#, "w+bits".parse().unwrap()).unwrap();
+    assert_eq!(machine_code.len(), 5);
+
+    // AND = 0b10111, OR = 0b11000, XOR = 0b11001
+    assert_eq!(machine_code[0], (0b10111u16 << 11) | (0b001u16 << 8) | 0x0F);
+    assert_eq!(machine_code[1], (0b11000u16 << 11) | 4);
+    assert_eq!(machine_code[2], (0b11001u16 << 11) | (0b100u16 << 8) | 3);
+}
+
+#[test]
+fn test_shift_instructions() {
+    let machine_code = assemble_source_with_profile(r#"
+        SHL #4
+        SAL #1
+        SHR #15
+        SAR #2
+        NOT
+    "#, "w+bits".parse().unwrap()).unwrap();
+    assert_eq!(machine_code.len(), 5);
+
+    // shift operations opcode = 0b11010, sub-operation in bits 7-4, shift count in bits 3-0
+    assert_eq!(machine_code[0], (0b11010u16 << 11) | (0b001u16 << 8) | 4);
+    assert_eq!(machine_code[1], (0b11010u16 << 11) | (0b001u16 << 8) | 1);
+    assert_eq!(machine_code[2], (0b11010u16 << 11) | (0b001u16 << 8) | (0b0001 << 4) | 15);
+    assert_eq!(machine_code[3], (0b11010u16 << 11) | (0b001u16 << 8) | (0b0010 << 4) | 2);
+    assert_eq!(machine_code[4], (0b11010u16 << 11) | (0b0011 << 4));
+}
+
+#[test]
+fn test_isa_directive_selects_profile() {
+    // directive enables muldiv and bits on top of the default profile
+    let machine_code = assemble_source(r#"
+        ISA full
+        POB #6
+        MNO #7
+        AND #0x0F
+        STP
+    "#).unwrap();
+    assert_eq!(machine_code.len(), 4);
+    assert_eq!(machine_code[1], (0b10001u16 << 11) | (0b001u16 << 8) | 7);
+    assert_eq!(machine_code[2], (0b10111u16 << 11) | (0b001u16 << 8) | 0x0F);
+}
@@ -0,0 +1,151 @@
+use hephasm::{
+    Assembler, AssemblerError, ExportedSymbol, LinkError, Linker, ObjectFile, Relocation, RelocationKind, RelocationTarget,
+};
+
+fn assemble_object(source: &str) -> Result<ObjectFile, AssemblerError> {
+    let program = parseid::parse_source(source).unwrap();
+    let mut assembler = Assembler::new();
+    assembler.assemble_object(&program)
+}
+
+fn link(modules: &[(&str, &str)]) -> Result<Vec<u16>, Vec<LinkError>> {
+    let mut linker = Linker::new();
+    for (name, source) in modules {
+        linker.add_module(*name, assemble_object(source).unwrap());
+    }
+    linker.link()
+}
+
+#[test]
+fn test_object_relocations_and_symbols() {
+    let object = assemble_object("IMPORTUJ ext\nEKSPORTUJ start, SIZE\nSTAŁA SIZE = 4\nstart: POB value\nDOD ext\nSTP\nvalue: RST start\n").unwrap();
+
+    // relocated instructions keep the far form even for small addresses
+    assert_eq!(object.code, [0xF800, 0x2005, 0xF800, 0x0800, 0x3800, 0x0000]);
+    assert_eq!(object.relocations, [
+        Relocation { offset: 0, kind: RelocationKind::Far, target: RelocationTarget::Module },
+        Relocation { offset: 2, kind: RelocationKind::Far, target: RelocationTarget::Symbol("ext".to_string()) },
+        Relocation { offset: 5, kind: RelocationKind::Word, target: RelocationTarget::Module },
+    ]);
+    assert_eq!(object.exports, [
+        ExportedSymbol { name: "start".to_string(), value: 0, relocatable: true },
+        ExportedSymbol { name: "SIZE".to_string(), value: 4, relocatable: false },
+    ]);
+    assert_eq!(object.imports, ["ext"]);
+}
+
+#[test]
+fn test_absolute_operands_are_not_relocated() {
+    let object = assemble_object("start: POB #5\nDOD end-start\nSOB +2\nend: STP\n").unwrap();
+    assert_eq!(object.code.len(), 4);
+    assert!(object.relocations.is_empty());
+}
+
+#[test]
+fn test_object_text_form_round_trip() {
+    let object = assemble_object("IMPORTUJ ext\nEKSPORTUJ start\nstart: POB ext+1\nSTP\nRST 1, 2, 3, 4, 5, 6, 7, start\n").unwrap();
+    let text = object.to_string();
+    assert!(text.starts_with("; asmodeus object 1\nimport ext\nexport start relocatable 0\ncode 0 F800 2001"));
+    assert!(text.contains("relocation 0 far ext\n"));
+    assert_eq!(text.parse::<ObjectFile>(), Ok(object));
+
+    assert!("RST 5".parse::<ObjectFile>().unwrap_err().starts_with("line 1"));
+    assert_eq!(
+        "; asmodeus object 1\ncode 0 0001\nrelocation 4 word -\n".parse::<ObjectFile>(),
+        Err("relocation at 4 is outside the code".to_string())
+    );
+}
+
+#[test]
+fn test_link_places_modules_in_order() {
+    let machine_code = link(&[
+        ("main.o", "IMPORTUJ show, value\nPOB value\nWYW show\nSTP\n"),
+        ("lib.o", "EKSPORTUJ show, value\nshow: WYJSCIE\nPOW\nvalue: RST 42\ntable: RST value\n"),
+    ]).unwrap();
+
+    // main takes 5 words, so show is at 5 and value at 7
+    assert_eq!(machine_code, [0xF800, 0x2007, 0xF800, 0xA805, 0x3800, 0x7800, 0xB000, 0x002A, 0x0007]);
+}
+
+#[test]
+fn test_link_relocates_indexed_operands() {
+    let machine_code = link(&[
+        ("main.o", "STP
+"),
+        ("lib.o", "POB tab[R1]
+DOD R2[tab+1]
+STP
+tab: RPA 2
+"),
+    ]).unwrap();
+
+    // lib starts at 1, tab at 6; the registers stay above the patched addresses
+    let first = (1u16 << 11) | 6;
+    let second = (2u16 << 11) | 7;
+    assert_eq!(machine_code[1..5], [0xF800 | (first >> 8), 0x2600 | (first & 0xFF), 0xF800 | (second >> 8), 0x0E00 | (second & 0xFF)]);
+}
+
+#[test]
+fn test_link_reports_undefined_and_duplicate_symbols() {
+    let errors = link(&[
+        ("a.o", "IMPORTUJ missing, other\nEKSPORTUJ shared\nshared: POB missing\nDOD other\n"),
+        ("b.o", "EKSPORTUJ shared\nshared: STP\n"),
+    ]).unwrap_err();
+
+    assert_eq!(errors, [
+        LinkError::DuplicateSymbol { symbol: "shared".to_string(), first: "a.o".to_string(), second: "b.o".to_string() },
+        LinkError::UndefinedSymbol { symbol: "missing".to_string(), module: "a.o".to_string() },
+        LinkError::UndefinedSymbol { symbol: "other".to_string(), module: "a.o".to_string() },
+    ]);
+}
+
+#[test]
+fn test_link_memory_overflow() {
+    let mut linker = Linker::new();
+    linker.add_module("a.o", assemble_object("RPA 1500\n").unwrap());
+    linker.add_module("b.o", assemble_object("RPA 1000\n").unwrap());
+    assert_eq!(linker.link(), Err(vec![LinkError::MemoryOverflow { size: 2500 }]));
+}
+
+#[test]
+fn test_invalid_relocations() {
+    assert!(matches!(
+        assemble_object("start: POB start*2\n"),
+        Err(AssemblerError::InvalidRelocation { line: 1, .. })
+    ));
+    assert!(matches!(
+        assemble_object("IMPORTUJ ext\nstart: RST ext+start\n"),
+        Err(AssemblerError::InvalidRelocation { line: 2, .. })
+    ));
+    assert!(matches!(
+        assemble_object("STAŁA TWICE = end*2\nend: STP\n"),
+        Err(AssemblerError::InvalidRelocation { line: 1, .. })
+    ));
+}
+
+#[test]
+fn test_shared_symbol_errors() {
+    assert!(matches!(
+        assemble_object("EKSPORTUJ nowhere\nSTP\n"),
+        Err(AssemblerError::UndefinedSymbol { line: 1, .. })
+    ));
+    assert!(matches!(
+        assemble_object("IMPORTUJ ext\nEKSPORTUJ ext\nSTP\n"),
+        Err(AssemblerError::InvalidDirectiveArguments { line: 2, .. })
+    ));
+
+    // imported symbols only exist in object files
+    let program = parseid::parse_source("IMPORTUJ ext\nPOB ext\n").unwrap();
+    assert!(matches!(
+        Assembler::new().assemble(&program),
+        Err(AssemblerError::InvalidDirectiveArguments { line: 1, .. })
+    ));
+}
+
+#[test]
+fn test_relative_operand_cannot_reach_another_module() {
+    assert!(matches!(
+        assemble_object("IMPORTUJ ext\nSOB +ext\n"),
+        Err(AssemblerError::InvalidRelocation { line: 2, .. })
+    ));
+}
@@ -0,0 +1,106 @@
+use hephasm::{Assembler, Lint, Warning};
+
+fn lint(source: &str) -> Vec<Warning> {
+    let program = parseid::parse_source(source).unwrap();
+    let mut assembler = Assembler::new();
+    assembler.assemble(&program).unwrap();
+    assembler.warnings().to_vec()
+}
+
+fn found(warnings: &[Warning]) -> Vec<(Lint, Option<usize>)> {
+    warnings.iter().map(|warning| (warning.lint, warning.line)).collect()
+}
+
+#[test]
+fn test_clean_program_has_no_warnings() {
+    let source = "start:  POB count\nloop:   ODE one\n        ŁAD count\n        SOZ end\n        SOB loop\nend:    STP\ncount:  RST 3\none:    RST 1\n";
+    assert!(lint(source).is_empty());
+}
+
+#[test]
+fn test_unreachable_code_after_stop_and_jump() {
+    let warnings = lint("POB #1\nSTP\nDOD #1\nSOB end\nODE #1\nend: STP\n");
+    assert_eq!(found(&warnings), [(Lint::UnreachableCode, Some(3)), (Lint::UnreachableCode, Some(5))]);
+    assert_eq!(warnings[0].to_string(), "Unreachable code after STP at line 3");
+    assert_eq!(warnings[0].subject.as_deref(), Some("DOD"));
+}
+
+#[test]
+fn test_jump_target_without_label_is_reachable() {
+    // SOZ +2 lands on DOD, right past the STP
+    assert!(lint("POB #1\nSOZ +2\nSTP\nDOD #1\nSOB -3\n").is_empty());
+    assert_eq!(found(&lint("SOB +2\nSTP\nDOD #1\nSOB -3\n")), [(Lint::UnreachableCode, Some(2))]);
+}
+
+#[test]
+fn test_unused_label() {
+    let warnings = lint("start: POB value\nunused: STP\nvalue: RST 1\n");
+    assert_eq!(found(&warnings), [(Lint::UnusedLabel, Some(2))]);
+    assert_eq!(warnings[0].to_string(), "Label unused is never used at line 2");
+    assert_eq!(warnings[0].subject.as_deref(), Some("unused"));
+}
+
+#[test]
+fn test_labels_in_macro_bodies_are_not_reported() {
+    assert!(lint("MAKRO add_one\nskip: DOD #1\nKONM\nPOB #0\nadd_one\nSTP\n").is_empty());
+}
+
+#[test]
+fn test_execution_falling_into_data() {
+    let warnings = lint("POB value\nvalue: RST 1\n");
+    assert!(found(&warnings).contains(&(Lint::FallIntoData, Some(1))));
+    assert!(warnings[0].to_string().starts_with("Execution falls from POB into data (RST)"));
+
+    // the stop ends execution before the data
+    assert!(lint("POB value\nSTP\nvalue: RST 1\n").is_empty());
+}
+
+#[test]
+fn test_missing_stop() {
+    let warnings = lint("loop: DOD #1\nSOB loop\n");
+    assert_eq!(found(&warnings), [(Lint::MissingStop, None)]);
+
+    // a module is called by the others, it does not have to stop
+    let program = parseid::parse_source("EKSPORTUJ add\nadd: DOD #1\nPOW\n").unwrap();
+    let mut assembler = Assembler::new();
+    assembler.assemble_object(&program).unwrap();
+    assert!(assembler.warnings().is_empty());
+}
+
+#[test]
+fn test_info_lints_are_opt_in() {
+    let source = "POB #300\nŁAD patch\npatch: DOD #1\nSTP\n";
+    let warnings = lint(source);
+    assert_eq!(found(&warnings), [(Lint::WideImmediate, Some(1))]);
+    assert_eq!(warnings[0].to_string(), "Immediate value 300 does not fit in 8 bits, POB takes two words at line 1");
+
+    let program = parseid::parse_source(source).unwrap();
+    let mut assembler = Assembler::new();
+    assembler.warn(Lint::SelfModifyingCode);
+    assembler.assemble(&program).unwrap();
+
+    let warnings = assembler.warnings();
+    assert_eq!(found(warnings), [(Lint::WideImmediate, Some(1)), (Lint::SelfModifyingCode, Some(2))]);
+    assert_eq!(warnings[1].to_string(), "Self-modifying code: ŁAD writes into the instruction at patch at line 2");
+    assert_eq!(Lint::ALL.iter().filter(|lint| lint.is_info()).collect::<Vec<_>>(), [&Lint::SelfModifyingCode]);
+    assert!(Lint::ALL.iter().all(|lint| lint.is_info() != lint.is_enabled_by_default()));
+}
+
+#[test]
+fn test_allowed_lint_is_not_reported() {
+    let program = parseid::parse_source("POB #1\nSTP\nDOD #1\n").unwrap();
+    let mut assembler = Assembler::new();
+    assembler.allow(Lint::UnreachableCode);
+    assembler.assemble(&program).unwrap();
+    assert!(assembler.warnings().is_empty());
+}
+
+#[test]
+fn test_lint_names_and_codes() {
+    for lint in Lint::ALL {
+        assert_eq!(Lint::from_name(lint.name()), Some(lint));
+        assert!(lint.code().starts_with("W03"));
+    }
+    assert_eq!(Lint::from_name("Unused-Label"), Some(Lint::UnusedLabel));
+    assert_eq!(Lint::from_name("unused"), None);
+}
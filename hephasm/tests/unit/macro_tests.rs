@@ -1,4 +1,4 @@
-use hephasm::assemble_source;
+use hephasm::{assemble_source, Assembler, AssemblerError};
 
 #[test]
 fn test_macro_definition_and_call() {
@@ -26,3 +26,210 @@ fn test_macro_definition_and_call() {
     let expected_stp = (0b00111u16 << 11) | (0b000u16 << 8) | 0;
     assert_eq!(machine_code[2], expected_stp);
 }
+
+#[test]
+fn test_macro_local_labels() {
+    let source = r#"
+        MAKRO count_down n
+            NAZWA_LOKALNA loop, done
+            POB #n
+        loop:
+            ODE #1
+            SOM done
+            SOB loop
+        done:
+        KONM
+
+        count_down 2
+        STP
+        count_down 3
+        STP
+    "#;
+    let machine_code = assemble_source(source).unwrap();
+
+    assert_eq!(machine_code.len(), 10);
+    // every expansion jumps within itself
+    assert_eq!(machine_code[2], (0b00110 << 11) | 4);
+    assert_eq!(machine_code[3], (0b00101 << 11) | 1);
+    assert_eq!(machine_code[7], (0b00110 << 11) | 9);
+    assert_eq!(machine_code[8], (0b00101 << 11) | 6);
+
+    // labels not declared local are shared by all expansions
+    let error = assemble_source(&source.replace("NAZWA_LOKALNA loop, done", "")).unwrap_err();
+    assert!(matches!(error.downcast_ref::<AssemblerError>(), Some(AssemblerError::DuplicateSymbol { .. })));
+}
+
+#[test]
+fn test_local_names_outside_macro() {
+    let error = assemble_source("NAZWA_LOKALNA loop\nloop: STP").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::InvalidDirectiveArguments { line: 1, .. })
+    ));
+}
+
+#[test]
+fn test_nested_macros_and_forward_calls() {
+    let machine_code = assemble_source(r#"
+        start:
+            add_twice value
+            STP
+
+        MAKRO add_twice x
+            add_once x
+            add_once x
+        KONM
+
+        MAKRO add_once y
+            DOD y
+        KONM
+
+        value: RST 3
+    "#).unwrap();
+
+    let dod_value = (0b00001 << 11) | 3;
+    assert_eq!(machine_code, vec![dod_value, dod_value, 0b00111 << 11, 3]);
+}
+
+#[test]
+fn test_macro_parameters_in_operands() {
+    let machine_code = assemble_source(r#"
+        MAKRO load p reg
+            POB #p
+            DOD [p]
+            ŁAD reg
+            POB [reg]
+        KONM
+
+        load 5 R2
+        STP
+    "#).unwrap();
+
+    assert_eq!(machine_code[0], (0b00100 << 11) | (0b001 << 8) | 5);
+    assert_eq!(machine_code[1], (0b00001 << 11) | (0b010 << 8) | 5);
+    let direct = assemble_source("ŁAD R2\nPOB [R2]").unwrap();
+    assert_eq!(&machine_code[2..4], &direct[..]);
+}
+
+#[test]
+fn test_recursive_macro() {
+    let error = assemble_source(r#"
+        MAKRO ping
+            pong
+        KONM
+        MAKRO pong
+            ping
+        KONM
+        ping
+    "#).unwrap_err();
+    assert_eq!(error.downcast_ref::<AssemblerError>(), Some(&AssemblerError::RecursiveMacro {
+        cycle: "ping -> pong -> ping".to_string(),
+        line: 6,
+    }));
+}
+
+#[test]
+fn test_macro_depth_limit() {
+    let source = r#"
+        MAKRO outer
+            inner
+        KONM
+        MAKRO inner
+            STP
+        KONM
+        outer
+    "#;
+    let program = parseid::parse_source(source).unwrap();
+    let mut assembler = Assembler::new();
+    assembler.set_max_macro_depth(1);
+    assert_eq!(assembler.assemble(&program), Err(AssemblerError::MacroDepthExceeded {
+        chain: "outer -> inner".to_string(),
+        limit: 1,
+        line: 3,
+    }));
+
+    assembler.set_max_macro_depth(2);
+    assert_eq!(assembler.assemble(&program), Ok(vec![0b00111 << 11]));
+}
+
+#[test]
+fn test_duplicate_macro() {
+    let error = assemble_source("MAKRO m\nSTP\nKONM\nMAKRO m\nSTP\nKONM").unwrap_err();
+    assert_eq!(error.downcast_ref::<AssemblerError>(), Some(&AssemblerError::DuplicateMacro {
+        name: "m".to_string(),
+        line: 4,
+    }));
+}
+
+#[test]
+fn test_macro_arguments_with_addressing_modes() {
+    let machine_code = assemble_source(r#"
+        MAKRO load_add value, extra
+            POB value
+            DOD extra
+        KONM
+
+        load_add #5, [10]
+        load_add ptr+1, R3
+        STP
+        ptr: RST 7
+    "#).unwrap();
+
+    let expected = assemble_source("POB #5\nDOD [10]\nPOB ptr+1\nDOD R3\nSTP\nptr: RST 7").unwrap();
+    assert_eq!(machine_code, expected);
+}
+
+#[test]
+fn test_macro_default_and_variadic_arguments() {
+    let machine_code = assemble_source(r#"
+        MAKRO add_to value, step = #1
+            POB value
+            DOD step
+        KONM
+
+        MAKRO table name, values...
+            name: RST values
+        KONM
+
+        MAKRO labelled_table values...
+            table data, values
+        KONM
+
+        add_to data
+        add_to data, #4
+        STP
+        labelled_table 10, 20, 30
+    "#).unwrap();
+
+    let expected = assemble_source("POB data\nDOD #1\nPOB data\nDOD #4\nSTP\ndata: RST 10, 20, 30").unwrap();
+    assert_eq!(machine_code, expected);
+
+    let error = assemble_source("MAKRO m a, b = 1\nKONM\nm").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::MacroParameterMismatch { expected: 1, found: 0, .. })
+    ));
+    let error = assemble_source("MAKRO m a, b = 1\nKONM\nm 1, 2, 3").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::MacroParameterMismatch { expected: 2, found: 3, .. })
+    ));
+}
+
+#[test]
+fn test_invalid_macro_arguments() {
+    // #param needs a value, not an operand with its own addressing mode
+    let error = assemble_source("MAKRO m p\nPOB #p\nKONM\nm [x]").unwrap_err();
+    assert_eq!(error.downcast_ref::<AssemblerError>(), Some(&AssemblerError::InvalidMacroArgument {
+        parameter: "p".to_string(),
+        argument: "[x]".to_string(),
+        line: 2,
+    }));
+
+    // a variadic parameter in an instruction has to stand for one argument
+    let error = assemble_source("MAKRO m values...\nPOB values\nKONM\nm 1, 2").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<AssemblerError>(),
+        Some(AssemblerError::InvalidMacroArgument { argument, .. }) if argument == "1, 2"
+    ));
+}
@@ -0,0 +1,99 @@
+use hephasm::{Assembler, AssemblerError, Segment};
+
+fn assemble(source: &str) -> Result<Vec<u16>, AssemblerError> {
+    let program = parseid::parse_source(source).unwrap();
+    let mut assembler = Assembler::new();
+    assembler.assemble(&program)
+}
+
+fn segment(section: &str, start: u16, end: u16, line: usize) -> Segment {
+    Segment { section: section.to_string(), start, end, line }
+}
+
+#[test]
+fn test_origin_leaves_a_gap() {
+    let program = parseid::parse_source("STP\nADRES 4\nnext: RST next\n").unwrap();
+    let mut assembler = Assembler::new();
+    let machine_code = assembler.assemble(&program).unwrap();
+
+    assert_eq!(machine_code, [0x3800, 0, 0, 0, 4]);
+    assert_eq!(assembler.segments(), [segment("kod", 0, 1, 0), segment("kod", 4, 5, 2)]);
+}
+
+#[test]
+fn test_default_section_is_placed_first() {
+    // kod is where the program starts, so dane follows it even though it comes first in the source
+    let machine_code = assemble("SEKCJA dane\nvalue: RST 5\nSEKCJA kod\nPOB value\nSTP\n").unwrap();
+    assert_eq!(machine_code, [0x2002, 0x3800, 0x0005]);
+
+    let machine_code = assemble("POB value\nSEKCJA dane\nvalue: RST 5\nSEKCJA kod\nSTP\n").unwrap();
+    assert_eq!(machine_code, [0x2002, 0x3800, 0x0005]);
+}
+
+#[test]
+fn test_section_at_fixed_address() {
+    let program = parseid::parse_source("SEKCJA wektor, 2000\nRST handler\nSEKCJA kod\nhandler: STP\n").unwrap();
+    let mut assembler = Assembler::new();
+    let machine_code = assembler.assemble(&program).unwrap();
+
+    assert_eq!(machine_code.len(), 2001);
+    assert_eq!(machine_code[0], 0x3800);
+    assert_eq!(machine_code[2000], 0x0000);
+    assert_eq!(assembler.segments(), [segment("wektor", 2000, 2001, 1), segment("kod", 0, 1, 3)]);
+
+    // far jump to the top of memory
+    let machine_code = assemble("SOB top\nSEKCJA wysoko, 2040\ntop: STP\n").unwrap();
+    assert_eq!(&machine_code[..2], [0xF807, 0x28F8]);
+    assert_eq!(machine_code[2040], 0x3800);
+
+    // directives without words after the last one of the program
+    let machine_code = assemble("RST 1\nSEKCJA dane, 10\nSTAŁA N = 4\nEKSPORTUJ a\na: RPA 0\n").unwrap();
+    assert_eq!(machine_code, [1]);
+}
+
+#[test]
+fn test_overlapping_sections() {
+    assert_eq!(
+        assemble("POB x\nSTP\nSEKCJA dane, 1\nx: RST 1\n"),
+        Err(AssemblerError::SectionOverlap {
+            first: "kod".to_string(),
+            second: "dane".to_string(),
+            address: 1,
+            line: 3,
+        })
+    );
+
+    // ADRES going back over its own section
+    let error = assemble("STP\nSTP\nSTP\nADRES 1\nRST 1\n").unwrap_err();
+    assert_eq!(error, AssemblerError::AddressOverlap { section: "kod".to_string(), start: 1, last: 1, line: 4 });
+    assert_eq!(error.to_string(), "ADRES places code over address 1 already used in section kod at line 4");
+    assert_eq!(
+        assemble("STP\nSTP\nSTP\nADRES 0\nRST 1, 2, 3, 4\n").unwrap_err().to_string(),
+        "ADRES places code over addresses 0-2 already used in section kod at line 4"
+    );
+}
+
+#[test]
+fn test_invalid_placement() {
+    assert!(matches!(
+        assemble("SEKCJA dane, 10\nRST 1\nSEKCJA dane, 20\n"),
+        Err(AssemblerError::InvalidDirectiveArguments { line: 3, .. })
+    ));
+    assert!(matches!(
+        assemble("ADRES 2048\nSTP\n"),
+        Err(AssemblerError::AddressOutOfBounds { address: 2048, line: 1, .. })
+    ));
+    assert!(matches!(
+        assemble("ADRES\nSTP\n"),
+        Err(AssemblerError::InvalidDirectiveArguments { line: 1, .. })
+    ));
+    // the line of the data that does not fit
+    assert!(matches!(assemble("ADRES 2047\nRST 1, 2\n"), Err(AssemblerError::MemoryOverflow { line: 2 })));
+
+    // object files are placed by the linker
+    let program = parseid::parse_source("SEKCJA dane, 100\nRST 1\n").unwrap();
+    assert!(matches!(
+        Assembler::new().assemble_object(&program),
+        Err(AssemblerError::InvalidDirectiveArguments { line: 1, .. })
+    ));
+}
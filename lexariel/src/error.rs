@@ -10,6 +10,48 @@ pub enum LexerError {
     InvalidNumberFormat { line: usize, column: usize, value: String },
     #[error("Unterminated string at line {line}, column {column}")]
     UnterminatedString { line: usize, column: usize },
+    #[error("Invalid character literal at line {line}, column {column}: {literal}")]
+    InvalidCharLiteral { line: usize, column: usize, literal: String },
+    #[error("Invalid escape sequence at line {line}, column {column}: '{sequence}'")]
+    InvalidEscapeSequence { line: usize, column: usize, sequence: String },
     #[error("Invalid character at line {line}, column {column}: '{character}'")]
     InvalidCharacter { line: usize, column: usize, character: char },
 }
+
+impl LexerError {
+    /// line the error refers to, for moving it into another file's numbering
+    pub fn line_mut(&mut self) -> &mut usize {
+        match self {
+            LexerError::UnknownToken { line, .. }
+            | LexerError::InvalidNumberFormat { line, .. }
+            | LexerError::UnterminatedString { line, .. }
+            | LexerError::InvalidCharLiteral { line, .. }
+            | LexerError::InvalidEscapeSequence { line, .. }
+            | LexerError::InvalidCharacter { line, .. } => line,
+        }
+    }
+
+    /// line and column of the offending character
+    pub fn position(&self) -> (usize, usize) {
+        match self {
+            LexerError::UnknownToken { line, column, .. }
+            | LexerError::InvalidNumberFormat { line, column, .. }
+            | LexerError::UnterminatedString { line, column }
+            | LexerError::InvalidCharLiteral { line, column, .. }
+            | LexerError::InvalidEscapeSequence { line, column, .. }
+            | LexerError::InvalidCharacter { line, column, .. } => (*line, *column),
+        }
+    }
+
+    /// stable code of the kind of error, E01xx for the lexer
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexerError::UnknownToken { .. } => "E0101",
+            LexerError::InvalidNumberFormat { .. } => "E0102",
+            LexerError::UnterminatedString { .. } => "E0103",
+            LexerError::InvalidCharLiteral { .. } => "E0104",
+            LexerError::InvalidEscapeSequence { .. } => "E0105",
+            LexerError::InvalidCharacter { .. } => "E0106",
+        }
+    }
+}
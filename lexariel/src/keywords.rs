@@ -1,17 +1,27 @@
 //! keyword and directive recognition
 
+/// instruction mnemonics, Polish letters and their ASCII spellings (ŁAD, LAD) and aliases (WPR, WEJSCIE) alike
+pub const KEYWORDS: &[&str] = &[
+    "DOD", "ODE", "ŁAD", "LAD", "POB", "SOB", "SOM", "SOZ", "STP",
+    "DNS", "PZS", "SDP", "CZM", "MSK", "PWR", "WPR", "WEJSCIE", "WYJSCIE", "WYJ",
+    "MNO", "DZI", "MOD", "INR", "DER", "WYW", "POW",
+    "AND", "OR", "XOR", "NOT", "SHL", "SAL", "SHR", "SAR",
+    "SOC", "SOV", "SOP", "SNZ",
+];
+
+/// directive names, with and without Polish letters
+pub const DIRECTIVES: &[&str] = &[
+    "RST", "RPA", "NAPIS", "NAPISZ", "NAPISD", "MAKRO", "KONM", "NAZWA_LOKALNA", "ISA",
+    "STAŁA", "STALA", "DOŁĄCZ", "DOLACZ", "JEŻELI", "JEZELI", "INACZEJ", "KONJ",
+    "EKSPORTUJ", "IMPORTUJ", "SEKCJA", "ADRES",
+];
+
 /// recognized keyword (instruction)
 pub(crate) fn is_keyword(word: &str) -> bool {
-    matches!(word.to_uppercase().as_str(),
-        "DOD" | "ODE" | "ŁAD" | "LAD" | "POB" | "SOB" | "SOM" | "SOZ" | "STP" | 
-        "DNS" | "PZS" | "SDP" | "CZM" | "MSK" | "PWR" | "WPR" | "WEJSCIE" | "WYJSCIE" | "WYJ" |
-        "MNO" | "DZI" | "MOD"
-    )
+    KEYWORDS.contains(&word.to_uppercase().as_str())
 }
 
 /// recognized directive
 pub(crate) fn is_directive(word: &str) -> bool {
-    matches!(word.to_uppercase().as_str(),
-        "RST" | "RPA" | "MAKRO" | "KONM" | "NAZWA_LOKALNA"
-    )
+    DIRECTIVES.contains(&word.to_uppercase().as_str())
 }
@@ -4,7 +4,7 @@ use crate::error::LexerError;
 use crate::token::{Token, TokenKind};
 use crate::keywords::{is_keyword, is_directive};
 use crate::position::InputReader;
-use crate::parsers::{read_identifier, read_number, skip_whitespace, skip_comment};
+use crate::parsers::{read_identifier, read_number, read_char_literal, read_string, skip_whitespace, skip_comment};
 
 pub struct Lexer {
     reader: InputReader,
@@ -92,7 +92,17 @@ impl Lexer {
                     return Ok(Token::new(TokenKind::Number, number, start_line, start_column));
                 }
                 
-                Some(ch) if "[]{}(),.#+-*/=<>!&|^~".contains(ch) => {
+                Some('\'') => {
+                    let code = read_char_literal(&mut self.reader)?;
+                    return Ok(Token::new(TokenKind::Number, code, start_line, start_column));
+                }
+                
+                Some('"') => {
+                    let text = read_string(&mut self.reader)?;
+                    return Ok(Token::new(TokenKind::String, text, start_line, start_column));
+                }
+                
+                Some(ch) if "[]{}(),.#+-*/%$=<>!&|^~".contains(ch) => {
                     self.reader.advance();
                     return Ok(Token::new(
                         TokenKind::Punctuation,
@@ -12,6 +12,7 @@ mod ascii_art;
 pub use error::LexerError;
 pub use token::{Token, TokenKind};
 pub use lexer::Lexer;
+pub use keywords::{KEYWORDS, DIRECTIVES};
 
 pub fn tokenize(input: &str) -> Result<Vec<Token>, LexerError> {
     let mut lexer = Lexer::new(input);
@@ -2,8 +2,10 @@ mod identifier;
 mod number;
 mod whitespace;
 mod comment;
+mod string;
 
 pub(crate) use identifier::read_identifier;
 pub(crate) use number::read_number;
 pub(crate) use whitespace::skip_whitespace;
 pub(crate) use comment::skip_comment;
+pub(crate) use string::{read_char_literal, read_string};
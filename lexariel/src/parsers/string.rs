@@ -0,0 +1,127 @@
+use crate::error::LexerError;
+use crate::position::InputReader;
+
+/// 'A' character literal, returns its code as a decimal number
+pub(crate) fn read_char_literal(reader: &mut InputReader) -> Result<String, LexerError> {
+    let start_line = reader.line();
+    let start_column = reader.column();
+    reader.advance(); // opening '
+
+    let ch = match reader.peek() {
+        None | Some('\n') => {
+            return Err(LexerError::UnterminatedString { line: start_line, column: start_column });
+        }
+        Some('\'') => {
+            return Err(LexerError::InvalidCharLiteral {
+                line: start_line,
+                column: start_column,
+                literal: "''".to_string(),
+            });
+        }
+        Some(_) => read_character(reader)?,
+    };
+
+    match reader.peek() {
+        Some('\'') => {
+            reader.advance(); // closing '
+        }
+        None | Some('\n') => {
+            return Err(LexerError::UnterminatedString { line: start_line, column: start_column });
+        }
+        Some(_) => {
+            // more than one character, report the whole literal
+            let mut literal = format!("'{}", ch);
+            while let Some(next) = reader.peek() {
+                if next == '\n' {
+                    break;
+                }
+                literal.push(next);
+                reader.advance();
+                if next == '\'' {
+                    break;
+                }
+            }
+            return Err(LexerError::InvalidCharLiteral { line: start_line, column: start_column, literal });
+        }
+    }
+
+    Ok(char_code(ch, reader)?.to_string())
+}
+
+/// "..." string literal, returns the text with escape sequences resolved
+pub(crate) fn read_string(reader: &mut InputReader) -> Result<String, LexerError> {
+    let start_line = reader.line();
+    let start_column = reader.column();
+    reader.advance(); // opening "
+
+    let mut text = String::new();
+    loop {
+        match reader.peek() {
+            None | Some('\n') => {
+                return Err(LexerError::UnterminatedString { line: start_line, column: start_column });
+            }
+            Some('"') => {
+                reader.advance(); // closing "
+                return Ok(text);
+            }
+            Some(_) => {
+                let ch = read_character(reader)?;
+                char_code(ch, reader)?;
+                text.push(ch);
+            }
+        }
+    }
+}
+
+/// single character or escape sequence: \n \t \r \0 \\ \' \" \xHH
+fn read_character(reader: &mut InputReader) -> Result<char, LexerError> {
+    let line = reader.line();
+    let column = reader.column();
+    let ch = reader.advance().unwrap();
+    if ch != '\\' {
+        return Ok(ch);
+    }
+
+    let escaped = match reader.advance() {
+        Some('n') => '\n',
+        Some('t') => '\t',
+        Some('r') => '\r',
+        Some('0') => '\0',
+        Some('\\') => '\\',
+        Some('\'') => '\'',
+        Some('"') => '"',
+        Some('x') => {
+            let mut digits = String::new();
+            while digits.len() < 2 {
+                match reader.peek() {
+                    Some(digit) if digit.is_ascii_hexdigit() => {
+                        digits.push(digit);
+                        reader.advance();
+                    }
+                    _ => break,
+                }
+            }
+            if digits.len() != 2 {
+                return Err(LexerError::InvalidEscapeSequence { line, column, sequence: format!("\\x{}", digits) });
+            }
+            u8::from_str_radix(&digits, 16).unwrap() as char
+        }
+        Some(other) => {
+            return Err(LexerError::InvalidEscapeSequence { line, column, sequence: format!("\\{}", other) });
+        }
+        None => {
+            return Err(LexerError::InvalidEscapeSequence { line, column, sequence: "\\".to_string() });
+        }
+    };
+
+    Ok(escaped)
+}
+
+/// characters have to fit in a 16-bit memory word
+fn char_code(ch: char, reader: &InputReader) -> Result<u16, LexerError> {
+    u16::try_from(ch as u32).map_err(|_| LexerError::InvalidCharacter {
+        line: reader.line(),
+        column: reader.column(),
+        character: ch,
+    })
+}
@@ -6,12 +6,14 @@ use std::fmt;
 pub enum TokenKind {
     /// assembly-like instructions (DOD, ODE, ŁAD, POB, SOB, SOM, STP, DNS, PZS, SDP, CZM, MSK, PWR, WEJSCIE, WYJSCIE)
     Keyword,
-    /// assembly-like directives (RST, RPA, MAKRO, KONM, NAZWA_LOKALNA)
+    /// assembly-like directives (RST, RPA, NAPIS, NAPISZ, NAPISD, MAKRO, KONM, NAZWA_LOKALNA, ISA, STAŁA, DOŁĄCZ, JEŻELI, INACZEJ, KONJ, EKSPORTUJ, IMPORTUJ, SEKCJA, ADRES)
     Directive,
     /// variable names, labels, macro names
     Identifier,
-    /// integer numbers (decimal, hexadecimal, binary)
+    /// integer numbers (decimal, hexadecimal, binary), 'A' char literals are turned into their code
     Number,
+    /// "..." string literals, value holds the text with escapes resolved
+    String,
     /// label definitions (identifier followed by colon)
     LabelDef,
     /// punctuation marks (,, [, ], #, etc.)
@@ -27,6 +29,7 @@ impl fmt::Display for TokenKind {
             TokenKind::Directive => write!(f, "Directive"),
             TokenKind::Identifier => write!(f, "Identifier"),
             TokenKind::Number => write!(f, "Number"),
+            TokenKind::String => write!(f, "String"),
             TokenKind::LabelDef => write!(f, "LabelDef"),
             TokenKind::Punctuation => write!(f, "Punctuation"),
             TokenKind::Eof => write!(f, "EOF"),
@@ -65,7 +65,7 @@ fn test_complex_assembly_program() {
     for token in &tokens {
         match token.kind {
             TokenKind::Keyword | TokenKind::Directive | TokenKind::Identifier | 
-            TokenKind::Number | TokenKind::LabelDef | TokenKind::Punctuation | TokenKind::Eof => {
+            TokenKind::Number | TokenKind::String | TokenKind::LabelDef | TokenKind::Punctuation | TokenKind::Eof => {
                 // all valid
             }
         }
@@ -7,4 +7,5 @@ mod unit {
     pub mod comment_tests;
     pub mod position_tests;
     pub mod error_tests;
+    pub mod string_tests;
 }
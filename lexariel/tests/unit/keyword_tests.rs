@@ -2,13 +2,15 @@ use lexariel::{tokenize, TokenKind};
 
 #[test]
 fn test_keywords() {
-    let input = "DOD ODE ŁAD LAD POB SOB SOM SOZ STP DNS PZS SDP CZM MSK PWR WEJSCIE WYJSCIE MNO DZI MOD";
+    let input = "DOD ODE ŁAD LAD POB SOB SOM SOZ STP DNS PZS SDP CZM MSK PWR WEJSCIE WYJSCIE MNO DZI MOD INR DER WYW POW AND OR XOR NOT SHL SAL SHR SAR SOC SOV SOP SNZ";
     let tokens = tokenize(input).unwrap();
     
     let expected_keywords = vec![
         "DOD", "ODE", "ŁAD", "LAD", "POB", "SOB", "SOM", "SOZ", "STP", 
         "DNS", "PZS", "SDP", "CZM", "MSK", "PWR", "WEJSCIE", "WYJSCIE",
-        "MNO", "DZI", "MOD"
+        "MNO", "DZI", "MOD", "INR", "DER", "WYW", "POW",
+        "AND", "OR", "XOR", "NOT", "SHL", "SAL", "SHR", "SAR",
+        "SOC", "SOV", "SOP", "SNZ"
     ];
     
     assert_eq!(tokens.len(), expected_keywords.len() + 1); // +1 for EOF
@@ -37,10 +39,10 @@ fn test_keywords_case_insensitive() {
 
 #[test]
 fn test_directives() {
-    let input = "RST RPA MAKRO KONM NAZWA_LOKALNA";
+    let input = "RST RPA MAKRO KONM NAZWA_LOKALNA ISA STAŁA STALA DOŁĄCZ DOLACZ JEŻELI JEZELI INACZEJ KONJ";
     let tokens = tokenize(input).unwrap();
     
-    let expected_directives = vec!["RST", "RPA", "MAKRO", "KONM", "NAZWA_LOKALNA"];
+    let expected_directives = vec!["RST", "RPA", "MAKRO", "KONM", "NAZWA_LOKALNA", "ISA", "STAŁA", "STALA", "DOŁĄCZ", "DOLACZ", "JEŻELI", "JEZELI", "INACZEJ", "KONJ"];
     
     assert_eq!(tokens.len(), expected_directives.len() + 1); // +1 for EOF
     
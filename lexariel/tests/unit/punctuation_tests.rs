@@ -2,9 +2,9 @@ use lexariel::{tokenize, TokenKind};
 
 #[test]
 fn test_punctuation() {
-    let tokens = tokenize("[]{}(),.#+*/=-<>!&|^~").unwrap();
+    let tokens = tokenize("[]{}(),.#+*/%$=-<>!&|^~").unwrap();
     
-    let expected_chars: Vec<char> = "[]{}(),.#+*/=-<>!&|^~".chars().collect();
+    let expected_chars: Vec<char> = "[]{}(),.#+*/%$=-<>!&|^~".chars().collect();
     
     assert_eq!(tokens.len(), expected_chars.len() + 1); // +1 for EOF
     
@@ -0,0 +1,84 @@
+use lexariel::{tokenize, TokenKind, LexerError};
+
+#[test]
+fn test_char_literals() {
+    let tokens = tokenize(r"'A' 'z' '0' ' '").unwrap();
+    
+    let expected_codes = ["65", "122", "48", "32"];
+    
+    assert_eq!(tokens.len(), expected_codes.len() + 1); // +1 for EOF
+    
+    for (i, expected) in expected_codes.iter().enumerate() {
+        assert_eq!(tokens[i].kind, TokenKind::Number);
+        assert_eq!(tokens[i].value, *expected);
+    }
+}
+
+#[test]
+fn test_char_literal_escapes() {
+    let tokens = tokenize(r"'\n' '\t' '\r' '\0' '\\' '\'' '\x41'").unwrap();
+    
+    let expected_codes = ["10", "9", "13", "0", "92", "39", "65"];
+    
+    for (i, expected) in expected_codes.iter().enumerate() {
+        assert_eq!(tokens[i].kind, TokenKind::Number);
+        assert_eq!(tokens[i].value, *expected);
+    }
+}
+
+#[test]
+fn test_char_literal_as_immediate() {
+    let tokens = tokenize("POB #'A'").unwrap();
+    assert_eq!(tokens.len(), 4);
+    assert_eq!(tokens[0].kind, TokenKind::Keyword);
+    assert_eq!(tokens[1].value, "#");
+    assert_eq!(tokens[2].kind, TokenKind::Number);
+    assert_eq!(tokens[2].value, "65");
+    assert_eq!(tokens[2].column, 6);
+}
+
+#[test]
+fn test_string_literals() {
+    let tokens = tokenize(r#"msg: NAPISZ "Hello; world\n""#).unwrap();
+    assert_eq!(tokens.len(), 4);
+    assert_eq!(tokens[0].kind, TokenKind::LabelDef);
+    assert_eq!(tokens[1].kind, TokenKind::Directive);
+    assert_eq!(tokens[2].kind, TokenKind::String);
+    assert_eq!(tokens[2].value, "Hello; world\n");
+    assert_eq!(tokens[3].kind, TokenKind::Eof);
+}
+
+#[test]
+fn test_string_escapes() {
+    let tokens = tokenize(r#""\"quoted\"\t\x21" """#).unwrap();
+    assert_eq!(tokens[0].value, "\"quoted\"\t!");
+    assert_eq!(tokens[1].kind, TokenKind::String);
+    assert_eq!(tokens[1].value, "");
+}
+
+#[test]
+fn test_unterminated_string() {
+    let result = tokenize("NAPIS \"abc\nSTP");
+    assert_eq!(result, Err(LexerError::UnterminatedString { line: 1, column: 7 }));
+    
+    let result = tokenize("POB #'A");
+    assert_eq!(result, Err(LexerError::UnterminatedString { line: 1, column: 6 }));
+}
+
+#[test]
+fn test_invalid_char_literals() {
+    let result = tokenize("POB #''");
+    assert!(matches!(result, Err(LexerError::InvalidCharLiteral { ref literal, .. }) if literal == "''"));
+    
+    let result = tokenize("POB #'AB'");
+    assert!(matches!(result, Err(LexerError::InvalidCharLiteral { ref literal, .. }) if literal == "'AB'"));
+}
+
+#[test]
+fn test_invalid_escape_sequence() {
+    let result = tokenize(r#"NAPIS "a\qb""#);
+    assert_eq!(result, Err(LexerError::InvalidEscapeSequence { line: 1, column: 9, sequence: "\\q".to_string() }));
+    
+    let result = tokenize(r"'\x4'");
+    assert!(matches!(result, Err(LexerError::InvalidEscapeSequence { ref sequence, .. }) if sequence == "\\x4"));
+}
@@ -0,0 +1,11 @@
+use super::{Expression, ProgramElement};
+
+/// JEŻELI condition ... INACZEJ ... KONJ, the assembler keeps one branch
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalBlock {
+    pub condition: Expression,
+    pub then_branch: Vec<ProgramElement>,
+    pub else_branch: Vec<ProgramElement>,
+    pub line: usize,
+    pub column: usize,
+}
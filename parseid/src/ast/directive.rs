@@ -1,7 +1,11 @@
+use super::Expression;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Directive {
     pub name: String,
     pub arguments: Vec<String>,
+    /// expression of each argument that is more than a single number or symbol, parallel to arguments
+    pub expressions: Vec<Option<Expression>>,
     pub line: usize,
     pub column: usize,
 }
@@ -0,0 +1,142 @@
+use std::fmt;
+
+/// constant expression in an operand or directive argument, e.g. `tab+3`, `(end-start)/2`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    /// number as written in source (decimal, hexadecimal, binary)
+    Number(String),
+    /// label or other symbol
+    Symbol(String),
+    /// $ - address of the current instruction or directive
+    CurrentAddress,
+    /// -value
+    Negate(Box<Expression>),
+    Binary {
+        operator: BinaryOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    ShiftLeft,
+    ShiftRight,
+    And,
+    Or,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+impl BinaryOperator {
+    /// binding strength, higher binds tighter
+    pub fn precedence(self) -> u8 {
+        match self {
+            BinaryOperator::Or => 1,
+            BinaryOperator::And => 2,
+            BinaryOperator::Equal | BinaryOperator::NotEqual => 3,
+            BinaryOperator::Less | BinaryOperator::LessEqual | BinaryOperator::Greater | BinaryOperator::GreaterEqual => 4,
+            BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => 5,
+            BinaryOperator::Add | BinaryOperator::Subtract => 6,
+            BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => 7,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Modulo => "%",
+            BinaryOperator::ShiftLeft => "<<",
+            BinaryOperator::ShiftRight => ">>",
+            BinaryOperator::And => "&",
+            BinaryOperator::Or => "|",
+            BinaryOperator::Equal => "==",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::Less => "<",
+            BinaryOperator::LessEqual => "<=",
+            BinaryOperator::Greater => ">",
+            BinaryOperator::GreaterEqual => ">=",
+        }
+    }
+}
+
+impl Expression {
+    /// single number or symbol, needs no evaluation
+    pub fn is_simple(&self) -> bool {
+        matches!(self, Expression::Number(_) | Expression::Symbol(_))
+    }
+
+    /// names of all symbols the expression refers to, in source order
+    pub fn symbols(&self) -> Vec<&str> {
+        match self {
+            Expression::Symbol(name) => vec![name.as_str()],
+            Expression::Number(_) | Expression::CurrentAddress => Vec::new(),
+            Expression::Negate(inner) => inner.symbols(),
+            Expression::Binary { left, right, .. } => {
+                let mut symbols = left.symbols();
+                symbols.extend(right.symbols());
+                symbols
+            }
+        }
+    }
+
+    /// value depends on where the expression is placed
+    pub fn uses_current_address(&self) -> bool {
+        match self {
+            Expression::CurrentAddress => true,
+            Expression::Number(_) | Expression::Symbol(_) => false,
+            Expression::Negate(inner) => inner.uses_current_address(),
+            Expression::Binary { left, right, .. } => left.uses_current_address() || right.uses_current_address(),
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expression::Binary { operator, .. } => operator.precedence(),
+            _ => u8::MAX,
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    /// source form, parenthesized only where precedence requires it
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Number(value) | Expression::Symbol(value) => write!(f, "{}", value),
+            Expression::CurrentAddress => write!(f, "$"),
+            Expression::Negate(inner) => {
+                if inner.is_simple() || **inner == Expression::CurrentAddress {
+                    write!(f, "-{}", inner)
+                } else {
+                    write!(f, "-({})", inner)
+                }
+            }
+            Expression::Binary { operator, left, right } => {
+                if left.precedence() < operator.precedence() {
+                    write!(f, "({})", left)?;
+                } else {
+                    write!(f, "{}", left)?;
+                }
+                write!(f, "{}", operator.symbol())?;
+                // operators are left-associative
+                if right.precedence() <= operator.precedence() {
+                    write!(f, "({})", right)
+                } else {
+                    write!(f, "{}", right)
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,13 @@
-use super::ProgramElement;
+use super::{Operand, ProgramElement};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct MacroDefinition {
     pub name: String,
     pub parameters: Vec<String>,
+    /// `param = value` default of each parameter, parallel to parameters
+    pub defaults: Vec<Option<Operand>>,
+    /// last parameter (`rest...`) takes all remaining arguments
+    pub variadic: bool,
     pub body: Vec<ProgramElement>,
     pub line: usize,
     pub column: usize,
@@ -12,7 +16,10 @@ pub struct MacroDefinition {
 #[derive(Debug, Clone, PartialEq)]
 pub struct MacroCall {
     pub name: String,
+    /// arguments as written, e.g. `#5`, `[ptr]`, `tab+1`
     pub arguments: Vec<String>,
+    /// parsed arguments, parallel to arguments
+    pub operands: Vec<Operand>,
     pub line: usize,
     pub column: usize,
 }
@@ -7,6 +7,8 @@ mod addressing_mode;
 mod directive;
 mod label;
 mod macro_def;
+mod expression;
+mod conditional;
 
 pub use program::{Program, ProgramElement};
 pub use instruction::Instruction;
@@ -15,3 +17,5 @@ pub use addressing_mode::AddressingMode;
 pub use directive::Directive;
 pub use label::LabelDefinition;
 pub use macro_def::{MacroDefinition, MacroCall};
+pub use expression::{Expression, BinaryOperator};
+pub use conditional::ConditionalBlock;
@@ -1,7 +1,29 @@
-use super::AddressingMode;
+use std::fmt;
+use super::{AddressingMode, Expression};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Operand {
     pub addressing_mode: AddressingMode,
     pub value: String,
+    /// set when value is more than a single number or symbol (`tab+3`, `$`),
+    /// for `R1[tab+3]` it is the offset
+    pub expression: Option<Expression>,
+    /// of its first character (`#`, `[`, `+`), 1-based, for pointing at it in errors
+    pub column: usize,
+}
+
+impl fmt::Display for Operand {
+    /// source form, e.g. `#5`, `[ptr]`, `R1[2]`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.addressing_mode {
+            AddressingMode::Immediate => write!(f, "#{}", self.value),
+            AddressingMode::Indirect | AddressingMode::RegisterIndirect => write!(f, "[{}]", self.value),
+            AddressingMode::MultipleIndirect => write!(f, "[[{}]]", self.value),
+            AddressingMode::Direct
+            | AddressingMode::Register
+            | AddressingMode::BaseRegister { .. }
+            | AddressingMode::Relative
+            | AddressingMode::Indexed { .. } => write!(f, "{}", self.value),
+        }
+    }
 }
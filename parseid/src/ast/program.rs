@@ -1,4 +1,4 @@
-use super::{Instruction, LabelDefinition, Directive, MacroDefinition, MacroCall};
+use super::{Instruction, LabelDefinition, Directive, MacroDefinition, MacroCall, ConditionalBlock};
 
 /// main program node containing all top-level elements
 #[derive(Debug, Clone, PartialEq)]
@@ -14,6 +14,7 @@ pub enum ProgramElement {
     Directive(Directive),
     MacroDefinition(MacroDefinition),
     MacroCall(MacroCall),
+    Conditional(ConditionalBlock),
 }
 
 impl Program {
@@ -0,0 +1,89 @@
+//! conditional block parsing
+
+use lexariel::TokenKind;
+use crate::ast::{ConditionalBlock, ProgramElement};
+use crate::error::ParserError;
+use crate::expression_parser::ExpressionParser;
+use crate::token_navigator::TokenNavigator;
+use crate::parser::Parser;
+
+pub(crate) struct ConditionalParser;
+
+impl ConditionalParser {
+    pub fn parse_conditional(navigator: &mut TokenNavigator) -> Result<ConditionalBlock, ParserError> {
+        let token = navigator.consume(TokenKind::Directive, "JEŻELI")?;
+        let line = token.line;
+        let column = token.column;
+
+        // condition on the JEŻELI line
+        let condition = match navigator.peek() {
+            Some(next) if next.line == line && ExpressionParser::starts_expression(next) => {
+                ExpressionParser::parse(navigator, "condition")?
+            }
+            Some(next) if next.kind != TokenKind::Eof => {
+                return Err(ParserError::UnexpectedToken {
+                    line: next.line,
+                    column: next.column,
+                    expected: "condition".to_string(),
+                    found: format!("{}", next.kind),
+                });
+            }
+            _ => return Err(ParserError::UnexpectedEof { expected: "condition".to_string() }),
+        };
+
+        let then_branch = Self::parse_branch(navigator, line, column)?;
+        let else_branch = if Self::consume_if(navigator, "INACZEJ") {
+            Self::parse_branch(navigator, line, column)?
+        } else {
+            Vec::new()
+        };
+
+        if !Self::consume_if(navigator, "KONJ") {
+            return Err(ParserError::UnterminatedConditional { line, column });
+        }
+
+        Ok(ConditionalBlock {
+            condition,
+            then_branch,
+            else_branch,
+            line,
+            column,
+        })
+    }
+
+    /// elements up to INACZEJ or KONJ, which are left for the caller
+    fn parse_branch(navigator: &mut TokenNavigator, line: usize, column: usize) -> Result<Vec<ProgramElement>, ParserError> {
+        let mut elements = Vec::new();
+
+        while let Some(token) = navigator.peek() {
+            if token.kind == TokenKind::Eof {
+                return Err(ParserError::UnterminatedConditional { line, column });
+            }
+            if token.kind == TokenKind::Directive && is_branch_end(&token.value) {
+                break;
+            }
+            elements.push(Parser::parse_program_element_with_navigator(navigator)?);
+        }
+
+        Ok(elements)
+    }
+
+    fn consume_if(navigator: &mut TokenNavigator, directive: &str) -> bool {
+        let found = navigator.peek()
+            .is_some_and(|token| token.kind == TokenKind::Directive && token.value.to_uppercase() == directive);
+        if found {
+            navigator.advance();
+        }
+        found
+    }
+}
+
+/// JEŻELI starting a conditional block
+pub(crate) fn is_conditional_start(name: &str) -> bool {
+    matches!(name.to_uppercase().as_str(), "JEŻELI" | "JEZELI")
+}
+
+/// INACZEJ or KONJ, only valid inside a conditional block
+pub(crate) fn is_branch_end(name: &str) -> bool {
+    matches!(name.to_uppercase().as_str(), "INACZEJ" | "KONJ")
+}
@@ -1,8 +1,9 @@
 //! directive parser
 
-use lexariel::TokenKind;
+use lexariel::{Token, TokenKind};
 use crate::ast::Directive;
 use crate::error::ParserError;
+use crate::expression_parser::ExpressionParser;
 use crate::token_navigator::TokenNavigator;
 
 /// Parses directives
@@ -16,17 +17,93 @@ impl DirectiveParser {
         let column = token.column;
 
         let mut arguments = Vec::new();
+        let mut expressions = Vec::new();
 
-        while let Some(arg_token) = navigator.peek() {
-            match arg_token.kind {
-                TokenKind::Number | TokenKind::Identifier => {
+        // profile names like classic-w+muldiv span several tokens on the directive line
+        if name.eq_ignore_ascii_case("ISA") {
+            let mut profile = String::new();
+            while let Some(token) = navigator.peek() {
+                let is_part = token.line == line && match token.kind {
+                    TokenKind::Identifier | TokenKind::Number => true,
+                    TokenKind::Punctuation => token.value == "+" || token.value == "-",
+                    _ => false,
+                };
+                if !is_part {
+                    break;
+                }
+                profile.push_str(&navigator.advance().unwrap().value);
+            }
+            arguments.push(profile);
+            expressions.push(None);
+        }
+
+        // NAPIS, NAPISZ, NAPISD and DOŁĄCZ take a single "..." string
+        if is_string_directive(&name) {
+            match navigator.peek() {
+                Some(text) if text.kind == TokenKind::String && text.line == line => {
                     arguments.push(navigator.advance().unwrap().value.clone());
+                    expressions.push(None);
                 }
-                TokenKind::Eof | TokenKind::LabelDef | TokenKind::Keyword | TokenKind::Directive => {
-                    break;
+                Some(other) => {
+                    return Err(ParserError::UnexpectedToken {
+                        line: other.line,
+                        column: other.column,
+                        expected: "string literal".to_string(),
+                        found: format!("{}", other.kind),
+                    });
+                }
+                None => {
+                    return Err(ParserError::UnexpectedEof { expected: "string literal".to_string() });
+                }
+            }
+        }
+
+        // STAŁA SIZE = 16 - name, optional '=' and the value
+        if is_constant_directive(&name) {
+            let symbol = match navigator.peek() {
+                Some(symbol) if symbol.kind == TokenKind::Identifier && symbol.line == line => navigator.advance().unwrap().value.clone(),
+                Some(other) => {
+                    return Err(ParserError::UnexpectedToken {
+                        line: other.line,
+                        column: other.column,
+                        expected: "constant name".to_string(),
+                        found: format!("{}", other.kind),
+                    });
+                }
+                None => {
+                    return Err(ParserError::UnexpectedEof { expected: "constant name".to_string() });
+                }
+            };
+            arguments.push(symbol);
+            expressions.push(None);
+
+            if navigator.peek().is_some_and(|token| token.line == line && token.kind == TokenKind::Punctuation && token.value == "=") {
+                navigator.advance();
+            }
+        }
+
+        // RST 1, end-start, tab+2 - a comma between every two, arguments end with the directive line
+        let on_line = |token: &Token| token.line == line && token.kind != TokenKind::Eof;
+        let starts_list = navigator.peek().filter(|token| on_line(token))
+            .is_some_and(|token| !matches!(token.kind, TokenKind::LabelDef | TokenKind::Keyword | TokenKind::Directive));
+        if starts_list {
+            loop {
+                match navigator.peek().filter(|token| on_line(token)) {
+                    Some(token) if ExpressionParser::starts_expression(token) => {
+                        let expression = ExpressionParser::parse(navigator, "directive argument")?;
+                        arguments.push(expression.to_string());
+                        expressions.push(Some(expression).filter(|expression| !expression.is_simple()));
+                    }
+                    // RST 1,,2 and RST 3,
+                    found => return Err(Self::unexpected(found, "directive argument", line, navigator.line_end(line))),
                 }
-                _ => {
-                    navigator.advance(); // skip other tokens (...punctuation)
+                match navigator.peek().filter(|token| on_line(token)) {
+                    None => break,
+                    Some(token) if token.kind == TokenKind::Punctuation && token.value == "," => {
+                        navigator.advance();
+                    }
+                    // RST 7 8
+                    found => return Err(Self::unexpected(found, "comma", line, navigator.line_end(line))),
                 }
             }
         }
@@ -34,8 +111,61 @@ impl DirectiveParser {
         Ok(Directive {
             name,
             arguments,
+            expressions,
             line,
             column,
         })
     }
+
+    /// token in the argument list where something else was expected, None at the end of the line
+    fn unexpected(found: Option<&Token>, expected: &str, line: usize, line_end: usize) -> ParserError {
+        match found {
+            Some(token) => ParserError::UnexpectedToken {
+                line: token.line,
+                column: token.column,
+                expected: expected.to_string(),
+                found: format!("{}", token.kind),
+            },
+            None => ParserError::UnexpectedToken {
+                line,
+                column: line_end,
+                expected: expected.to_string(),
+                found: "end of line".to_string(),
+            },
+        }
+    }
+}
+
+/// directives whose argument is a string literal
+pub fn is_string_directive(name: &str) -> bool {
+    matches!(name.to_uppercase().as_str(), "NAPIS" | "NAPISZ" | "NAPISD") || is_include_directive(name)
+}
+
+/// directive splicing another source file in place
+pub fn is_include_directive(name: &str) -> bool {
+    matches!(name.to_uppercase().as_str(), "DOŁĄCZ" | "DOLACZ")
+}
+
+/// directives binding a name to a constant value
+pub fn is_constant_directive(name: &str) -> bool {
+    matches!(name.to_uppercase().as_str(), "STAŁA" | "STALA")
+}
+
+/// directives naming symbols shared with other modules of a linked program
+pub fn is_export_directive(name: &str) -> bool {
+    name.eq_ignore_ascii_case("EKSPORTUJ")
+}
+
+pub fn is_import_directive(name: &str) -> bool {
+    name.eq_ignore_ascii_case("IMPORTUJ")
+}
+
+/// SEKCJA name[, address] - switches to a named section of the program
+pub fn is_section_directive(name: &str) -> bool {
+    name.eq_ignore_ascii_case("SEKCJA")
+}
+
+/// ADRES address - places what follows at an address
+pub fn is_origin_directive(name: &str) -> bool {
+    name.eq_ignore_ascii_case("ADRES")
 }
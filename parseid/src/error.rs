@@ -32,6 +32,92 @@ pub enum ParserError {
         column: usize,
         message: String,
     },
+    #[error("Conditional block at line {line}, column {column} is missing KONJ")]
+    UnterminatedConditional { line: usize, column: usize },
+    #[error("{directive} without a matching JEŻELI at line {line}, column {column}")]
+    UnmatchedConditional { directive: String, line: usize, column: usize },
+    #[error("Included file not found: {path} at line {line}")]
+    IncludeNotFound { path: String, line: usize },
+    #[error("Failed to read included file {path} at line {line}: {reason}")]
+    IncludeFailed { path: String, reason: String, line: usize },
+    #[error("Circular include: {cycle} at line {line}")]
+    CircularInclude { cycle: String, line: usize },
+    #[error("{error} in {file}")]
+    InFile { file: String, error: Box<ParserError> },
     #[error("Lexer error: {0}")]
     LexerError(#[from] LexerError),
 }
+
+impl ParserError {
+    /// line the error refers to, for moving it into another file's numbering
+    pub fn line_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            ParserError::UnexpectedToken { line, .. }
+            | ParserError::InvalidAddressingMode { line, .. }
+            | ParserError::MissingOperand { line, .. }
+            | ParserError::InvalidMacroDefinition { line, .. }
+            | ParserError::UnterminatedConditional { line, .. }
+            | ParserError::UnmatchedConditional { line, .. }
+            | ParserError::IncludeNotFound { line, .. }
+            | ParserError::IncludeFailed { line, .. }
+            | ParserError::CircularInclude { line, .. } => Some(line),
+            ParserError::InFile { error, .. } => error.line_mut(),
+            ParserError::LexerError(e) => Some(e.line_mut()),
+            ParserError::UnexpectedEof { .. } => None,
+        }
+    }
+
+    /// line of the error, in the numbering of the file named by InFile
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ParserError::UnexpectedToken { line, .. }
+            | ParserError::InvalidAddressingMode { line, .. }
+            | ParserError::MissingOperand { line, .. }
+            | ParserError::InvalidMacroDefinition { line, .. }
+            | ParserError::UnterminatedConditional { line, .. }
+            | ParserError::UnmatchedConditional { line, .. }
+            | ParserError::IncludeNotFound { line, .. }
+            | ParserError::IncludeFailed { line, .. }
+            | ParserError::CircularInclude { line, .. } => Some(*line),
+            ParserError::InFile { error, .. } => error.line(),
+            ParserError::LexerError(e) => Some(e.position().0),
+            ParserError::UnexpectedEof { .. } => None,
+        }
+    }
+
+    /// column of the token at fault, None for errors about a whole line
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            ParserError::UnexpectedToken { column, .. }
+            | ParserError::InvalidAddressingMode { column, .. }
+            | ParserError::MissingOperand { column, .. }
+            | ParserError::InvalidMacroDefinition { column, .. }
+            | ParserError::UnterminatedConditional { column, .. }
+            | ParserError::UnmatchedConditional { column, .. } => Some(*column),
+            ParserError::InFile { error, .. } => error.column(),
+            ParserError::LexerError(e) => Some(e.position().1),
+            ParserError::IncludeNotFound { .. }
+            | ParserError::IncludeFailed { .. }
+            | ParserError::CircularInclude { .. }
+            | ParserError::UnexpectedEof { .. } => None,
+        }
+    }
+
+    /// stable code of the kind of error, E02xx, the lexer's code for errors found tokenizing
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParserError::UnexpectedToken { .. } => "E0201",
+            ParserError::UnexpectedEof { .. } => "E0202",
+            ParserError::InvalidAddressingMode { .. } => "E0203",
+            ParserError::MissingOperand { .. } => "E0204",
+            ParserError::InvalidMacroDefinition { .. } => "E0205",
+            ParserError::UnterminatedConditional { .. } => "E0206",
+            ParserError::UnmatchedConditional { .. } => "E0207",
+            ParserError::IncludeNotFound { .. } => "E0208",
+            ParserError::IncludeFailed { .. } => "E0209",
+            ParserError::CircularInclude { .. } => "E0210",
+            ParserError::InFile { error, .. } => error.code(),
+            ParserError::LexerError(e) => e.code(),
+        }
+    }
+}
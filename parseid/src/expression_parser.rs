@@ -0,0 +1,167 @@
+//! constant expressions (+ - * / % << >> & | == != < <= > >=, parentheses, $)
+
+use lexariel::{Token, TokenKind};
+use crate::ast::{Expression, BinaryOperator};
+use crate::error::ParserError;
+use crate::token_navigator::TokenNavigator;
+
+/// Parses expressions, all tokens have to be on the line the expression starts on
+pub(crate) struct ExpressionParser;
+
+impl ExpressionParser {
+    pub fn parse(navigator: &mut TokenNavigator, expected: &str) -> Result<Expression, ParserError> {
+        let line = match navigator.peek() {
+            Some(token) => token.line,
+            None => return Err(ParserError::UnexpectedEof { expected: expected.to_string() }),
+        };
+        Self::parse_binary(navigator, line, 1, expected)
+    }
+
+    /// if the next token can start an expression
+    pub fn starts_expression(token: &Token) -> bool {
+        match token.kind {
+            TokenKind::Number | TokenKind::Identifier => true,
+            TokenKind::Punctuation => matches!(token.value.as_str(), "(" | "$" | "-"),
+            _ => false,
+        }
+    }
+
+    fn parse_binary(navigator: &mut TokenNavigator, line: usize, min_precedence: u8, expected: &str) -> Result<Expression, ParserError> {
+        let left = Self::parse_unary(navigator, line, expected)?;
+        Self::parse_binary_rest(navigator, line, left, min_precedence, expected)
+    }
+
+    /// precedence climbing, all operators are left-associative
+    fn parse_binary_rest(
+        navigator: &mut TokenNavigator,
+        line: usize,
+        mut left: Expression,
+        min_precedence: u8,
+        expected: &str
+    ) -> Result<Expression, ParserError> {
+        while let Some((operator, token_count)) = Self::peek_operator(navigator, line) {
+            if operator.precedence() < min_precedence {
+                break;
+            }
+
+            let right = if token_count == 0 {
+                // tab-3 is lexed as tab, -3
+                let number = navigator.advance().unwrap().value[1..].to_string();
+                Self::parse_binary_rest(navigator, line, Expression::Number(number), operator.precedence() + 1, expected)?
+            } else {
+                for _ in 0..token_count {
+                    navigator.advance();
+                }
+                Self::parse_binary(navigator, line, operator.precedence() + 1, expected)?
+            };
+
+            left = Expression::Binary {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+
+        Ok(left)
+    }
+
+    /// operator at the current position and how many tokens it spans
+    fn peek_operator(navigator: &TokenNavigator, line: usize) -> Option<(BinaryOperator, usize)> {
+        let token = navigator.peek().filter(|token| token.line == line)?;
+
+        if token.kind == TokenKind::Number && token.value.starts_with('-') {
+            return Some((BinaryOperator::Subtract, 0));
+        }
+        if token.kind != TokenKind::Punctuation {
+            return None;
+        }
+
+        let next_is = |value: &str| {
+            navigator.peek_ahead(1).is_some_and(|next| next.kind == TokenKind::Punctuation && next.value == value)
+        };
+
+        match token.value.as_str() {
+            "+" => Some((BinaryOperator::Add, 1)),
+            "-" => Some((BinaryOperator::Subtract, 1)),
+            "*" => Some((BinaryOperator::Multiply, 1)),
+            "/" => Some((BinaryOperator::Divide, 1)),
+            "%" => Some((BinaryOperator::Modulo, 1)),
+            "&" => Some((BinaryOperator::And, 1)),
+            "|" => Some((BinaryOperator::Or, 1)),
+            "<" if next_is("<") => Some((BinaryOperator::ShiftLeft, 2)),
+            ">" if next_is(">") => Some((BinaryOperator::ShiftRight, 2)),
+            "<" if next_is("=") => Some((BinaryOperator::LessEqual, 2)),
+            ">" if next_is("=") => Some((BinaryOperator::GreaterEqual, 2)),
+            "<" => Some((BinaryOperator::Less, 1)),
+            ">" => Some((BinaryOperator::Greater, 1)),
+            "=" if next_is("=") => Some((BinaryOperator::Equal, 2)),
+            "!" if next_is("=") => Some((BinaryOperator::NotEqual, 2)),
+            _ => None,
+        }
+    }
+
+    fn parse_unary(navigator: &mut TokenNavigator, line: usize, expected: &str) -> Result<Expression, ParserError> {
+        if let Some(token) = navigator.peek() {
+            if token.line == line && token.kind == TokenKind::Punctuation && token.value == "-" {
+                navigator.advance();
+                let operand = Self::parse_unary(navigator, line, expected)?;
+                return Ok(Expression::Negate(Box::new(operand)));
+            }
+        }
+        Self::parse_primary(navigator, line, expected)
+    }
+
+    fn parse_primary(navigator: &mut TokenNavigator, line: usize, expected: &str) -> Result<Expression, ParserError> {
+        let token = match navigator.peek() {
+            Some(token) if token.kind != TokenKind::Eof => token.clone(),
+            _ => return Err(ParserError::UnexpectedEof { expected: expected.to_string() }),
+        };
+
+        if token.line == line {
+            match token.kind {
+                TokenKind::Number => {
+                    navigator.advance();
+                    return Ok(Expression::Number(token.value));
+                }
+                TokenKind::Identifier => {
+                    navigator.advance();
+                    return Ok(Expression::Symbol(token.value));
+                }
+                TokenKind::Punctuation if token.value == "$" => {
+                    navigator.advance();
+                    return Ok(Expression::CurrentAddress);
+                }
+                TokenKind::Punctuation if token.value == "(" => {
+                    navigator.advance();
+                    let inner = Self::parse_binary(navigator, line, 1, expected)?;
+                    Self::consume_closing_paren(navigator, line)?;
+                    return Ok(inner);
+                }
+                _ => {}
+            }
+        }
+
+        Err(ParserError::UnexpectedToken {
+            line: token.line,
+            column: token.column,
+            expected: expected.to_string(),
+            found: format!("{}", token.kind),
+        })
+    }
+
+    fn consume_closing_paren(navigator: &mut TokenNavigator, line: usize) -> Result<(), ParserError> {
+        match navigator.peek() {
+            Some(token) if token.line == line && token.kind == TokenKind::Punctuation && token.value == ")" => {
+                navigator.advance();
+                Ok(())
+            }
+            Some(token) if token.kind != TokenKind::Eof => Err(ParserError::UnexpectedToken {
+                line: token.line,
+                column: token.column,
+                expected: ")".to_string(),
+                found: format!("{}", token.kind),
+            }),
+            _ => Err(ParserError::UnexpectedEof { expected: ")".to_string() }),
+        }
+    }
+}
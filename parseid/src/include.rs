@@ -0,0 +1,150 @@
+//! DOŁĄCZ "file" - splicing other source files into the program
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::ast::{Program, ProgramElement};
+use crate::directive_parser::is_include_directive;
+use crate::error::ParserError;
+use crate::parser::Parser;
+use crate::source_map::SourceMap;
+
+/// Resolves includes while parsing a file
+pub(crate) struct IncludeResolver<'a> {
+    include_paths: &'a [PathBuf],
+    source_map: SourceMap,
+    /// files currently being included (canonical path, path as named), outermost first
+    stack: Vec<(PathBuf, String)>,
+    /// errors of every file, parsing goes on with the next line or the next include
+    errors: Vec<ParserError>,
+}
+
+impl<'a> IncludeResolver<'a> {
+    pub fn new(include_paths: &'a [PathBuf]) -> Self {
+        Self {
+            include_paths,
+            source_map: SourceMap::new(),
+            stack: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn parse(mut self, source: &str, path: &Path) -> Result<(Program, SourceMap), Vec<ParserError>> {
+        let elements = self.parse_file(source, path);
+        if !self.errors.is_empty() {
+            return Err(self.errors);
+        }
+        Ok((Program { elements }, self.source_map))
+    }
+
+    /// elements of one file with its includes spliced in, lines numbered as in the source map
+    fn parse_file(&mut self, source: &str, path: &Path) -> Vec<ProgramElement> {
+        // one extra line for the end of file
+        let offset = self.source_map.add_file(path.display().to_string(), source.lines().count() + 1);
+
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.stack.push((canonical, path.display().to_string()));
+        let first_error = self.errors.len();
+        let elements = self.parse_elements(source, path, offset);
+        let errors: Vec<ParserError> = self.errors.drain(first_error..).map(|e| Self::in_file(e, path, offset)).collect();
+        self.errors.extend(errors);
+        self.stack.pop();
+
+        elements
+    }
+
+    fn parse_elements(&mut self, source: &str, path: &Path, offset: usize) -> Vec<ProgramElement> {
+        let mut tokens = match lexariel::tokenize(source) {
+            Ok(tokens) => tokens,
+            Err(mut e) => {
+                *e.line_mut() += offset;
+                self.errors.push(e.into());
+                return Vec::new();
+            }
+        };
+        for token in &mut tokens {
+            token.line += offset;
+        }
+        let (program, mut errors) = Parser::new(tokens).parse_recovering();
+        // taken from the back, so they come out in source order among the errors of included files
+        errors.reverse();
+        let elements = self.splice(program.elements, path, &mut errors);
+        self.errors.extend(errors.into_iter().rev());
+        elements
+    }
+
+    /// replaces includes with the included elements, also inside conditional blocks;
+    /// pending holds the parser errors of this file not reported yet, last line first
+    fn splice(&mut self, parsed: Vec<ProgramElement>, path: &Path, pending: &mut Vec<ParserError>) -> Vec<ProgramElement> {
+        let mut elements = Vec::with_capacity(parsed.len());
+        for element in parsed {
+            match element {
+                ProgramElement::Directive(directive) if is_include_directive(&directive.name) => {
+                    while let Some(e) = pending.pop_if(|e| e.line().is_some_and(|line| line < directive.line)) {
+                        self.errors.push(e);
+                    }
+                    let name = directive.arguments.first().map(String::as_str).unwrap_or_default();
+                    match self.include(name, path, directive.line) {
+                        Ok(included) => elements.extend(included),
+                        Err(e) => self.errors.push(e),
+                    }
+                }
+                ProgramElement::Conditional(mut conditional) => {
+                    conditional.then_branch = self.splice(conditional.then_branch, path, pending);
+                    conditional.else_branch = self.splice(conditional.else_branch, path, pending);
+                    elements.push(ProgramElement::Conditional(conditional));
+                }
+                element => elements.push(element),
+            }
+        }
+
+        elements
+    }
+
+    /// errors in included files carry the file name and its own line numbers,
+    /// the parsed file (offset 0) keeps plain errors
+    fn in_file(mut error: ParserError, path: &Path, offset: usize) -> ParserError {
+        if offset == 0 || matches!(error, ParserError::InFile { .. }) {
+            return error;
+        }
+        if let Some(line) = error.line_mut() {
+            *line -= offset;
+        }
+        ParserError::InFile {
+            file: path.display().to_string(),
+            error: Box::new(error),
+        }
+    }
+
+    fn include(&mut self, name: &str, including: &Path, line: usize) -> Result<Vec<ProgramElement>, ParserError> {
+        let path = self.find(name, including).ok_or_else(|| ParserError::IncludeNotFound {
+            path: name.to_string(),
+            line,
+        })?;
+
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if let Some(start) = self.stack.iter().position(|(open, _)| *open == canonical) {
+            let cycle = self.stack[start..].iter()
+                .map(|(_, name)| name.as_str())
+                .chain([self.stack[start].1.as_str()])
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(ParserError::CircularInclude { cycle, line });
+        }
+
+        let source = fs::read_to_string(&path).map_err(|e| ParserError::IncludeFailed {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+            line,
+        })?;
+        Ok(self.parse_file(&source, &path))
+    }
+
+    /// next to the including file first, then in the include paths in order
+    fn find(&self, name: &str, including: &Path) -> Option<PathBuf> {
+        let directory = including.parent().unwrap_or(Path::new(""));
+        std::iter::once(directory)
+            .chain(self.include_paths.iter().map(PathBuf::as_path))
+            .map(|directory| directory.join(name))
+            .find(|candidate| candidate.is_file())
+    }
+}
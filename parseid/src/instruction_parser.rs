@@ -25,6 +25,14 @@ impl InstructionParser {
         let column = token.column;
 
         let operand = if Self::has_operand(&opcode) {
+            // the operand is on the same line, the next one starts another element
+            if navigator.peek().is_some_and(|next| next.kind != TokenKind::Eof && next.line != line) {
+                return Err(ParserError::MissingOperand {
+                    instruction: opcode,
+                    line,
+                    column: column + token.value.chars().count(),
+                });
+            }
             Some(OperandParser::parse_operand(navigator)?)
         } else {
             None
@@ -40,7 +48,7 @@ impl InstructionParser {
 
     fn has_operand(opcode: &str) -> bool {
         match opcode.to_uppercase().as_str() {
-            "STP" | "DNS" | "PZS" | "SDP" | "CZM" | "PWR" | "WPR" | "WYJ" | "WEJSCIE" | "WYJSCIE" => false,
+            "STP" | "DNS" | "PZS" | "SDP" | "CZM" | "PWR" | "POW" | "NOT" | "WPR" | "WYJ" | "WEJSCIE" | "WYJSCIE" => false,
             _ => true,
         }
     }
@@ -7,15 +7,23 @@ mod token_navigator;
 mod operand_parser;
 mod instruction_parser;
 mod directive_parser;
+mod expression_parser;
 mod macro_parser;
+mod conditional_parser;
 mod parser;
+mod include;
+mod source_map;
 mod ascii_art;
 
 pub use error::ParserError;
 pub use parser::Parser;
+pub use directive_parser::{is_string_directive, is_constant_directive, is_include_directive, is_export_directive, is_import_directive, is_section_directive, is_origin_directive};
+pub use source_map::SourceMap;
 pub use ast::*;
 
 use lexariel::Token;
+use std::path::{Path, PathBuf};
+use include::IncludeResolver;
 
 pub fn parse(tokens: Vec<Token>) -> Result<Program, ParserError> {
     let mut parser = Parser::new(tokens);
@@ -27,6 +35,17 @@ pub fn parse_source(source: &str) -> Result<Program, ParserError> {
     parse(tokens)
 }
 
+/// parses the source of the file at path, splicing in files named by DOŁĄCZ;
+/// they are looked up next to the including file, then in include_paths
+pub fn parse_source_file(source: &str, path: &Path, include_paths: &[PathBuf]) -> Result<(Program, SourceMap), ParserError> {
+    parse_source_file_collecting_errors(source, path, include_paths).map_err(|mut errors| errors.remove(0))
+}
+
+/// like parse_source_file, but reports the errors of every line and every included file
+pub fn parse_source_file_collecting_errors(source: &str, path: &Path, include_paths: &[PathBuf]) -> Result<(Program, SourceMap), Vec<ParserError>> {
+    IncludeResolver::new(include_paths).parse(source, path)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ascii_art::print_parseid_logo;
@@ -1,8 +1,10 @@
 //! Macro parsing
 
-use lexariel::TokenKind;
-use crate::ast::{MacroDefinition, MacroCall};
+use lexariel::{Token, TokenKind};
+use crate::ast::{MacroDefinition, MacroCall, Operand};
 use crate::error::ParserError;
+use crate::expression_parser::ExpressionParser;
+use crate::operand_parser::OperandParser;
 use crate::token_navigator::TokenNavigator;
 use crate::parser::Parser;
 
@@ -17,23 +19,64 @@ impl MacroParser {
         let name_token = navigator.consume(TokenKind::Identifier, "macro name")?;
         let name = name_token.value;
 
-        let mut parameters = Vec::new();
+        let mut parameters: Vec<String> = Vec::new();
+        let mut defaults: Vec<Option<Operand>> = Vec::new();
+        let mut variadic = false;
         let mut body = Vec::new();
 
-        // parameters (optional)
+        // parameters (optional) on the MAKRO line: a, b = 5, rest...
         while let Some(token) = navigator.peek() {
+            if token.line != line {
+                break;
+            }
             match token.kind {
-                TokenKind::Identifier => {
-                    parameters.push(navigator.advance().unwrap().value.clone());
-                }
-                TokenKind::Directive if token.value.to_uppercase() == "KONM" => {
+                // KONM or a body starting on the same line
+                TokenKind::Eof | TokenKind::Keyword | TokenKind::LabelDef | TokenKind::Directive => {
                     break;
                 }
-                TokenKind::Keyword | TokenKind::LabelDef => {
-                    break;
+                TokenKind::Punctuation if token.value == "," && !parameters.is_empty() => {
+                    navigator.advance();
+                    if !navigator.peek().is_some_and(|next| next.line == line && next.kind == TokenKind::Identifier) {
+                        return Err(Self::invalid_definition(line, column, "Expected a parameter after ','"));
+                    }
+                }
+                TokenKind::Identifier => {
+                    let parameter = navigator.advance().unwrap().value.clone();
+                    if variadic {
+                        return Err(Self::invalid_definition(line, column, &format!("Parameter {} follows the variadic parameter", parameter)));
+                    }
+                    if parameters.contains(&parameter) {
+                        return Err(Self::invalid_definition(line, column, &format!("Duplicate parameter {}", parameter)));
+                    }
+
+                    let default = if Self::check_punctuation(navigator, line, "=") {
+                        navigator.advance();
+                        Some(OperandParser::parse_operand(navigator)?)
+                    } else {
+                        None
+                    };
+
+                    if Self::check_punctuation(navigator, line, ".") {
+                        for _ in 0..3 {
+                            if !Self::check_punctuation(navigator, line, ".") {
+                                return Err(Self::invalid_definition(line, column, "Expected '...' after variadic parameter"));
+                            }
+                            navigator.advance();
+                        }
+                        if default.is_some() {
+                            return Err(Self::invalid_definition(line, column, "Variadic parameter cannot have a default value"));
+                        }
+                        variadic = true;
+                    } else if default.is_none() && defaults.last().is_some_and(Option::is_some) {
+                        return Err(Self::invalid_definition(line, column, &format!("Parameter {} needs a default value, like the ones before it", parameter)));
+                    }
+
+                    parameters.push(parameter);
+                    defaults.push(default);
                 }
                 _ => {
-                    navigator.advance(); // skip other tokens
+                    let message = format!("Unexpected '{}' in parameter list", token.value);
+                    return Err(Self::invalid_definition(line, column, &message));
                 }
             }
         }
@@ -57,6 +100,8 @@ impl MacroParser {
         Ok(MacroDefinition {
             name,
             parameters,
+            defaults,
+            variadic,
             body,
             line,
             column,
@@ -69,28 +114,62 @@ impl MacroParser {
         let line = token.line;
         let column = token.column;
 
-        let mut arguments = Vec::new();
+        let mut operands = Vec::new();
 
-        // arguments
+        // arguments on the line of the call, any operand syntax, separated by commas or spaces
         while let Some(arg_token) = navigator.peek() {
-            match arg_token.kind {
-                TokenKind::Number | TokenKind::Identifier => {
-                    arguments.push(navigator.advance().unwrap().value.clone());
-                }
-                TokenKind::Eof | TokenKind::LabelDef | TokenKind::Keyword | TokenKind::Directive => {
-                    break;
-                }
-                _ => {
-                    navigator.advance(); // skip other tokens
+            if arg_token.line != line || arg_token.kind == TokenKind::Eof {
+                break;
+            }
+            if !operands.is_empty() && arg_token.kind == TokenKind::Punctuation && arg_token.value == "," {
+                navigator.advance();
+                match navigator.peek() {
+                    Some(next) if next.line == line && Self::starts_argument(next) => {}
+                    Some(next) if next.line == line && next.kind != TokenKind::Eof => {
+                        return Err(Self::unexpected_argument(next));
+                    }
+                    _ => return Err(ParserError::UnexpectedEof { expected: "macro argument".to_string() }),
                 }
+                continue;
+            }
+            if !Self::starts_argument(arg_token) {
+                return Err(Self::unexpected_argument(arg_token));
             }
+            operands.push(OperandParser::parse_operand(navigator)?);
         }
 
         Ok(MacroCall {
             name,
-            arguments,
+            arguments: operands.iter().map(Operand::to_string).collect(),
+            operands,
             line,
             column,
         })
     }
+
+    fn starts_argument(token: &Token) -> bool {
+        ExpressionParser::starts_expression(token)
+            || (token.kind == TokenKind::Punctuation && matches!(token.value.as_str(), "#" | "[" | "+"))
+    }
+
+    fn check_punctuation(navigator: &TokenNavigator, line: usize, value: &str) -> bool {
+        navigator.peek().is_some_and(|token| token.line == line && token.kind == TokenKind::Punctuation && token.value == value)
+    }
+
+    fn unexpected_argument(token: &Token) -> ParserError {
+        ParserError::UnexpectedToken {
+            line: token.line,
+            column: token.column,
+            expected: "macro argument".to_string(),
+            found: token.value.clone(),
+        }
+    }
+
+    fn invalid_definition(line: usize, column: usize, message: &str) -> ParserError {
+        ParserError::InvalidMacroDefinition {
+            line,
+            column,
+            message: message.to_string(),
+        }
+    }
 }
@@ -1,45 +1,35 @@
-//! (address, address[index])
+//! (address, address[index], expression)
 
 use lexariel::TokenKind;
 use crate::ast::{Operand, AddressingMode};
 use crate::error::ParserError;
+use crate::expression_parser::ExpressionParser;
 use crate::token_navigator::TokenNavigator;
 
 pub(crate) struct DirectParser;
 
 impl DirectParser {
-    pub fn parse_number_or_indexed(navigator: &mut TokenNavigator) -> Result<Operand, ParserError> {
-        let number_token = navigator.advance().unwrap().clone();
-        
-        // number[index]
-        if navigator.check(TokenKind::Punctuation) && navigator.peek().unwrap().value == "[" {
-            Self::parse_indexed(navigator, number_token.value)
-        } else {
-            // direct addressing
-            Ok(Operand {
-                addressing_mode: AddressingMode::Direct,
-                value: number_token.value,
-            })
-        }
-    }
-
-    pub fn parse_identifier_or_indexed(navigator: &mut TokenNavigator) -> Result<Operand, ParserError> {
-        let addr_token = navigator.consume_value("address")?;
-        let addr_value = addr_token.value.clone();
-        
+    pub fn parse(navigator: &mut TokenNavigator) -> Result<Operand, ParserError> {
+        let column = navigator.peek().unwrap().column;
         // address[index]
-        if navigator.check(TokenKind::Punctuation) && navigator.peek().unwrap().value == "[" {
-            Self::parse_indexed(navigator, addr_value)
-        } else {
-            // direct addressing
-            Ok(Operand {
-                addressing_mode: AddressingMode::Direct,
-                value: addr_value,
-            })
+        let is_indexed = navigator.peek_ahead(1)
+            .is_some_and(|next| next.kind == TokenKind::Punctuation && next.value == "[");
+        if is_indexed && matches!(navigator.peek().unwrap().kind, TokenKind::Number | TokenKind::Identifier) {
+            let addr_token = navigator.advance().unwrap().clone();
+            return Self::parse_indexed(navigator, addr_token.value, addr_token.column);
         }
+
+        // direct addressing
+        let expression = ExpressionParser::parse(navigator, "address")?;
+        Ok(Operand {
+            addressing_mode: AddressingMode::Direct,
+            value: expression.to_string(),
+            expression: Some(expression).filter(|expression| !expression.is_simple()),
+            column,
+        })
     }
 
-    fn parse_indexed(navigator: &mut TokenNavigator, address: String) -> Result<Operand, ParserError> {
+    fn parse_indexed(navigator: &mut TokenNavigator, address: String, column: usize) -> Result<Operand, ParserError> {
         navigator.advance(); // [
         let index_token = navigator.consume_value("index")?;
         let index_value = index_token.value.clone();
@@ -51,6 +41,8 @@ impl DirectParser {
                 index: index_value.clone(),
             },
             value: format!("{}[{}]", address, index_value),
+            expression: None,
+            column,
         })
     }
 }
@@ -1,18 +1,21 @@
-//! value
+//! #value, #expression
 
 use crate::ast::{Operand, AddressingMode};
 use crate::error::ParserError;
+use crate::expression_parser::ExpressionParser;
 use crate::token_navigator::TokenNavigator;
 
 pub(crate) struct ImmediateParser;
 
 impl ImmediateParser {
     pub fn parse(navigator: &mut TokenNavigator) -> Result<Operand, ParserError> {
-        navigator.advance(); // #
-        let value_token = navigator.consume_value("immediate value")?;
+        let column = navigator.advance().unwrap().column; // #
+        let expression = ExpressionParser::parse(navigator, "immediate value")?;
         Ok(Operand {
             addressing_mode: AddressingMode::Immediate,
-            value: value_token.value,
+            value: expression.to_string(),
+            expression: Some(expression).filter(|expression| !expression.is_simple()),
+            column,
         })
     }
 }
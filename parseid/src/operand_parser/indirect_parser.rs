@@ -3,41 +3,44 @@
 use lexariel::TokenKind;
 use crate::ast::{Operand, AddressingMode};
 use crate::error::ParserError;
+use crate::expression_parser::ExpressionParser;
 use crate::token_navigator::TokenNavigator;
+use super::validator::OperandValidator;
 
 pub(crate) struct IndirectParser;
 
 impl IndirectParser {
     pub fn parse(navigator: &mut TokenNavigator) -> Result<Operand, ParserError> {
-        navigator.consume(TokenKind::Punctuation, "[")?;
+        let column = navigator.consume(TokenKind::Punctuation, "[")?.column;
 
         // multiple indirect [[...]]
         if navigator.check(TokenKind::Punctuation) && navigator.peek().unwrap().value == "[" {
-            Self::parse_multiple_indirect(navigator)
+            Self::parse_multiple_indirect(navigator, column)
         } else {
-            Self::parse_single_indirect(navigator)
+            Self::parse_single_indirect(navigator, column)
         }
     }
 
-    fn parse_multiple_indirect(navigator: &mut TokenNavigator) -> Result<Operand, ParserError> {
+    fn parse_multiple_indirect(navigator: &mut TokenNavigator, column: usize) -> Result<Operand, ParserError> {
         navigator.advance(); // second [
-        let value_token = navigator.consume_value("address")?;
-        let value = value_token.value.clone();
+        let expression = ExpressionParser::parse(navigator, "address")?;
         navigator.consume(TokenKind::Punctuation, "]")?; // first ]
         navigator.consume(TokenKind::Punctuation, "]")?; // second ]
         
         Ok(Operand {
             addressing_mode: AddressingMode::MultipleIndirect,
-            value,
+            value: expression.to_string(),
+            expression: Some(expression).filter(|expression| !expression.is_simple()),
+            column,
         })
     }
 
-    fn parse_single_indirect(navigator: &mut TokenNavigator) -> Result<Operand, ParserError> {
+    fn parse_single_indirect(navigator: &mut TokenNavigator, column: usize) -> Result<Operand, ParserError> {
         let value_token = navigator.peek().ok_or(ParserError::UnexpectedEof {
             expected: "address or register".to_string(),
         })?;
 
-        if value_token.kind == TokenKind::Identifier && value_token.value.to_uppercase().starts_with('R') {
+        if value_token.kind == TokenKind::Identifier && OperandValidator::is_register(&value_token.value) {
             // register indirect [R0]
             let reg_token = navigator.advance().unwrap().clone();
             navigator.consume(TokenKind::Punctuation, "]")?;
@@ -45,16 +48,19 @@ impl IndirectParser {
             Ok(Operand {
                 addressing_mode: AddressingMode::RegisterIndirect,
                 value: reg_token.value,
+                expression: None,
+                column,
             })
         } else {
             // regular indirect [address]
-            let addr_token = navigator.consume_value("address")?;
-            let value = addr_token.value.clone();
+            let expression = ExpressionParser::parse(navigator, "address")?;
             navigator.consume(TokenKind::Punctuation, "]")?;
             
             Ok(Operand {
                 addressing_mode: AddressingMode::Indirect,
-                value,
+                value: expression.to_string(),
+                expression: Some(expression).filter(|expression| !expression.is_simple()),
+                column,
             })
         }
     }
@@ -38,16 +38,11 @@ impl OperandParser {
             TokenKind::Punctuation if token.value == "+" || token.value == "-" => {
                 RelativeParser::parse_with_sign(navigator)
             }
-            TokenKind::Number => {
-                let number_token = navigator.peek().unwrap();
-                if number_token.value.starts_with('-') {
-                    RelativeParser::parse_negative_number(navigator)
-                } else {
-                    DirectParser::parse_number_or_indexed(navigator)
-                }
+            TokenKind::Number if token.value.starts_with('-') => {
+                RelativeParser::parse_negative_number(navigator)
             }
             _ => {
-                DirectParser::parse_identifier_or_indexed(navigator)
+                DirectParser::parse(navigator)
             }
         }
     }
@@ -3,6 +3,7 @@
 use lexariel::TokenKind;
 use crate::ast::{Operand, AddressingMode};
 use crate::error::ParserError;
+use crate::expression_parser::ExpressionParser;
 use crate::token_navigator::TokenNavigator;
 
 pub(crate) struct RegisterParser;
@@ -11,23 +12,27 @@ impl RegisterParser {
     pub fn parse(navigator: &mut TokenNavigator) -> Result<Operand, ParserError> {
         let reg_token = navigator.consume(TokenKind::Identifier, "register")?;
         let reg_name = reg_token.value.clone();
+        let column = reg_token.column;
         
         // R0[offset]
         if navigator.check(TokenKind::Punctuation) && navigator.peek().unwrap().value == "[" {
-            Self::parse_base_register(navigator, reg_name)
+            Self::parse_base_register(navigator, reg_name, column)
         } else {
             // simple register addressing
             Ok(Operand {
                 addressing_mode: AddressingMode::Register,
                 value: reg_name,
+                expression: None,
+                column,
             })
         }
     }
 
-    fn parse_base_register(navigator: &mut TokenNavigator, reg_name: String) -> Result<Operand, ParserError> {
+    fn parse_base_register(navigator: &mut TokenNavigator, reg_name: String, column: usize) -> Result<Operand, ParserError> {
         navigator.advance(); // [
-        let offset_token = navigator.consume_value("offset")?;
-        let offset_value = offset_token.value.clone();
+        // R1[tab+2], a label or constant as well as a number
+        let expression = ExpressionParser::parse(navigator, "offset")?;
+        let offset_value = expression.to_string();
         navigator.consume(TokenKind::Punctuation, "]")?;
         
         Ok(Operand {
@@ -36,6 +41,8 @@ impl RegisterParser {
                 offset: offset_value.clone(),
             },
             value: format!("{}[{}]", reg_name, offset_value),
+            expression: Some(expression).filter(|expression| !expression.is_simple()),
+            column,
         })
     }
 }
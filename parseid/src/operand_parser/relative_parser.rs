@@ -13,6 +13,8 @@ impl RelativeParser {
         Ok(Operand {
             addressing_mode: AddressingMode::Relative,
             value: format!("{}{}", sign_token.value, offset_token.value),
+            expression: None,
+            column: sign_token.column,
         })
     }
 
@@ -21,6 +23,8 @@ impl RelativeParser {
         Ok(Operand {
             addressing_mode: AddressingMode::Relative,
             value: number_token.value,
+            expression: None,
+            column: number_token.column,
         })
     }
 }
@@ -7,6 +7,7 @@ use crate::token_navigator::TokenNavigator;
 use crate::instruction_parser::InstructionParser;
 use crate::directive_parser::DirectiveParser;
 use crate::macro_parser::MacroParser;
+use crate::conditional_parser::{ConditionalParser, is_conditional_start, is_branch_end};
 
 pub struct Parser {
     navigator: TokenNavigator,
@@ -20,20 +21,68 @@ impl Parser {
         }
     }
 
-    /// parses the entire program
+    /// parses the entire program, stopping at the first error
     pub fn parse(&mut self) -> Result<Program, ParserError> {
+        let (program, errors) = self.parse_recovering();
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(program),
+        }
+    }
+
+    /// parses the entire program, going on with the next line after an error;
+    /// the program holds the elements that could be parsed
+    pub fn parse_recovering(&mut self) -> (Program, Vec<ParserError>) {
         let mut program = Program::new();
+        let mut errors = Vec::new();
 
         while let Some(token) = self.navigator.peek() {
             if token.kind == TokenKind::Eof {
                 break;
             }
 
-            let element = self.parse_program_element()?;
-            program.add_element(element);
+            // a block (MAKRO, JEŻELI) failing on a later line is skipped up to there,
+            // anything else ends with its line and is reported there
+            let start_line = token.line;
+            let start = self.navigator.position();
+            let block = Self::starts_block(token);
+            match self.parse_program_element() {
+                Ok(element) => program.add_element(element),
+                Err(error) => {
+                    let Some(line) = error.line() else {
+                        errors.push(error);
+                        break;
+                    };
+                    if block {
+                        errors.push(error);
+                        self.navigator.skip_line(line.max(start_line));
+                    } else {
+                        errors.push(self.cut_at_line_end(error, start_line));
+                        self.navigator.rewind(start);
+                        self.navigator.skip_line(start_line);
+                    }
+                }
+            }
         }
 
-        Ok(program)
+        (program, errors)
+    }
+
+    fn starts_block(token: &Token) -> bool {
+        token.kind == TokenKind::Directive && (token.value.to_uppercase() == "MAKRO" || is_conditional_start(&token.value))
+    }
+
+    /// an element that ran into the next line was cut short at the end of its own (`POB #`)
+    fn cut_at_line_end(&self, error: ParserError, line: usize) -> ParserError {
+        match error {
+            ParserError::UnexpectedToken { line: found_line, expected, .. } if found_line > line => ParserError::UnexpectedToken {
+                line,
+                column: self.navigator.line_end(line),
+                expected,
+                found: "end of line".to_string(),
+            },
+            error => error,
+        }
     }
 
     /// parses a top-level program element
@@ -53,6 +102,15 @@ impl Parser {
                     if token.value.to_uppercase() == "MAKRO" {
                         let macro_def = MacroParser::parse_macro_definition(navigator)?;
                         Ok(ProgramElement::MacroDefinition(macro_def))
+                    } else if is_conditional_start(&token.value) {
+                        let conditional = ConditionalParser::parse_conditional(navigator)?;
+                        Ok(ProgramElement::Conditional(conditional))
+                    } else if is_branch_end(&token.value) {
+                        Err(ParserError::UnmatchedConditional {
+                            directive: token.value.clone(),
+                            line: token.line,
+                            column: token.column,
+                        })
                     } else {
                         let directive = DirectiveParser::parse_directive(navigator)?;
                         Ok(ProgramElement::Directive(directive))
@@ -0,0 +1,41 @@
+//! origin of program lines when several files are spliced together
+
+/// every file gets its own range of line numbers in the combined program,
+/// the file being parsed keeps its own numbering
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SourceFile {
+    path: String,
+    /// added to the file's own line numbers
+    offset: usize,
+    line_count: usize,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// reserves line numbers for a file, returns the offset to add to its lines
+    pub fn add_file(&mut self, path: String, line_count: usize) -> usize {
+        let offset = self.files.last().map_or(0, |file| file.offset + file.line_count);
+        self.files.push(SourceFile { path, offset, line_count });
+        offset
+    }
+
+    /// file and line within it of a line of the combined program
+    pub fn locate(&self, line: usize) -> Option<(&str, usize)> {
+        self.files.iter()
+            .find(|file| line > file.offset && line <= file.offset + file.line_count)
+            .map(|file| (file.path.as_str(), line - file.offset))
+    }
+
+    /// first file is the one that was parsed, the rest in order of inclusion
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        self.files.iter().map(|file| file.path.as_str())
+    }
+}
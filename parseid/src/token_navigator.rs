@@ -20,7 +20,7 @@ impl TokenNavigator {
     }
 
     /// returns the token at the given offset without advancing
-    pub fn _peek_ahead(&self, offset: usize) -> Option<&Token> {
+    pub fn peek_ahead(&self, offset: usize) -> Option<&Token> {
         self.tokens.get(self.position + offset)
     }
 
@@ -35,6 +35,29 @@ impl TokenNavigator {
         }
     }
 
+    /// where the navigator is, to go back to with rewind
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn rewind(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// column just past the last token of the line, 1 for a line without tokens
+    pub fn line_end(&self, line: usize) -> usize {
+        self.tokens.iter()
+            .rfind(|token| token.line == line && token.kind != TokenKind::Eof)
+            .map_or(1, |token| token.column + token.value.chars().count())
+    }
+
+    /// skips the rest of the given line, stopping at the end of file
+    pub fn skip_line(&mut self, line: usize) {
+        while self.peek().is_some_and(|token| token.line <= line && token.kind != TokenKind::Eof) {
+            self.position += 1;
+        }
+    }
+
     /// checks if the current token matches the expected kind
     pub fn check(&self, kind: TokenKind) -> bool {
         if let Some(token) = self.peek() {
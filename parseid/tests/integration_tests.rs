@@ -31,7 +31,7 @@ fn test_complex_program() {
             ProgramElement::LabelDefinition(_) => label_count += 1,
             ProgramElement::Instruction(_) => instruction_count += 1,
             ProgramElement::Directive(_) => directive_count += 1,
-            ProgramElement::MacroCall(_) => {}
+            ProgramElement::MacroCall(_) | ProgramElement::Conditional(_) => {}
         }
     }
     
@@ -7,4 +7,7 @@ mod unit {
     pub mod macro_tests;
     pub mod error_tests;
     pub mod value_format_tests;
+    pub mod expression_tests;
+    pub mod include_tests;
+    pub mod conditional_tests;
 }
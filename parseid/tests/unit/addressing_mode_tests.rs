@@ -88,6 +88,20 @@ fn test_base_register_addressing() {
     }
 }
 
+#[test]
+fn test_base_register_offset_expression() {
+    let program = parse_source("POB R1[tab + 2]").unwrap();
+    
+    if let ProgramElement::Instruction(inst) = &program.elements[0] {
+        let operand = inst.operand.as_ref().unwrap();
+        assert_eq!(operand.addressing_mode, AddressingMode::BaseRegister { base: "R1".to_string(), offset: "tab+2".to_string() });
+        assert_eq!(operand.value, "R1[tab+2]");
+        assert!(operand.expression.is_some());
+    } else {
+        panic!("Expected instruction");
+    }
+}
+
 #[test]
 fn test_indexed_addressing() {
     let program = parse_source("POB array[i]").unwrap();
@@ -172,3 +186,16 @@ fn test_multiple_addressing_modes() {
         }
     }
 }
+
+#[test]
+fn test_operand_column() {
+    // first character of the operand, # and [ included
+    let program = parse_source("POB #42\n  DOD  [[ptr]]\nSOB -3\nPOB R1[2]\nPOB tab[R2]\nSOB +loop\n").unwrap();
+    let columns: Vec<usize> = program.elements.iter()
+        .map(|element| match element {
+            ProgramElement::Instruction(inst) => inst.operand.as_ref().unwrap().column,
+            other => panic!("Expected instruction, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(columns, [5, 8, 5, 5, 5, 5]);
+}
@@ -0,0 +1,70 @@
+use parseid::{parse_source, ast::*, ParserError};
+
+#[test]
+fn test_conditional_block() {
+    let program = parse_source(r#"
+        JEŻELI VERSION >= 2
+            POB a
+            MNO b
+        INACZEJ
+            POB a
+        KONJ
+        STP
+    "#).unwrap();
+
+    assert_eq!(program.elements.len(), 2);
+    match &program.elements[0] {
+        ProgramElement::Conditional(conditional) => {
+            assert_eq!(conditional.condition, Expression::Binary {
+                operator: BinaryOperator::GreaterEqual,
+                left: Box::new(Expression::Symbol("VERSION".to_string())),
+                right: Box::new(Expression::Number("2".to_string())),
+            });
+            assert_eq!(conditional.then_branch.len(), 2);
+            assert_eq!(conditional.else_branch.len(), 1);
+            assert_eq!(conditional.line, 2);
+        }
+        other => panic!("Expected conditional block, got {:?}", other),
+    }
+    assert!(matches!(&program.elements[1], ProgramElement::Instruction(inst) if inst.opcode == "STP"));
+}
+
+#[test]
+fn test_nested_conditionals() {
+    let program = parse_source(r#"
+        JEZELI A
+            JEŻELI B == 1 & C != 0
+                label: RST 1
+            KONJ
+        KONJ
+    "#).unwrap();
+
+    let ProgramElement::Conditional(outer) = &program.elements[0] else {
+        panic!("Expected conditional block");
+    };
+    assert!(outer.else_branch.is_empty());
+    let ProgramElement::Conditional(inner) = &outer.then_branch[0] else {
+        panic!("Expected nested conditional block");
+    };
+    assert_eq!(inner.condition.to_string(), "B==1&C!=0");
+    assert!(matches!(&inner.then_branch[0], ProgramElement::LabelDefinition(label) if label.name == "label"));
+    assert!(matches!(&inner.then_branch[1], ProgramElement::Directive(dir) if dir.name == "RST"));
+}
+
+#[test]
+fn test_conditional_errors() {
+    let error = parse_source("JEŻELI A\nPOB x\n").unwrap_err();
+    assert_eq!(error, ParserError::UnterminatedConditional { line: 1, column: 1 });
+
+    let error = parse_source("JEŻELI A\nINACZEJ\nINACZEJ\nKONJ").unwrap_err();
+    assert!(matches!(error, ParserError::UnterminatedConditional { line: 1, .. }));
+
+    let error = parse_source("POB x\nKONJ").unwrap_err();
+    assert!(matches!(error, ParserError::UnmatchedConditional { ref directive, line: 2, .. } if directive == "KONJ"));
+
+    let error = parse_source("INACZEJ").unwrap_err();
+    assert!(matches!(error, ParserError::UnmatchedConditional { line: 1, .. }));
+
+    // the condition belongs on the JEŻELI line
+    assert!(parse_source("JEŻELI\nA\nKONJ").is_err());
+}
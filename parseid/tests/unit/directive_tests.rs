@@ -1,4 +1,4 @@
-use parseid::{parse_source, ast::*};
+use parseid::{parse_source, ast::*, ParserError};
 
 #[test]
 fn test_directive() {
@@ -25,3 +25,119 @@ fn test_directive_without_arguments() {
         panic!("Expected directive");
     }
 }
+
+#[test]
+fn test_isa_directive() {
+    let program = parse_source(r#"
+        ISA classic-w+muldiv
+        start: POB #1
+    "#).unwrap();
+    assert_eq!(program.elements.len(), 3);
+    
+    if let ProgramElement::Directive(dir) = &program.elements[0] {
+        assert_eq!(dir.name, "ISA");
+        assert_eq!(dir.arguments, vec!["classic-w+muldiv"]);
+    } else {
+        panic!("Expected directive");
+    }
+}
+
+#[test]
+fn test_string_directives() {
+    let program = parse_source(r#"
+        msg: NAPISZ "HI\n"
+        NAPISD "abc"
+        RST 'A'
+    "#).unwrap();
+    assert_eq!(program.elements.len(), 4);
+    
+    if let ProgramElement::Directive(dir) = &program.elements[1] {
+        assert_eq!(dir.name, "NAPISZ");
+        assert_eq!(dir.arguments, vec!["HI\n"]);
+    } else {
+        panic!("Expected directive");
+    }
+    
+    if let ProgramElement::Directive(dir) = &program.elements[3] {
+        assert_eq!(dir.name, "RST");
+        assert_eq!(dir.arguments, vec!["65"]);
+    } else {
+        panic!("Expected directive");
+    }
+}
+
+#[test]
+fn test_string_directive_requires_string() {
+    assert!(parse_source("NAPIS 42").is_err());
+    assert!(parse_source("NAPIS\nSTP").is_err());
+}
+
+#[test]
+fn test_directive_argument_lists() {
+    let program = parse_source(r#"
+        tab: RST 1, 3, -5
+        buf: RPA 4, 0xFF
+        next
+    "#).unwrap();
+    assert_eq!(program.elements.len(), 5);
+    
+    if let ProgramElement::Directive(dir) = &program.elements[1] {
+        assert_eq!(dir.arguments, vec!["1", "3", "-5"]);
+    } else {
+        panic!("Expected directive");
+    }
+    
+    // arguments stop at the end of the line
+    if let ProgramElement::Directive(dir) = &program.elements[3] {
+        assert_eq!(dir.arguments, vec!["4", "0xFF"]);
+    } else {
+        panic!("Expected directive");
+    }
+    assert!(matches!(&program.elements[4], ProgramElement::MacroCall(call) if call.name == "next"));
+}
+
+#[test]
+fn test_directive_argument_list_syntax() {
+    let error_at = |source: &str| match parse_source(source) {
+        Err(ParserError::UnexpectedToken { line, column, expected, found }) => (line, column, expected, found),
+        other => panic!("Expected UnexpectedToken for {:?}, got {:?}", source, other),
+    };
+
+    // empty element
+    assert_eq!(error_at("RST 1,,2"), (1, 7, "directive argument".to_string(), "Punctuation".to_string()));
+    // trailing comma
+    assert_eq!(error_at("RST 3,\nSTP"), (1, 7, "directive argument".to_string(), "end of line".to_string()));
+    // missing separator
+    assert_eq!(error_at("RST 7 8"), (1, 7, "comma".to_string(), "Number".to_string()));
+    // stray token
+    assert_eq!(error_at("RST 1, 2 ]"), (1, 10, "comma".to_string(), "Punctuation".to_string()));
+    assert_eq!(error_at("RST ] 1"), (1, 5, "directive argument".to_string(), "Punctuation".to_string()));
+}
+
+#[test]
+fn test_constant_directive() {
+    let program = parse_source(r#"
+        STAŁA SIZE = 16
+        STALA LAST SIZE - 1
+        STP
+    "#).unwrap();
+    assert_eq!(program.elements.len(), 3);
+
+    if let ProgramElement::Directive(dir) = &program.elements[0] {
+        assert_eq!(dir.name, "STAŁA");
+        assert_eq!(dir.arguments, vec!["SIZE", "16"]);
+        assert_eq!(dir.expressions, vec![None, None]);
+    } else {
+        panic!("Expected directive");
+    }
+
+    if let ProgramElement::Directive(dir) = &program.elements[1] {
+        assert_eq!(dir.arguments, vec!["LAST", "SIZE-1"]);
+        assert!(dir.expressions[1].is_some());
+    } else {
+        panic!("Expected directive");
+    }
+
+    assert!(parse_source("STAŁA 16").is_err());
+    assert!(parse_source("STAŁA\nSIZE = 16").is_err());
+}
@@ -1,4 +1,4 @@
-use parseid::{parse_source, ParserError};
+use parseid::{parse_source, Parser, ParserError};
 
 #[test]
 fn test_error_unexpected_token() {
@@ -46,3 +46,43 @@ fn test_error_unexpected_eof() {
         }
     }
 }
+
+#[test]
+fn test_error_recovery_reports_every_line() {
+    let tokens = lexariel::tokenize("POB 5\nDOD (\nSTP\nŁAD ) 1\nDOD 1\n").unwrap();
+    let (program, errors) = Parser::new(tokens).parse_recovering();
+
+    // parsing resumes on the line after each error, DOD ( does not take STP with it
+    let lines: Vec<_> = errors.iter().map(ParserError::line).collect();
+    assert_eq!(lines, vec![Some(2), Some(4)]);
+    assert_eq!(program.elements.len(), 3);
+}
+
+#[test]
+fn test_error_recovery_operand_cut_by_end_of_line() {
+    let tokens = lexariel::tokenize("POB #
+DOD [5
+ODE [6
+SOB
+STP
+").unwrap();
+    let (program, errors) = Parser::new(tokens).parse_recovering();
+
+    // every error on the line of its own instruction, right after what is there
+    let positions: Vec<_> = errors.iter().map(|error| (error.line(), error.column())).collect();
+    assert_eq!(positions, [(Some(1), Some(6)), (Some(2), Some(7)), (Some(3), Some(7)), (Some(4), Some(4))]);
+    assert!(matches!(&errors[0], ParserError::UnexpectedToken { found, .. } if found == "end of line"));
+    assert!(matches!(&errors[3], ParserError::MissingOperand { instruction, .. } if instruction == "SOB"));
+    assert_eq!(program.elements.len(), 1);
+}
+
+#[test]
+fn test_error_codes() {
+    assert_eq!(parse_source("DOD").unwrap_err().code(), "E0202");
+    assert_eq!(parse_source("DOD )").unwrap_err().code(), "E0201");
+    // tokenizing errors keep the lexer's code, wherever the file was included from
+    let error = parse_source("DOD @").unwrap_err();
+    assert_eq!(error.code(), "E0106");
+    let error = ParserError::InFile { file: "lib.asmod".to_string(), error: Box::new(error) };
+    assert_eq!(error.code(), "E0106");
+}
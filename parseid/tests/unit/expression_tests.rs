@@ -0,0 +1,101 @@
+use parseid::{parse_source, ast::*};
+
+fn parse_operand(source: &str) -> Operand {
+    let program = parse_source(source).unwrap();
+    match &program.elements[0] {
+        ProgramElement::Instruction(inst) => inst.operand.clone().unwrap(),
+        other => panic!("Expected instruction, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_simple_operands_have_no_expression() {
+    let operand = parse_operand("POB tab");
+    assert_eq!(operand.value, "tab");
+    assert_eq!(operand.expression, None);
+    
+    let operand = parse_operand("DOD #0x10");
+    assert_eq!(operand.value, "0x10");
+    assert_eq!(operand.expression, None);
+}
+
+#[test]
+fn test_label_arithmetic() {
+    let operand = parse_operand("POB tab+3");
+    assert_eq!(operand.addressing_mode, AddressingMode::Direct);
+    assert_eq!(operand.value, "tab+3");
+    assert_eq!(operand.expression, Some(Expression::Binary {
+        operator: BinaryOperator::Add,
+        left: Box::new(Expression::Symbol("tab".to_string())),
+        right: Box::new(Expression::Number("3".to_string())),
+    }));
+    
+    // end-1 is lexed as end, -1
+    let operand = parse_operand("POB #end-1");
+    assert_eq!(operand.addressing_mode, AddressingMode::Immediate);
+    assert_eq!(operand.value, "end-1");
+    assert!(matches!(operand.expression, Some(Expression::Binary { operator: BinaryOperator::Subtract, .. })));
+}
+
+#[test]
+fn test_operator_precedence() {
+    assert_eq!(parse_operand("POB #1 + 2 * 3").value, "1+2*3");
+    assert_eq!(parse_operand("POB #(1 + 2) * 3").value, "(1+2)*3");
+    assert_eq!(parse_operand("POB #10 - (4 - 1)").value, "10-(4-1)");
+    assert_eq!(parse_operand("POB #1 << 4 | 2 & 3").value, "1<<4|2&3");
+    assert_eq!(parse_operand("POB #-(end - start) % 8 >> 1").value, "-(end-start)%8>>1");
+    
+    let operand = parse_operand("POB #1 << 2 + 3");
+    assert!(matches!(operand.expression, Some(Expression::Binary { operator: BinaryOperator::ShiftLeft, .. })));
+}
+
+#[test]
+fn test_current_address() {
+    let operand = parse_operand("SOB $");
+    assert_eq!(operand.expression, Some(Expression::CurrentAddress));
+    
+    assert_eq!(parse_operand("SOB $+2").value, "$+2");
+}
+
+#[test]
+fn test_expressions_in_indirect_modes() {
+    let operand = parse_operand("POB [ptr+1]");
+    assert_eq!(operand.addressing_mode, AddressingMode::Indirect);
+    assert_eq!(operand.value, "ptr+1");
+    
+    let operand = parse_operand("POB [[ptr*2]]");
+    assert_eq!(operand.addressing_mode, AddressingMode::MultipleIndirect);
+    assert_eq!(operand.value, "ptr*2");
+    
+    // identifiers starting with R are not registers
+    let operand = parse_operand("POB [result]");
+    assert_eq!(operand.addressing_mode, AddressingMode::Indirect);
+}
+
+#[test]
+fn test_expressions_in_directives() {
+    let program = parse_source("size: RST end-start, tab+1, 7\nbuf: RPA (end-start)*2, -1").unwrap();
+    
+    if let ProgramElement::Directive(dir) = &program.elements[1] {
+        assert_eq!(dir.arguments, vec!["end-start", "tab+1", "7"]);
+        assert_eq!(dir.expressions.len(), 3);
+        assert!(dir.expressions[0].is_some());
+        assert!(dir.expressions[2].is_none());
+    } else {
+        panic!("Expected directive");
+    }
+    
+    if let ProgramElement::Directive(dir) = &program.elements[3] {
+        assert_eq!(dir.arguments, vec!["(end-start)*2", "-1"]);
+        assert_eq!(dir.expressions[1], None);
+    } else {
+        panic!("Expected directive");
+    }
+}
+
+#[test]
+fn test_expression_errors() {
+    assert!(parse_source("POB #(1 + 2").is_err());
+    assert!(parse_source("POB tab +").is_err());
+    assert!(parse_source("POB #(1 +\n2)").is_err());
+}
@@ -0,0 +1,111 @@
+use parseid::{parse_source_file, parse_source_file_collecting_errors, ast::*, ParserError};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn parse_file(path: &Path, include_paths: &[PathBuf]) -> Result<(Program, parseid::SourceMap), ParserError> {
+    let source = fs::read_to_string(path).unwrap();
+    parse_source_file(&source, path, include_paths)
+}
+
+#[test]
+fn test_include_splices_elements() {
+    let temp_dir = TempDir::new().unwrap();
+    let main = temp_dir.path().join("main.asmod");
+    fs::write(temp_dir.path().join("data.asmod"), "value: RST 5\n").unwrap();
+    fs::write(&main, "POB value\nDOŁĄCZ \"data.asmod\"\nSTP\n").unwrap();
+
+    let (program, source_map) = parse_file(&main, &[]).unwrap();
+    assert_eq!(program.elements.len(), 4);
+    assert!(matches!(&program.elements[0], ProgramElement::Instruction(inst) if inst.opcode == "POB"));
+    assert!(matches!(&program.elements[1], ProgramElement::LabelDefinition(label) if label.name == "value"));
+    assert!(matches!(&program.elements[2], ProgramElement::Directive(dir) if dir.name == "RST"));
+    assert!(matches!(&program.elements[3], ProgramElement::Instruction(inst) if inst.opcode == "STP" && inst.line == 3));
+
+    // included lines are numbered after the including file
+    if let ProgramElement::Directive(dir) = &program.elements[2] {
+        let (file, line) = source_map.locate(dir.line).unwrap();
+        assert!(file.ends_with("data.asmod"));
+        assert_eq!(line, 1);
+    }
+    assert_eq!(source_map.locate(3), Some((main.to_str().unwrap(), 3)));
+    assert_eq!(source_map.files().count(), 2);
+}
+
+#[test]
+fn test_include_search_paths() {
+    let temp_dir = TempDir::new().unwrap();
+    let lib = temp_dir.path().join("lib");
+    let nested = lib.join("nested");
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(lib.join("io.asmod"), "DOLACZ \"nested/more.asmod\"\nWYJSCIE\n").unwrap();
+    fs::write(nested.join("more.asmod"), "POB #1\n").unwrap();
+
+    let main = temp_dir.path().join("main.asmod");
+    fs::write(&main, "DOŁĄCZ \"io.asmod\"\nSTP\n").unwrap();
+
+    let error = parse_file(&main, &[]).unwrap_err();
+    assert_eq!(error, ParserError::IncludeNotFound { path: "io.asmod".to_string(), line: 1 });
+
+    // nested includes are looked up next to the file naming them
+    let (program, _) = parse_file(&main, &[lib]).unwrap();
+    let opcodes: Vec<&str> = program.elements.iter()
+        .filter_map(|element| match element {
+            ProgramElement::Instruction(inst) => Some(inst.opcode.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(opcodes, ["POB", "WYJSCIE", "STP"]);
+}
+
+#[test]
+fn test_circular_include() {
+    let temp_dir = TempDir::new().unwrap();
+    let main = temp_dir.path().join("a.asmod");
+    fs::write(&main, "DOŁĄCZ \"b.asmod\"\n").unwrap();
+    fs::write(temp_dir.path().join("b.asmod"), "STP\nDOŁĄCZ \"a.asmod\"\n").unwrap();
+
+    let error = parse_file(&main, &[]).unwrap_err();
+    match error {
+        ParserError::InFile { file, error } => {
+            assert!(file.ends_with("b.asmod"));
+            assert!(matches!(*error, ParserError::CircularInclude { ref cycle, line: 2 } if cycle.matches("a.asmod").count() == 2));
+        }
+        other => panic!("Expected error in b.asmod, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_error_in_included_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let main = temp_dir.path().join("main.asmod");
+    fs::write(temp_dir.path().join("bad.asmod"), "STP\n\nPOB ]\n").unwrap();
+    fs::write(&main, "STP\nDOŁĄCZ \"bad.asmod\"\n").unwrap();
+
+    let error = parse_file(&main, &[]).unwrap_err();
+    match error {
+        ParserError::InFile { file, error } => {
+            assert!(file.ends_with("bad.asmod"));
+            assert!(matches!(*error, ParserError::UnexpectedToken { line: 3, .. }));
+        }
+        other => panic!("Expected error in bad.asmod, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_include_requires_string() {
+    assert!(parseid::parse_source("DOŁĄCZ io").is_err());
+}
+
+#[test]
+fn test_include_errors_collected_from_every_file() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("lib.asmod"), "POB )\nSTP\n").unwrap();
+    let main = dir.path().join("main.asmod");
+
+    let errors = parse_source_file_collecting_errors("DOŁĄCZ \"lib.asmod\"\nDOD (\n", &main, &[]).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(&errors[0], ParserError::InFile { file, .. } if file.ends_with("lib.asmod")));
+    assert_eq!(errors[0].line(), Some(1));
+    assert!(matches!(&errors[1], ParserError::UnexpectedEof { .. }));
+}
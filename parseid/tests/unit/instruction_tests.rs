@@ -66,3 +66,59 @@ fn test_all_machine_w_instructions() {
     
     assert_eq!(instruction_count, 15);
 }
+
+#[test]
+fn test_subroutine_instructions() {
+    let program = parse_source(r#"
+        WYW routine
+        STP
+        routine:
+        POW
+    "#).unwrap();
+    assert_eq!(program.elements.len(), 4);
+    
+    if let ProgramElement::Instruction(inst) = &program.elements[0] {
+        assert_eq!(inst.opcode, "WYW");
+        assert_eq!(inst.operand.as_ref().unwrap().value, "routine");
+    } else {
+        panic!("Expected instruction");
+    }
+    
+    if let ProgramElement::Instruction(inst) = &program.elements[3] {
+        assert_eq!(inst.opcode, "POW");
+        assert!(inst.operand.is_none());
+    } else {
+        panic!("Expected instruction");
+    }
+}
+
+#[test]
+fn test_bitwise_instructions() {
+    let program = parse_source(r#"
+        AND #0x0F
+        NOT
+        SHL #4
+    "#).unwrap();
+    assert_eq!(program.elements.len(), 3);
+    
+    if let ProgramElement::Instruction(inst) = &program.elements[0] {
+        assert_eq!(inst.opcode, "AND");
+        assert_eq!(inst.operand.as_ref().unwrap().addressing_mode, AddressingMode::Immediate);
+    } else {
+        panic!("Expected instruction");
+    }
+    
+    if let ProgramElement::Instruction(inst) = &program.elements[1] {
+        assert_eq!(inst.opcode, "NOT");
+        assert!(inst.operand.is_none());
+    } else {
+        panic!("Expected instruction");
+    }
+    
+    if let ProgramElement::Instruction(inst) = &program.elements[2] {
+        assert_eq!(inst.opcode, "SHL");
+        assert_eq!(inst.operand.as_ref().unwrap().value, "4");
+    } else {
+        panic!("Expected instruction");
+    }
+}
@@ -1,4 +1,4 @@
-use parseid::{parse_source, ast::*};
+use parseid::{parse_source, ast::*, ParserError};
 
 #[test]
 fn test_macro_definition() {
@@ -32,3 +32,77 @@ fn test_macro_call() {
         panic!("Expected macro call");
     }
 }
+
+#[test]
+fn test_macro_calls_end_with_line() {
+    let program = parse_source(r#"
+        MAKRO twice value
+            once value
+            once value
+        KONM
+        twice 10
+        twice data
+    "#).unwrap();
+    assert_eq!(program.elements.len(), 3);
+
+    if let ProgramElement::MacroDefinition(macro_def) = &program.elements[0] {
+        assert_eq!(macro_def.parameters, vec!["value"]);
+        assert_eq!(macro_def.body.len(), 2);
+        assert!(matches!(&macro_def.body[1], ProgramElement::MacroCall(call) if call.arguments == ["value"]));
+    } else {
+        panic!("Expected macro definition");
+    }
+    assert!(matches!(&program.elements[2], ProgramElement::MacroCall(call) if call.arguments == ["data"]));
+}
+
+#[test]
+fn test_macro_call_operand_arguments() {
+    let program = parse_source("load #5, [ptr], R1, tab+1 [[x]] -2").unwrap();
+
+    if let ProgramElement::MacroCall(macro_call) = &program.elements[0] {
+        assert_eq!(macro_call.arguments, vec!["#5", "[ptr]", "R1", "tab+1", "[[x]]", "-2"]);
+        let modes: Vec<&AddressingMode> = macro_call.operands.iter().map(|operand| &operand.addressing_mode).collect();
+        assert_eq!(modes, [
+            &AddressingMode::Immediate,
+            &AddressingMode::Indirect,
+            &AddressingMode::Register,
+            &AddressingMode::Direct,
+            &AddressingMode::MultipleIndirect,
+            &AddressingMode::Relative,
+        ]);
+        assert!(macro_call.operands[3].expression.is_some());
+    } else {
+        panic!("Expected macro call");
+    }
+}
+
+#[test]
+fn test_macro_defaults_and_variadic_parameters() {
+    let program = parse_source(r#"
+        MAKRO table name, step = #1, values...
+            STP
+        KONM
+    "#).unwrap();
+
+    if let ProgramElement::MacroDefinition(macro_def) = &program.elements[0] {
+        assert_eq!(macro_def.parameters, vec!["name", "step", "values"]);
+        assert!(macro_def.defaults[0].is_none());
+        assert!(matches!(&macro_def.defaults[1], Some(operand) if operand.to_string() == "#1"));
+        assert!(macro_def.defaults[2].is_none());
+        assert!(macro_def.variadic);
+    } else {
+        panic!("Expected macro definition");
+    }
+}
+
+#[test]
+fn test_malformed_macros() {
+    let error = parse_source("load 5 )").unwrap_err();
+    assert!(matches!(error, ParserError::UnexpectedToken { ref expected, ref found, .. } if expected == "macro argument" && found == ")"));
+    assert!(parse_source("load 5,").is_err());
+
+    for definition in ["MAKRO m a = 1 b\nKONM", "MAKRO m rest... last\nKONM", "MAKRO m a a\nKONM", "MAKRO m a ) \nKONM", "MAKRO m rest.. \nKONM"] {
+        let error = parse_source(definition).unwrap_err();
+        assert!(matches!(error, ParserError::InvalidMacroDefinition { line: 1, .. }), "{}: {:?}", definition, error);
+    }
+}
@@ -23,6 +23,44 @@ pub mod addressing_mode_bits {
     pub const RELATIVE: u8 = 0b111;
 }
 
+/// lowercase name of the mode bits, as shown in listings
+pub fn addressing_mode_name(mode: u8) -> &'static str {
+    match mode & 0b111 {
+        addressing_mode_bits::DIRECT => "direct",
+        addressing_mode_bits::IMMEDIATE => "immediate",
+        addressing_mode_bits::INDIRECT => "indirect",
+        addressing_mode_bits::MULTIPLE_INDIRECT => "multiple indirect",
+        addressing_mode_bits::REGISTER => "register",
+        addressing_mode_bits::REGISTER_INDIRECT => "register indirect",
+        addressing_mode_bits::BASE_REGISTER => "base register",
+        _ => "relative",
+    }
+}
+
+/// a relative argument is a signed 8-bit offset from the word after the instruction,
+/// None when it points outside memory
+pub fn relative_target(next_address: u16, argument: u16) -> Option<u16> {
+    let target = next_address as i32 + (argument as u8 as i8) as i32;
+    (0..2048).contains(&target).then_some(target as u16)
+}
+
+/// argument reaching target from the instruction before next_address, None beyond -128..=127
+pub fn relative_argument(next_address: u16, target: u16) -> Option<u16> {
+    let offset = target as i32 - next_address as i32;
+    i8::try_from(offset).ok().map(|offset| offset as u8 as u16)
+}
+
+/// base register and indexed argument: register in bits 11-13 above an address in bits 0-10,
+/// so relocating the argument moves the address
+pub fn encode_base_register(register: u16, offset: u16) -> u16 {
+    ((register & 0b111) << 11) | (offset & 0x7FF)
+}
+
+/// (register, offset) of a base register or indexed argument
+pub fn decode_base_register(argument: u16) -> (u16, u16) {
+    ((argument >> 11) & 0b111, argument & 0x7FF)
+}
+
 pub fn is_valid_addressing_mode(mode: u8) -> bool {
     mode <= 7
 }
@@ -30,3 +68,32 @@ pub fn is_valid_addressing_mode(mode: u8) -> bool {
 pub fn is_valid_register(register: u8) -> bool {
     register <= 7
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_round_trip() {
+        for (next, target) in [(10, 13), (10, 10), (10, 2), (200, 72), (0, 127)] {
+            let argument = relative_argument(next, target).unwrap();
+            assert!(argument <= 0xFF);
+            assert_eq!(relative_target(next, argument), Some(target));
+        }
+    }
+
+    #[test]
+    fn test_base_register_round_trip() {
+        let argument = encode_base_register(5, 1000);
+        assert_eq!(decode_base_register(argument), (5, 1000));
+        assert_eq!(encode_base_register(0, 12), 12);
+    }
+
+    #[test]
+    fn test_relative_range() {
+        assert_eq!(relative_argument(200, 71), None);
+        assert_eq!(relative_argument(0, 128), None);
+        assert_eq!(relative_target(2, 0xFD), None);
+        assert_eq!(relative_target(2040, 10), None);
+    }
+}
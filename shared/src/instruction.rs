@@ -1,3 +1,5 @@
+use crate::opcodes::Opcode;
+
 /// [5 opcode bits][3 address bits][8 argument bits]
 pub fn encode_instruction(opcode: u8, addressing_mode: u8, argument: u16) -> u16 {
     let opcode_bits = (opcode as u16 & 0b11111) << 11;
@@ -7,6 +9,32 @@ pub fn encode_instruction(opcode: u8, addressing_mode: u8, argument: u16) -> u16
     opcode_bits | mode_bits | arg_bits
 }
 
+/// far form: [DAL prefix][000][high 8 argument bits] followed by the instruction with the low 8 bits
+pub fn encode_far_instruction(opcode: u8, addressing_mode: u8, argument: u16) -> [u16; 2] {
+    [
+        encode_instruction(Opcode::DAL as u8, 0, argument >> 8),
+        encode_instruction(opcode, addressing_mode, argument),
+    ]
+}
+
+/// argument does not fit in the 8 bits of a single word
+pub fn requires_far_form(argument: u16) -> bool {
+    argument > 0xFF
+}
+
+pub fn is_far_prefix(instruction: u16) -> bool {
+    extract_opcode(instruction) == Opcode::DAL as u8
+}
+
+/// prefix + instruction -> (opcode, addressing mode, full 16-bit argument)
+pub fn decode_far_instruction(prefix: u16, instruction: u16) -> (u8, u8, u16) {
+    let opcode = extract_opcode(instruction);
+    let addressing_mode = extract_addressing_mode(instruction);
+    let argument = (extract_argument(prefix) << 8) | extract_argument(instruction);
+    
+    (opcode, addressing_mode, argument)
+}
+
 pub fn decode_instruction(instruction: u16) -> (u8, u8, u16) {
     let opcode = extract_opcode(instruction);
     let addressing_mode = extract_addressing_mode(instruction);
@@ -40,6 +68,16 @@ mod tests {
         assert_eq!(instruction, (0b00001u16 << 11) | (0b000u16 << 8) | 100);
     }
 
+    #[test]
+    fn test_far_instruction_encoding() {
+        let [prefix, instruction] = encode_far_instruction(0b00100, 0b000, 1000);
+        assert!(is_far_prefix(prefix));
+        assert!(!is_far_prefix(instruction));
+        assert_eq!(prefix, (0b11111u16 << 11) | (1000 >> 8));
+        assert_eq!(instruction, (0b00100u16 << 11) | (1000 & 0xFF));
+        assert_eq!(decode_far_instruction(prefix, instruction), (0b00100, 0b000, 1000));
+    }
+
     #[test]
     fn test_instruction_decoding() {
         let instruction = (0b00001u16 << 11) | (0b000u16 << 8) | 100;
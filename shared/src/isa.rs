@@ -0,0 +1,155 @@
+//! instruction set profiles: original Machine W set plus optional extensions
+
+use crate::opcodes::Opcode;
+use std::fmt;
+use std::str::FromStr;
+
+/// optional group of opcodes on top of the original Machine W set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IsaExtension {
+    Core,      // INR, DER, WYW, POW, SOC, SOV, SOP, SNZ, DAL (far form)
+    MulDiv,    // MNO, DZI, MOD
+    Bits,      // AND, OR, XOR, NOT, SHL, SHR, SAR
+}
+
+impl IsaExtension {
+    pub const ALL: [IsaExtension; 3] = [Self::Core, Self::MulDiv, Self::Bits];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Core => "core",
+            Self::MulDiv => "muldiv",
+            Self::Bits => "bits",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|extension| extension.name().eq_ignore_ascii_case(name))
+    }
+
+    fn bit(self) -> u8 {
+        match self {
+            Self::Core => 0b001,
+            Self::MulDiv => 0b010,
+            Self::Bits => 0b100,
+        }
+    }
+}
+
+/// set of enabled extensions, written as `base(+extension)*`:
+/// - `classic-w` - original Machine W instructions only
+/// - `w` - classic-w with registers, subroutines and flag jumps (default)
+/// - `full` - every extension
+///
+/// e.g. `w+muldiv`, `classic-w+bits`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IsaProfile {
+    extensions: u8,
+}
+
+impl IsaProfile {
+    pub const CLASSIC_W: IsaProfile = IsaProfile { extensions: 0 };
+    pub const W: IsaProfile = IsaProfile { extensions: 0b001 };
+    pub const FULL: IsaProfile = IsaProfile { extensions: 0b111 };
+
+    pub fn with(self, extension: IsaExtension) -> Self {
+        Self { extensions: self.extensions | extension.bit() }
+    }
+
+    pub fn has(self, extension: IsaExtension) -> bool {
+        (self.extensions & extension.bit()) != 0
+    }
+
+    /// if every extension of other is part of this profile
+    pub fn includes(self, other: IsaProfile) -> bool {
+        (self.extensions & other.extensions) == other.extensions
+    }
+
+    /// if opcode may be used under this profile
+    pub fn allows(self, opcode: Opcode) -> bool {
+        match opcode.extension() {
+            Some(extension) => self.has(extension),
+            None => true,
+        }
+    }
+}
+
+impl Default for IsaProfile {
+    fn default() -> Self {
+        Self::W
+    }
+}
+
+impl FromStr for IsaProfile {
+    type Err = String;
+
+    /// error holds the unrecognized part of the name
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let mut parts = name.trim().split('+');
+        let base = parts.next().unwrap_or_default().trim();
+
+        let mut profile = match base.to_lowercase().as_str() {
+            "classic-w" => Self::CLASSIC_W,
+            "w" => Self::W,
+            "full" => Self::FULL,
+            _ => return Err(base.to_string()),
+        };
+
+        for part in parts {
+            let part = part.trim();
+            let extension = IsaExtension::from_name(part).ok_or_else(|| part.to_string())?;
+            profile = profile.with(extension);
+        }
+
+        Ok(profile)
+    }
+}
+
+impl fmt::Display for IsaProfile {
+    /// canonical name, parses back to the same profile
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self == Self::FULL {
+            return write!(f, "full");
+        }
+
+        write!(f, "{}", if self.has(IsaExtension::Core) { "w" } else { "classic-w" })?;
+        for extension in [IsaExtension::MulDiv, IsaExtension::Bits] {
+            if self.has(extension) {
+                write!(f, "+{}", extension.name())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_names_round_trip() {
+        for name in ["classic-w", "w", "w+muldiv", "w+bits", "classic-w+muldiv+bits", "full"] {
+            let profile: IsaProfile = name.parse().unwrap();
+            assert_eq!(profile.to_string(), name);
+        }
+
+        assert_eq!("w+muldiv+bits".parse::<IsaProfile>(), Ok(IsaProfile::FULL));
+        assert_eq!("classic-w+core".parse::<IsaProfile>(), Ok(IsaProfile::W));
+        assert_eq!("W+MulDiv".parse::<IsaProfile>(), Ok(IsaProfile::W.with(IsaExtension::MulDiv)));
+    }
+
+    #[test]
+    fn test_unknown_profile_names() {
+        assert_eq!("classic".parse::<IsaProfile>(), Err("classic".to_string()));
+        assert_eq!("w+floats".parse::<IsaProfile>(), Err("floats".to_string()));
+    }
+
+    #[test]
+    fn test_profile_includes() {
+        assert!(IsaProfile::FULL.includes(IsaProfile::W));
+        assert!(IsaProfile::W.includes(IsaProfile::CLASSIC_W));
+        assert!(!IsaProfile::CLASSIC_W.includes(IsaProfile::W));
+        assert!(!IsaProfile::W.with(IsaExtension::MulDiv).includes(IsaProfile::W.with(IsaExtension::Bits)));
+        assert!(!IsaProfile::CLASSIC_W.allows(Opcode::DAL));
+    }
+}
@@ -1,7 +1,15 @@
 pub mod addressing;
 pub mod instruction;
+pub mod isa;
 pub mod opcodes;
 
-pub use addressing::{AddressingModeBits, addressing_mode_bits};
-pub use instruction::{encode_instruction, decode_instruction, extract_opcode, extract_addressing_mode, extract_argument};
-pub use opcodes::Opcode;
+pub use addressing::{
+    AddressingModeBits, addressing_mode_bits, addressing_mode_name, relative_target, relative_argument,
+    encode_base_register, decode_base_register,
+};
+pub use instruction::{
+    encode_instruction, decode_instruction, extract_opcode, extract_addressing_mode, extract_argument,
+    encode_far_instruction, decode_far_instruction, requires_far_form, is_far_prefix,
+};
+pub use isa::{IsaProfile, IsaExtension};
+pub use opcodes::{Opcode, register_operations, shift_operations};
@@ -1,3 +1,5 @@
+use crate::isa::IsaExtension;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Opcode {
     DOD = 0b00001,    // ADD 
@@ -21,6 +23,44 @@ pub enum Opcode {
     MNO = 0b10001,    // Multiply (Extended)
     DZI = 0b10010,    // Divide (Extended)
     MOD = 0b10011,    // Modulo (Extended)
+
+    // register operations (sub-operation in argument bits 7-3, register in bits 2-0)
+    REJ = 0b10100,    // Register Operations (INR, DER)
+
+    // subroutines
+    WYW = 0b10101,    // Call Subroutine
+    POW = 0b10110,    // Return From Subroutine
+
+    // bitwise opcodes (instruction set)
+    AND = 0b10111,    // Bitwise AND (Bitwise)
+    OR = 0b11000,     // Bitwise OR (Bitwise)
+    XOR = 0b11001,    // Bitwise XOR (Bitwise)
+
+    // shift operations (sub-operation in argument bits 7-4, shift count in bits 3-0)
+    PRZ = 0b11010,    // Shift Operations (SHL, SHR, SAR, NOT) (Bitwise)
+
+    // flag conditional jumps
+    SOC = 0b11011,    // Jump if Carry
+    SOV = 0b11100,    // Jump if Overflow
+    SOP = 0b11101,    // Jump if Greater Than Zero
+    SNZ = 0b11110,    // Jump if Not Zero
+
+    // far address prefix (high argument byte for the following instruction)
+    DAL = 0b11111,    // Far Address Prefix
+}
+
+/// sub-operations of the REJ opcode
+pub mod register_operations {
+    pub const INR: u8 = 0b00000; // Increment Register
+    pub const DER: u8 = 0b00001; // Decrement Register
+}
+
+/// sub-operations of the PRZ opcode
+pub mod shift_operations {
+    pub const SHL: u8 = 0b0000; // Logical Shift Left
+    pub const SHR: u8 = 0b0001; // Logical Shift Right
+    pub const SAR: u8 = 0b0010; // Arithmetic Shift Right
+    pub const NOT: u8 = 0b0011; // Bitwise Negation
 }
 
 impl Opcode {
@@ -48,16 +88,54 @@ impl Opcode {
             0b10001 => Some(Self::MNO),    // Multiply (Extended)
             0b10010 => Some(Self::DZI),    // Divide (Extended)
             0b10011 => Some(Self::MOD),    // Modulo (Extended)
+
+            // register operations
+            0b10100 => Some(Self::REJ),
+
+            // subroutines
+            0b10101 => Some(Self::WYW),
+            0b10110 => Some(Self::POW),
+
+            // bitwise opcodes
+            0b10111 => Some(Self::AND),
+            0b11000 => Some(Self::OR),
+            0b11001 => Some(Self::XOR),
+            0b11010 => Some(Self::PRZ),
+
+            // flag conditional jumps
+            0b11011 => Some(Self::SOC),
+            0b11100 => Some(Self::SOV),
+            0b11101 => Some(Self::SOP),
+            0b11110 => Some(Self::SNZ),
+
+            // far address prefix
+            0b11111 => Some(Self::DAL),
             _ => None,
         }
     }
 
     pub fn requires_operand(self) -> bool {
-        !matches!(self, Self::STP | Self::DNS | Self::PZS | Self::SDP | Self::CZM | Self::PWR)
+        !matches!(self, Self::STP | Self::DNS | Self::PZS | Self::SDP | Self::CZM | Self::PWR | Self::WEJSCIE | Self::WYJSCIE | Self::POW)
+    }
+
+    /// instruction set extension the opcode belongs to (None for original Machine W opcodes)
+    pub fn extension(self) -> Option<IsaExtension> {
+        match self {
+            // the far prefix is an Asmodeus addition as much as the registers are
+            Self::REJ | Self::WYW | Self::POW | Self::SOC | Self::SOV | Self::SOP | Self::SNZ | Self::DAL => Some(IsaExtension::Core),
+            Self::MNO | Self::DZI | Self::MOD => Some(IsaExtension::MulDiv),
+            Self::AND | Self::OR | Self::XOR | Self::PRZ => Some(IsaExtension::Bits),
+            _ => None,
+        }
     }
 
     /// if opcode is part of extended instruction set
     pub fn is_extended(self) -> bool {
-        matches!(self, Self::MNO | Self::DZI | Self::MOD)
+        self.extension() == Some(IsaExtension::MulDiv)
+    }
+
+    /// if opcode is part of bitwise instruction set
+    pub fn is_bitwise(self) -> bool {
+        self.extension() == Some(IsaExtension::Bits)
     }
 }
@@ -26,8 +26,13 @@ fn test_extended_opcode_from_u8() {
     assert_eq!(Opcode::from_u8(0b10010), Some(Opcode::DZI));
     assert_eq!(Opcode::from_u8(0b10011), Some(Opcode::MOD));
     
-    // invalid extended opcode
-    assert_eq!(Opcode::from_u8(0b10100), None);
+    // next opcode belongs to register operations, not to the extended set
+    assert_eq!(Opcode::from_u8(0b10100), Some(Opcode::REJ));
+    assert!(!Opcode::REJ.is_extended());
+
+    // invalid opcodes
+    assert_eq!(Opcode::from_u8(0b00000), None);
+    assert_eq!(Opcode::from_u8(0b100000), None);
 }
 
 #[test]
@@ -50,3 +55,19 @@ fn test_all_extended_opcodes_enumeration() {
         assert!(opcode.requires_operand(), "Extended opcode {:?} should require operand", opcode);
     }
 }
+
+#[test]
+fn test_bitwise_opcode_identification() {
+    assert_eq!(Opcode::from_u8(0b10111), Some(Opcode::AND));
+    assert_eq!(Opcode::from_u8(0b11000), Some(Opcode::OR));
+    assert_eq!(Opcode::from_u8(0b11001), Some(Opcode::XOR));
+    assert_eq!(Opcode::from_u8(0b11010), Some(Opcode::PRZ));
+
+    for opcode in [Opcode::AND, Opcode::OR, Opcode::XOR, Opcode::PRZ] {
+        assert!(opcode.is_bitwise(), "Opcode {:?} should be marked as bitwise", opcode);
+        assert!(!opcode.is_extended(), "Opcode {:?} should not be marked as extended", opcode);
+    }
+
+    assert!(!Opcode::MNO.is_bitwise());
+    assert!(!Opcode::DOD.is_bitwise());
+}
@@ -1,15 +1,76 @@
 //! from source to machine code
 
 use lexariel::tokenize;
-use parseid::parse;
-use hephasm::{assemble_program, assemble_program_extended};
+use parseid::{parse_source_file_collecting_errors, Program, SourceMap};
+use std::path::{Path, PathBuf};
+use hephasm::{Assembler, AssemblerError, DebugInfo, ObjectFile, Segment};
+use crate::diagnostic::{Diagnostic, Sources};
+use asmodeus_shared::IsaProfile;
 
 use crate::error::AsmodeusError;
 use crate::cli::Args;
 use crate::file_utils::read_file;
 use crate::debug::{print_tokens_debug, print_ast_debug};
 
-pub fn assemble_file(input_path: &str, args: &Args) -> Result<Vec<u16>, AsmodeusError> {
+/// machine code, the instruction set profile it was assembled for (ISA directive overrides --isa),
+/// where each word came from, with the files and lines of included sources, and the segments placed by SEKCJA and ADRES
+pub fn assemble_file_with_debug_info(input_path: &str, args: &Args) -> Result<(Vec<u16>, IsaProfile, DebugInfo, Vec<Segment>), AsmodeusError> {
+    let (ast, source_map) = parse_file(input_path, args)?;
+    
+    if args.verbose {
+        println!("⚙️ Assembling AST to machine code...");
+    }
+    
+    let mut assembler = new_assembler(args);
+    let (machine_code, mut debug_info) = assembler.assemble_collecting_errors(&ast).map_err(|errors| {
+        assembler_diagnostics(errors, &source_map)
+    })?;
+    debug_info.locate_files(|line| {
+        source_map.locate(line).map(|(file, file_line)| (file.to_string(), file_line))
+    });
+    
+    if args.verbose {
+        println!("✅ Assembly completed successfully. Generated {} words.", machine_code.len());
+    }
+    
+    Ok((machine_code, assembler.profile(), debug_info, assembler.segments().to_vec()))
+}
+
+/// module to be linked with others, see asmod link
+pub fn assemble_object_file(input_path: &str, args: &Args) -> Result<ObjectFile, AsmodeusError> {
+    let (ast, source_map) = parse_file(input_path, args)?;
+    
+    if args.verbose {
+        println!("⚙️ Assembling AST to relocatable object code...");
+    }
+    
+    let object = new_assembler(args).assemble_object_collecting_errors(&ast).map_err(|errors| {
+        assembler_diagnostics(errors, &source_map)
+    })?;
+    
+    if args.verbose {
+        println!("✅ Assembly completed successfully. Generated {} words, {} relocations.", object.code.len(), object.relocations.len());
+    }
+    
+    Ok(object)
+}
+
+/// every error in the program and the files it includes, or the warnings about it once it assembles;
+/// nothing is written
+pub fn check_file(input_path: &str, args: &Args) -> Result<(Program, SourceMap, Vec<Diagnostic>), AsmodeusError> {
+    let (ast, source_map) = parse_file(input_path, args)?;
+    let mut assembler = new_assembler(args);
+    assembler.assemble_collecting_errors(&ast).map_err(|errors| {
+        assembler_diagnostics(errors, &source_map)
+    })?;
+    let mut sources = Sources::new();
+    let warnings = assembler.warnings().iter()
+        .map(|warning| Diagnostic::from_warning(warning.clone(), &source_map, &mut sources))
+        .collect();
+    Ok((ast, source_map, warnings))
+}
+
+fn parse_file(input_path: &str, args: &Args) -> Result<(Program, SourceMap), AsmodeusError> {
     if args.verbose {
         println!("📖 Reading source file: {}", input_path);
     }
@@ -20,11 +81,11 @@ pub fn assemble_file(input_path: &str, args: &Args) -> Result<Vec<u16>, Asmodeus
         println!("🔤 Tokenizing source code...");
     }
     
-    let tokens = tokenize(&source).map_err(|e| {
-        AsmodeusError::LexerError(e)
-    })?;
-    
     if args.debug {
+        // tokens of the file itself, without what it includes
+        let tokens = tokenize(&source).map_err(|e| {
+            AsmodeusError::LexerError(e)
+        })?;
         print_tokens_debug(&tokens);
     }
     
@@ -32,31 +93,41 @@ pub fn assemble_file(input_path: &str, args: &Args) -> Result<Vec<u16>, Asmodeus
         println!("🌳 Parsing tokens to AST...");
     }
     
-    let ast = parse(tokens).map_err(|e| {
-        AsmodeusError::ParserError(e)
-    })?;
+    // tokenizes and parses included files as well
+    let include_paths: Vec<PathBuf> = args.include_paths.iter().map(PathBuf::from).collect();
+    let (ast, source_map) = parse_source_file_collecting_errors(&source, Path::new(input_path), &include_paths)
+        .map_err(|errors| {
+            let mut sources = Sources::new();
+            AsmodeusError::Diagnostics(errors.into_iter().map(|e| Diagnostic::from_parser_error(e, input_path, &mut sources)).collect())
+        })?;
     
     if args.debug {
         print_ast_debug(&ast);
     }
     
-    if args.verbose {
-        println!("⚙️ Assembling AST to machine code...");
+    Ok((ast, source_map))
+}
+
+fn new_assembler(args: &Args) -> Assembler {
+    let mut assembler = Assembler::new_with_profile(args.isa);
+    if args.isa_given {
+        assembler.lock_profile();
     }
-    
-    let machine_code = if args.extended {
-        assemble_program_extended(&ast, true).map_err(|e| {
-            AsmodeusError::AssemblerError(e)
-        })?
-    } else {
-        assemble_program(&ast).map_err(|e| {
-            AsmodeusError::AssemblerError(e)
-        })?
-    };
-    
-    if args.verbose {
-        println!("✅ Assembly completed successfully. Generated {} words.", machine_code.len());
+    for (name, value) in &args.definitions {
+        assembler.define_constant(name.as_str(), *value);
     }
-    
-    Ok(machine_code)
+    for &(lint, enabled) in &args.lints {
+        if enabled {
+            assembler.warn(lint);
+        } else {
+            assembler.allow(lint);
+        }
+    }
+    assembler
+}
+
+/// errors in included files point into those files
+fn assembler_diagnostics(errors: Vec<AssemblerError>, source_map: &SourceMap) -> AsmodeusError {
+    let mut sources = Sources::new();
+    AsmodeusError::Diagnostics(errors.into_iter().map(|e| Diagnostic::from_assembler_error(e, source_map, &mut sources)).collect())
 }
@@ -1,8 +1,9 @@
 use std::fs;
-use dismael::disassemble;
+use std::path::Path;
+use dismael::{disassemble_with_profile, disassemble_with_debug_info};
 use crate::error::AsmodeusError;
 use crate::cli::Args;
-use crate::file_utils::read_binary;
+use crate::file_utils::{read_binary, read_debug_info, debug_info_path};
 
 pub fn disassemble_file(input_path: &str, args: &Args) -> Result<(), AsmodeusError> {
     if args.verbose {
@@ -15,7 +16,16 @@ pub fn disassemble_file(input_path: &str, args: &Args) -> Result<(), AsmodeusErr
         println!("Disassembling {} words of machine code...", machine_code.len());
     }
     
-    let assembly = disassemble(&machine_code)?;
+    // labels and source lines from the .dbg file written by assemble -g
+    let debug_path = debug_info_path(input_path);
+    let assembly = if Path::new(&debug_path).is_file() {
+        if args.verbose {
+            println!("Using debug info from: {}", debug_path);
+        }
+        disassemble_with_debug_info(&machine_code, args.isa, read_debug_info(&debug_path)?)?
+    } else {
+        disassemble_with_profile(&machine_code, args.isa)?
+    };
     
     let output = assembly.join("\n");
     
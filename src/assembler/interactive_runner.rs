@@ -1,14 +1,18 @@
 use asmachina::MachineW;
+use asmodeus_shared::IsaProfile;
+use hephasm::DebugInfo;
+use crate::diagnostic::{Diagnostic, Sources};
 use crate::error::AsmodeusError;
 use crate::cli::Args;
 
-pub fn run_interactive_program(machine_code: &[u16], args: &Args) -> Result<(), AsmodeusError> {
+pub fn run_interactive_program(machine_code: &[u16], isa: IsaProfile, debug_info: &DebugInfo, args: &Args) -> Result<(), AsmodeusError> {
     println!("🔤 Asmodeus Interactive Mode");
     println!("Character-based I/O enabled - type characters for real-time processing");
     println!("Press Ctrl+C to interrupt\n");
     
     let mut machine = MachineW::new();
     machine.set_interactive_mode(true);
+    machine.set_isa_profile(isa);
     machine.load_program(machine_code)?;
     
     if args.verbose {
@@ -30,7 +34,8 @@ pub fn run_interactive_program(machine_code: &[u16], args: &Args) -> Result<(),
         }
         Err(e) => {
             eprintln!("\n❌ Execution error: {}", e);
-            return Err(AsmodeusError::MachineError(e));
+            let diagnostic = Diagnostic::from_machine_error(e, machine.instruction_start, debug_info, &mut Sources::new());
+            return Err(AsmodeusError::Diagnostics(vec![diagnostic]));
         }
     }
     
@@ -0,0 +1,205 @@
+//! assembly listing: addresses, encoded words and source lines side by side
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use asmodeus_shared::{addressing_mode_name, decode_far_instruction, decode_instruction, is_far_prefix, Opcode};
+use hephasm::{DebugInfo, DebugSymbol, SourceLocation, SymbolType, WordKind};
+
+/// data words per row, and rows shown before the rest of a long RST/RPA is left out
+const WORDS_PER_ROW: usize = 2;
+const MAX_DATA_ROWS: usize = 4;
+
+/// listing of a program assembled from input_path, followed by its symbol table and cross-reference
+pub fn format_listing(machine_code: &[u16], debug_info: &DebugInfo, input_path: &str) -> String {
+    let mut listing = Listing {
+        machine_code,
+        debug_info,
+        input_path,
+        sources: HashMap::new(),
+        output: String::new(),
+    };
+    listing.write_code();
+    listing.write_symbols();
+    listing.write_cross_reference();
+    listing.output
+}
+
+struct Listing<'a> {
+    machine_code: &'a [u16],
+    debug_info: &'a DebugInfo,
+    input_path: &'a str,
+    /// lines of every file read so far
+    sources: HashMap<String, Vec<String>>,
+    output: String,
+}
+
+impl Listing<'_> {
+    fn write_code(&mut self) {
+        self.output.push_str(&format!("Asmodeus listing: {}\n\n", self.input_path));
+        self.output.push_str(&format!("{:<4}  {:<9}  {:<24}  {:>6}    {}\n", "ADDR", "CODE", "MODE", "LINE", "SOURCE"));
+
+        let words = &self.debug_info.words;
+        let mut address = 0;
+        while address < words.len().min(self.machine_code.len()) {
+            // nothing placed between sections
+            let Some(location) = &words[address] else {
+                address += 1;
+                continue;
+            };
+            // words of one source element
+            let end = (address..words.len()).find(|&next| words[next].as_ref() != Some(location)).unwrap_or(words.len());
+
+            self.write_labels(address as u16, Some(location));
+            let source = self.source_column(location);
+            match location.kind {
+                WordKind::Instruction => self.write_instructions(address, end, &source),
+                WordKind::Data => self.write_data(address, end, &source),
+            }
+            address = end;
+        }
+
+        // labels after the last word, e.g. end: at the end of the program
+        let mut trailing: Vec<u16> = labels(self.debug_info).map(|symbol| symbol.value as u16)
+            .filter(|&value| value as usize >= address)
+            .collect();
+        trailing.dedup();
+        for value in trailing {
+            self.write_labels(value, None);
+        }
+    }
+
+    fn write_instructions(&mut self, start: usize, end: usize, source: &str) {
+        let mut address = start;
+        while address < end {
+            let word = self.machine_code[address];
+            let (code, (opcode, mode, _), far) = if is_far_prefix(word) && address + 1 < end {
+                let instruction = self.machine_code[address + 1];
+                (format!("{:04X} {:04X}", word, instruction), decode_far_instruction(word, instruction), true)
+            } else {
+                (format!("{:04X}", word), decode_instruction(word), false)
+            };
+
+            let source = if address == start { source } else { "" };
+            self.row(format!("{:04X}  {:<9}  {:<24}  {}", address, code, Self::mode_column(opcode, mode, far), source));
+            address += if far { 2 } else { 1 };
+        }
+    }
+
+    fn write_data(&mut self, start: usize, end: usize, source: &str) {
+        let rows: Vec<&[u16]> = self.machine_code[start..end].chunks(WORDS_PER_ROW).collect();
+        for (row, chunk) in rows.iter().enumerate().take(MAX_DATA_ROWS) {
+            let code = chunk.iter().map(|word| format!("{:04X}", word)).collect::<Vec<_>>().join(" ");
+            let source = if row == 0 { source } else { "" };
+            self.row(format!("{:04X}  {:<9}  {:<24}  {}", start + row * WORDS_PER_ROW, code, "data", source));
+        }
+        if rows.len() > MAX_DATA_ROWS {
+            let shown = MAX_DATA_ROWS * WORDS_PER_ROW;
+            self.row(format!("{:04X}  {:<9}  {:<24}  ({} more words)", start + shown, "....", "", end - start - shown));
+        }
+    }
+
+    /// labels at an address, unless they are on the line shown next to it
+    fn write_labels(&mut self, address: u16, location: Option<&SourceLocation>) {
+        let labels: Vec<&DebugSymbol> = labels(self.debug_info)
+            .filter(|symbol| symbol.value == address as i32)
+            .filter(|symbol| location.is_none_or(|location| {
+                symbol.line != Some(location.line) || symbol.file != location.file
+            }))
+            .collect();
+        for symbol in labels {
+            let line = symbol.line.map(|line| self.line_reference(symbol.file.as_deref(), line)).unwrap_or_default();
+            self.row(format!("{:<4}  {:<9}  {:<24}  {:>6}    {}:", "", "", "", line, symbol.name));
+        }
+    }
+
+    fn row(&mut self, row: String) {
+        self.output.push_str(row.trim_end());
+        self.output.push('\n');
+    }
+
+    /// line number, + for every macro the line was expanded from, and the source text
+    fn source_column(&mut self, location: &SourceLocation) -> String {
+        let depth = location.expansion.map_or(0, |expansion| self.debug_info.macro_chain(expansion).len());
+        let line = self.line_reference(location.file.as_deref(), location.line);
+        let text = self.source_line(location.file.as_deref(), location.line);
+        format!("{:>6}{:<3} {}", line, "+".repeat(depth), text.trim_end())
+    }
+
+    fn source_line(&mut self, file: Option<&str>, line: usize) -> String {
+        let path = file.unwrap_or(self.input_path).to_string();
+        let lines = self.sources.entry(path.clone()).or_insert_with(|| {
+            fs::read_to_string(&path).map(|source| source.lines().map(String::from).collect()).unwrap_or_default()
+        });
+        lines.get(line.wrapping_sub(1)).cloned().unwrap_or_default()
+    }
+
+    /// 12 in the listed file, io.asmod:12 in an included one
+    fn line_reference(&self, file: Option<&str>, line: usize) -> String {
+        match file.filter(|file| *file != self.input_path) {
+            Some(file) => {
+                let name = Path::new(file).file_name().map_or(file.into(), |name| name.to_string_lossy());
+                format!("{}:{}", name, line)
+            }
+            None => line.to_string(),
+        }
+    }
+
+    /// addressing mode of an instruction, - when the argument bits are not an address
+    fn mode_column(opcode: u8, mode: u8, far: bool) -> String {
+        let name = match Opcode::from_u8(opcode) {
+            Some(Opcode::REJ | Opcode::PRZ) => "-",
+            Some(opcode) if opcode.requires_operand() => addressing_mode_name(mode),
+            Some(_) => "-",
+            None => "?",
+        };
+        if far {
+            format!("{} (far)", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn write_symbols(&mut self) {
+        self.output.push_str("\nSymbol table:\n");
+        self.output.push_str(&format!("{:<24}  {:<9}  {:<14}  {}\n", "NAME", "TYPE", "VALUE", "DEFINED"));
+        for symbol in &self.debug_info.symbols {
+            let symbol_type = match symbol.symbol_type {
+                SymbolType::Label => "label",
+                SymbolType::Variable => "variable",
+                SymbolType::Constant => "constant",
+                SymbolType::External => "external",
+            };
+            let value = format!("{:04X} ({})", symbol.value as u16, symbol.value);
+            self.output.push_str(&format!("{:<24}  {:<9}  {:<14}  {}\n", symbol.name, symbol_type, value, self.definition(symbol)));
+        }
+    }
+
+    fn write_cross_reference(&mut self) {
+        self.output.push_str("\nCross-reference:\n");
+        self.output.push_str(&format!("{:<24}  {:<14}  {}\n", "NAME", "DEFINED", "REFERENCED"));
+        for symbol in &self.debug_info.symbols {
+            let mut references: Vec<String> = Vec::new();
+            for reference in self.debug_info.references.iter().filter(|reference| reference.name == symbol.name) {
+                let line = self.line_reference(reference.file.as_deref(), reference.line);
+                if !references.contains(&line) {
+                    references.push(line);
+                }
+            }
+            let references = if references.is_empty() { "-".to_string() } else { references.join(", ") };
+            self.output.push_str(&format!("{:<24}  {:<14}  {}\n", symbol.name, self.definition(symbol), references));
+        }
+    }
+
+    /// line of the definition, -D for constants given on the command line
+    fn definition(&self, symbol: &DebugSymbol) -> String {
+        match symbol.line {
+            Some(line) => self.line_reference(symbol.file.as_deref(), line),
+            None => "-D".to_string(),
+        }
+    }
+}
+
+fn labels(debug_info: &DebugInfo) -> impl Iterator<Item = &DebugSymbol> {
+    debug_info.symbols.iter().filter(|symbol| symbol.symbol_type == SymbolType::Label)
+}
@@ -4,8 +4,10 @@ mod assembly_pipeline;
 mod program_runner;
 mod disassembler;
 mod interactive_runner;
+mod listing;
 
-pub use assembly_pipeline::assemble_file;
+pub use assembly_pipeline::{assemble_file_with_debug_info, assemble_object_file, check_file};
 pub use program_runner::run_program;
 pub use disassembler::disassemble_file;
 pub use interactive_runner::run_interactive_program;
+pub use listing::format_listing;
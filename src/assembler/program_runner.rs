@@ -1,15 +1,20 @@
 use asmachina::MachineW;
+use asmodeus_shared::IsaProfile;
+use hephasm::DebugInfo;
+use crate::diagnostic::{Diagnostic, Sources};
 use crate::error::AsmodeusError;
 use crate::cli::Args;
 use crate::debug::{print_machine_state, print_program_output};
 use crate::ascii_art::{print_success, print_info};
 
-pub fn run_program(machine_code: &[u16], args: &Args) -> Result<(), AsmodeusError> {
+/// runtime errors point at the line of the failing instruction
+pub fn run_program(machine_code: &[u16], isa: IsaProfile, debug_info: &DebugInfo, args: &Args) -> Result<(), AsmodeusError> {
     if args.verbose {
         print_info("Creating Asmachina emulator...");
     }
     
     let mut machine = MachineW::new();
+    machine.set_isa_profile(isa);
     
     if args.verbose {
         print_info("Loading program into memory...");
@@ -23,7 +28,7 @@ pub fn run_program(machine_code: &[u16], args: &Args) -> Result<(), AsmodeusErro
     }
     
     machine.run().map_err(|e| {
-        AsmodeusError::MachineError(e)
+        AsmodeusError::Diagnostics(vec![Diagnostic::from_machine_error(e, machine.instruction_start, debug_info, &mut Sources::new())])
     })?;
     
     print_success("Program execution completed successfully.");
@@ -1,17 +1,21 @@
 use asmachina::{MachineW, MachineError};
+use hephasm::DebugInfo;
+use std::fs;
 use crate::error::AsmodeusError;
 use crate::debug::print_machine_state;
 use super::address_parser::parse_address;
 
-pub fn handle_step(machine: &mut MachineW) -> Result<(), AsmodeusError> {
+pub fn handle_step(machine: &mut MachineW, debug_info: &DebugInfo) -> Result<(), AsmodeusError> {
     match machine.step_instruction() {
         Ok(()) => {
             println!("Step executed.");
             print_machine_state(machine);
+            print_source_location(machine, debug_info);
         }
         Err(MachineError::BreakpointHit { address }) => {
             println!("Breakpoint hit at address {}!", address);
             print_machine_state(machine);
+            print_source_location(machine, debug_info);
         }
         Err(e) => {
             println!("Execution error: {}", e);
@@ -20,19 +24,91 @@ pub fn handle_step(machine: &mut MachineW) -> Result<(), AsmodeusError> {
     Ok(())
 }
 
-pub fn handle_next(machine: &mut MachineW) -> Result<(), AsmodeusError> {
-    handle_step(machine)
+pub fn handle_next(machine: &mut MachineW, debug_info: &DebugInfo) -> Result<(), AsmodeusError> {
+    match machine.step_over() {
+        Ok(()) => {
+            println!("Step executed.");
+            print_machine_state(machine);
+            print_source_location(machine, debug_info);
+        }
+        Err(MachineError::BreakpointHit { address }) => {
+            println!("Breakpoint hit at address {}!", address);
+            print_machine_state(machine);
+            print_source_location(machine, debug_info);
+        }
+        Err(e) => {
+            println!("Execution error: {}", e);
+        }
+    }
+    Ok(())
+}
+
+pub fn handle_finish(machine: &mut MachineW, debug_info: &DebugInfo) -> Result<(), AsmodeusError> {
+    match machine.step_out() {
+        Ok(()) => {
+            println!("Returned to address {}.", machine.l);
+            print_machine_state(machine);
+            print_source_location(machine, debug_info);
+        }
+        Err(MachineError::BreakpointHit { address }) => {
+            println!("Breakpoint hit at address {}!", address);
+            print_machine_state(machine);
+            print_source_location(machine, debug_info);
+        }
+        Err(e) => {
+            println!("Execution error: {}", e);
+        }
+    }
+    Ok(())
+}
+
+pub fn handle_backtrace(machine: &MachineW, debug_info: &DebugInfo) {
+    let call_stack = machine.get_call_stack();
+    if call_stack.is_empty() {
+        println!("No active subroutine calls.");
+    } else {
+        println!("Call stack (innermost first):");
+        for (depth, frame) in call_stack.iter().rev().enumerate() {
+            match debug_info.label_at(frame.target) {
+                Some(label) => println!("  #{} {:04} {} (returns to {:04})", depth, frame.target, label, frame.return_address),
+                None => println!("  #{} {:04} (returns to {:04})", depth, frame.target, frame.return_address),
+            }
+        }
+    }
+}
+
+/// source line of the next instruction, with the label at it and the macro it came from
+pub fn print_source_location(machine: &MachineW, debug_info: &DebugInfo) {
+    let Some(location) = debug_info.location(machine.l) else {
+        return;
+    };
+
+    let mut header = format!("At {}", location);
+    if let Some(label) = debug_info.label_at(machine.l) {
+        header.push_str(&format!(" ({})", label));
+    }
+    if let Some(expansion) = location.expansion {
+        header.push_str(&format!(" in macro {}", debug_info.macro_trace(expansion)));
+    }
+    println!("{}", header);
+
+    let source = location.file.as_ref().and_then(|file| fs::read_to_string(file).ok());
+    if let Some(text) = source.as_deref().and_then(|source| source.lines().nth(location.line.saturating_sub(1))) {
+        println!("  {:>4} | {}", location.line, text.trim_end());
+    }
 }
 
-pub fn handle_continue(machine: &mut MachineW) -> Result<(), AsmodeusError> {
+pub fn handle_continue(machine: &mut MachineW, debug_info: &DebugInfo) -> Result<(), AsmodeusError> {
     match machine.run_until_halt_or_breakpoint() {
         Ok(()) => {
             println!("Program completed successfully.");
             print_machine_state(machine);
+            print_source_location(machine, debug_info);
         }
         Err(MachineError::BreakpointHit { address }) => {
             println!("Breakpoint hit at address {}!", address);
             print_machine_state(machine);
+            print_source_location(machine, debug_info);
         }
         Err(e) => {
             println!("Execution error: {}", e);
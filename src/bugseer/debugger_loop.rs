@@ -1,10 +1,11 @@
 use std::io::{self, Write};
 use asmachina::{MachineW};
+use hephasm::DebugInfo;
 use crate::error::AsmodeusError;
 use crate::debug::print_machine_state;
 use super::{command_handlers, help};
 
-pub fn interactive_debugger_loop(machine: &mut MachineW) -> Result<(), AsmodeusError> {
+pub fn interactive_debugger_loop(machine: &mut MachineW, debug_info: &DebugInfo) -> Result<(), AsmodeusError> {
     loop {
         print!("(bugseer)> ");
         io::stdout().flush().unwrap();
@@ -27,10 +28,14 @@ pub fn interactive_debugger_loop(machine: &mut MachineW) -> Result<(), AsmodeusE
         
         match command {
             "h" | "help" => help::print_debugger_help(),
-            "s" | "step" => command_handlers::handle_step(machine)?,
-            "n" | "next" => command_handlers::handle_next(machine)?,
-            "c" | "continue" => command_handlers::handle_continue(machine)?,
-            "d" | "display" => print_machine_state(machine),
+            "s" | "step" => command_handlers::handle_step(machine, debug_info)?,
+            "n" | "next" => command_handlers::handle_next(machine, debug_info)?,
+            "f" | "finish" => command_handlers::handle_finish(machine, debug_info)?,
+            "c" | "continue" => command_handlers::handle_continue(machine, debug_info)?,
+            "d" | "display" => {
+                print_machine_state(machine);
+                command_handlers::print_source_location(machine, debug_info);
+            }
             "q" | "quit" => {
                 println!("Bugseer debugger terminated.");
                 break;
@@ -39,6 +44,7 @@ pub fn interactive_debugger_loop(machine: &mut MachineW) -> Result<(), AsmodeusE
             "rb" | "remove-breakpoint" => command_handlers::handle_remove_breakpoint(machine, &parts)?,
             "lb" | "list-breakpoints" => command_handlers::handle_list_breakpoints(machine),
             "m" | "memory" => command_handlers::handle_memory_dump(machine, &parts)?,
+            "bt" | "backtrace" => command_handlers::handle_backtrace(machine, debug_info),
             _ => println!("Unknown command: '{}'. Type 'h' for help.", command),
         }
         
@@ -3,12 +3,14 @@ pub fn print_debugger_help() {
     println!();
     println!("  EXECUTION:");
     println!("    s, step           - Execute one instruction");
-    println!("    n, next           - Alias for step (same functionality)");
+    println!("    n, next           - Execute one instruction, stepping over subroutine calls");
+    println!("    f, finish         - Run until the current subroutine returns");
     println!("    c, continue       - Continue execution until halt or breakpoint");
     println!();
     println!("  INSPECTION:");
     println!("    d, display        - Display current machine state");
     println!("    m <start> [end]   - Memory dump (16 words from start, or range)");
+    println!("    bt, backtrace     - Show active subroutine calls");
     println!();
     println!("  BREAKPOINTS:");
     println!("    b <addr>          - Set breakpoint at address (decimal or hex)");
@@ -6,3 +6,4 @@ mod help;
 mod address_parser;
 
 pub use debugger_loop::interactive_debugger_loop;
+pub use command_handlers::print_source_location;
@@ -1,5 +1,8 @@
 //! CLI argument structures and modes
 
+use asmodeus_shared::IsaProfile;
+use hephasm::Lint;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Mode {
     Assemble,
@@ -11,9 +14,28 @@ pub enum Mode {
     New,
     Check,
     Format,
+    Link,
     Help,
 }
 
+/// --image, how assemble and link lay out the binary
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    /// ASMS header, then the load address, length and words of every segment
+    Sparse,
+    /// all 2048 words of memory
+    Full,
+}
+
+/// --message-format, how errors are reported
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MessageFormat {
+    /// the source line with the culprit underlined
+    Human,
+    /// one JSON object per line, for editors and graders
+    Json,
+}
+
 #[derive(Debug, Clone)]
 pub struct Args {
     pub mode: Mode,
@@ -21,6 +43,24 @@ pub struct Args {
     pub output_file: Option<String>,
     pub verbose: bool,
     pub debug: bool,
-    pub extended: bool,
+    pub isa: IsaProfile,
+    /// --isa was given, an ISA directive in the source may then only narrow the profile
+    pub isa_given: bool,
+    pub include_paths: Vec<String>,
+    /// -D NAME=value, in the order given
+    pub definitions: Vec<(String, i32)>,
+    /// assemble writes a .dbg file next to the binary
+    pub debug_info: bool,
+    /// assemble prints a listing, or writes a .lst file next to the binary
+    pub listing: bool,
+    /// assemble writes a relocatable object file instead of a binary
+    pub object: bool,
+    /// link: object files, placed in memory in this order
+    pub object_files: Vec<String>,
+    /// None writes the words from address 0 to the end of the program
+    pub image: Option<ImageFormat>,
     pub watch: bool,
+    pub message_format: MessageFormat,
+    /// -W lint (true) and -A lint (false), in the order given, later ones winning
+    pub lints: Vec<(Lint, bool)>,
 }
@@ -1,9 +1,14 @@
 use std::env;
-use super::{Args, Mode};
+use super::{Args, Mode, ImageFormat, MessageFormat};
 use crate::error::AsmodeusError;
+use asmodeus_shared::{IsaProfile, IsaExtension};
+use hephasm::Lint;
+
+/// long options taking a value, which can also be written --option=value
+const VALUE_OPTIONS: [&str; 8] = ["--output", "--isa", "--include", "--define", "--image", "--message-format", "--warn", "--allow"];
 
 pub fn parse_args() -> Result<Args, AsmodeusError> {
-    let args: Vec<String> = env::args().collect();
+    let args = split_option_values(env::args().collect());
     
     if args.len() < 2 {
         return Err(AsmodeusError::UsageError("No arguments provided".to_string()));
@@ -14,8 +19,20 @@ pub fn parse_args() -> Result<Args, AsmodeusError> {
     let mut output_file = None;
     let mut verbose = false;
     let mut debug = false;
+    let mut isa = IsaProfile::default();
+    let mut isa_given = false;
+    let mut include_paths = Vec::new();
+    let mut definitions = Vec::new();
+    let mut debug_info = false;
+    let mut listing = false;
+    let mut object = false;
+    let mut object_files = Vec::new();
+    let mut image = None;
     let mut extended = false;
+    let mut bits = false;
     let mut watch = false;
+    let mut message_format = MessageFormat::Human;
+    let mut lints = Vec::new();
     
     let mut i;
     
@@ -68,6 +85,10 @@ pub fn parse_args() -> Result<Args, AsmodeusError> {
             mode = Mode::Format;
             i = 2;
         }
+        Some("link") => {
+            mode = Mode::Link;
+            i = 2;
+        }
         Some("--help") | Some("-h") => {
             mode = Mode::Help;
             i = 2;
@@ -100,13 +121,68 @@ pub fn parse_args() -> Result<Args, AsmodeusError> {
                     return Err(AsmodeusError::UsageError("Missing output file".to_string()));
                 }
             }
+            "--isa" => {
+                i += 1;
+                if i < args.len() {
+                    isa = args[i].parse().map_err(|name| {
+                        AsmodeusError::UsageError(format!("Unknown instruction set profile: {}", name))
+                    })?;
+                    isa_given = true;
+                } else {
+                    return Err(AsmodeusError::UsageError("Missing instruction set profile".to_string()));
+                }
+            }
+            "--include" | "-I" => {
+                i += 1;
+                if i < args.len() {
+                    include_paths.push(args[i].clone());
+                } else {
+                    return Err(AsmodeusError::UsageError("Missing include directory".to_string()));
+                }
+            }
+            arg if arg.starts_with("-I") => include_paths.push(arg[2..].to_string()),
+            "--define" | "-D" => {
+                i += 1;
+                if i < args.len() {
+                    definitions.push(parse_definition(&args[i])?);
+                } else {
+                    return Err(AsmodeusError::UsageError("Missing definition".to_string()));
+                }
+            }
+            arg if arg.starts_with("-D") => definitions.push(parse_definition(&arg[2..])?),
+            "--image" => {
+                i += 1;
+                image = Some(match args.get(i).map(|s| s.as_str()) {
+                    Some("sparse") => ImageFormat::Sparse,
+                    Some("full") => ImageFormat::Full,
+                    Some(other) => return Err(AsmodeusError::UsageError(format!("Unknown image format: {} (expected sparse or full)", other))),
+                    None => return Err(AsmodeusError::UsageError("Missing image format".to_string())),
+                });
+            }
+            "--message-format" => {
+                i += 1;
+                message_format = parse_message_format(args.get(i).map(|s| s.as_str()))?;
+            }
+            "--warn" | "-W" | "--allow" | "-A" => {
+                let enabled = matches!(args[i].as_str(), "--warn" | "-W");
+                i += 1;
+                let names = args.get(i).ok_or_else(|| AsmodeusError::UsageError("Missing lint name".to_string()))?;
+                lints.extend(parse_lints(names)?.into_iter().map(|lint| (lint, enabled)));
+            }
+            arg if arg.starts_with("-W") => lints.extend(parse_lints(&arg[2..])?.into_iter().map(|lint| (lint, true))),
+            arg if arg.starts_with("-A") => lints.extend(parse_lints(&arg[2..])?.into_iter().map(|lint| (lint, false))),
+            "--debug-info" | "-g" => debug_info = true,
+            "--listing" | "-l" => listing = true,
+            "--object" | "-c" => object = true,
             "--verbose" | "-v" => verbose = true,
             "--debug-mode" => debug = true,
             "--extended" | "-e" => extended = true,
+            "--bits" | "-b" => bits = true,
             "--watch" | "-w" => watch = true,
             arg if arg.starts_with('-') => {
                 return Err(AsmodeusError::UsageError(format!("Unknown option: {}", arg)));
             }
+            _ if mode == Mode::Link => object_files.push(args[i].clone()),
             _ => {
                 if input_file.is_none() {
                     input_file = Some(args[i].clone());
@@ -118,13 +194,100 @@ pub fn parse_args() -> Result<Args, AsmodeusError> {
         i += 1;
     }
 
+    // --extended and --bits add to the selected profile
+    if extended {
+        isa = isa.with(IsaExtension::MulDiv);
+    }
+    if bits {
+        isa = isa.with(IsaExtension::Bits);
+    }
+
     Ok(Args {
         mode,
         input_file,
         output_file,
         verbose,
         debug,
-        extended,
+        isa,
+        isa_given,
+        include_paths,
+        definitions,
+        debug_info,
+        listing,
+        object,
+        object_files,
+        image,
         watch,
+        message_format,
+        lints,
     })
 }
+
+/// --isa=full as --isa full
+fn split_option_values(args: Vec<String>) -> Vec<String> {
+    args.into_iter()
+        .flat_map(|arg| match arg.split_once('=') {
+            Some((option, value)) if VALUE_OPTIONS.contains(&option) => vec![option.to_string(), value.to_string()],
+            _ => vec![arg],
+        })
+        .collect()
+}
+
+/// --message-format asked for on the command line, for reporting the errors that keep parse_args from finishing
+pub fn requested_message_format() -> MessageFormat {
+    let args = split_option_values(env::args().collect());
+    let json = args.windows(2).any(|pair| pair[0] == "--message-format" && pair[1] == "json");
+    if json { MessageFormat::Json } else { MessageFormat::Human }
+}
+
+/// NAME=value or NAME (= 1), value in decimal, 0x hex or 0b binary
+fn parse_definition(definition: &str) -> Result<(String, i32), AsmodeusError> {
+    let (name, value) = definition.split_once('=').unwrap_or((definition, "1"));
+    let name = name.trim();
+    let valid_name = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if !valid_name {
+        return Err(AsmodeusError::UsageError(format!("Invalid definition name: {}", definition)));
+    }
+
+    let value = value.trim();
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let parsed = if let Some(hex) = digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+        i32::from_str_radix(hex, 16)
+    } else if let Some(binary) = digits.strip_prefix("0b").or_else(|| digits.strip_prefix("0B")) {
+        i32::from_str_radix(binary, 2)
+    } else {
+        digits.parse::<i32>()
+    };
+
+    match parsed.map(|magnitude| if negative { -magnitude } else { magnitude }) {
+        Ok(value) if (i16::MIN as i32..=u16::MAX as i32).contains(&value) => Ok((name.to_string(), value)),
+        _ => Err(AsmodeusError::UsageError(format!("Invalid value in definition: {}", definition))),
+    }
+}
+
+fn parse_message_format(format: Option<&str>) -> Result<MessageFormat, AsmodeusError> {
+    match format {
+        Some("human") => Ok(MessageFormat::Human),
+        Some("json") => Ok(MessageFormat::Json),
+        Some(other) => Err(AsmodeusError::UsageError(format!("Unknown message format: {} (expected human or json)", other))),
+        None => Err(AsmodeusError::UsageError("Missing message format".to_string())),
+    }
+}
+
+/// one lint by name, or all of them
+fn parse_lints(name: &str) -> Result<Vec<Lint>, AsmodeusError> {
+    if name.eq_ignore_ascii_case("all") {
+        return Ok(Lint::ALL.to_vec());
+    }
+    match Lint::from_name(name) {
+        Some(lint) => Ok(vec![lint]),
+        None => {
+            let names: Vec<&str> = Lint::ALL.iter().map(|lint| lint.name()).collect();
+            Err(AsmodeusError::UsageError(format!("Unknown lint: {} (expected all or one of {})", name, names.join(", "))))
+        }
+    }
+}
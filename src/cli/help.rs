@@ -16,16 +16,28 @@ pub fn print_help() {
     print_command("live", "Alias for interactive mode");
     print_command("examples", "Manage and run example programs"); 
     print_command("new", "Create new project from template");
-    print_command("check", "Check syntax without assembling");
+    print_command("check", "Report every error without writing output");
     print_command("format", "Format source code for readability");
+    print_command("link", "Link object files into one binary");
     println!();
     
     println!("OPTIONS:");
     print_command("-o, --output", "Specify output file");
     print_command("-v, --verbose", "Verbose output");
     print_command("--debug", "Debug output");
-    print_command("-e, --extended", "Enable extended instruction set");
+    print_command("--isa PROFILE", "Instruction set profile (classic-w, w, full, e.g. w+muldiv)");
+    print_command("-e, --extended", "Enable extended instruction set (+muldiv)");
+    print_command("-b, --bits", "Enable bitwise instruction set (+bits)");
+    print_command("-I, --include DIR", "Also search DIR for files named by DOŁĄCZ");
+    print_command("-D, --define NAME=VALUE", "Define constant NAME for JEŻELI (overrides STAŁA NAME)");
+    print_command("-g, --debug-info", "Write source lines and labels to a .dbg file next to the binary");
+    print_command("-l, --listing", "Print an assembly listing (written to a .lst file with -o)");
+    print_command("--image FORMAT", "Write a sparse image with load addresses or a full 2048-word one");
+    print_command("-c, --object", "Assemble to a relocatable object file (.o) for linking");
     print_command("-w, --watch", "Watch file for changes and auto-rerun");
+    print_command("--message-format FORMAT", "Report errors as human-readable text or one JSON object per line");
+    print_command("-W, --warn LINT", "Report warnings of LINT in check (or all of them)");
+    print_command("-A, --allow LINT", "Do not report warnings of LINT in check (or any)");
     print_command("-h, --help", "Show this help message");
     println!();
     
@@ -34,16 +46,27 @@ pub fn print_help() {
     print_command("asmod run program.asmod", "# Run assembly program");
     print_command("asmod run --debug program.asmod", "# Run with debug output");
     print_command("asmod run --extended program.asmod", "# Run with extended instruction set");
+    print_command("asmod run --bits program.asmod", "# Run with bitwise instruction set");
+    print_command("asmod run --isa classic-w program.asmod", "# Original Machine W instructions only");
     print_command("asmod run --watch program.asmod", "# Watch file and auto-rerun on changes");
+    print_command("asmod run -I lib program.asmod", "# Look for included files in lib/");
+    print_command("asmod run -D EXTENDED=1 -e program.asmod", "# Assemble the JEŻELI EXTENDED branches");
     print_command("asmod debug program.asmod", "# Interactive debugger");
     print_command("asmod interactive char_io.asmod", "# Real-time character I/O");
     print_command("asmod assemble program.asmod", "# Assemble to binary");
-    print_command("asmod disassemble program.bin", "# Disassemble binary");
+    print_command("asmod assemble -g -o program.bin program.asmod", "# Also write program.dbg");
+    print_command("asmod assemble -l program.asmod", "# Listing with symbol table and cross-reference");
+    print_command("asmod assemble --image full -o program.bin program.asmod", "# Binary of the whole memory");
+    print_command("asmod assemble -c lib.asmod", "# Assemble a module to lib.o");
+    print_command("asmod link main.o lib.o -o program.bin", "# Link modules, main.o placed first");
+    print_command("asmod disassemble program.bin", "# Disassemble binary (uses program.dbg if present)");
     print_command("asmod examples list", "# List all available examples");
     print_command("asmod examples run hello", "# Run hello example");
     print_command("asmod new hello", "# Create new hello world project");
     print_command("asmod new calc --template calc", "# Create calculator from template");
     print_command("asmod check program.asmod", "# Quick syntax check");
+    print_command("asmod check --message-format=json program.asmod", "# Errors with codes, for editors");
+    print_command("asmod check -W self-modifying-code program.asmod", "# Also report ŁAD into code");
     print_command("asmod format program.asmod", "# Format assembly code");
     print_command("asmod format -o clean.asmod program.asmod", "# Format to specific output file");
     println!();
@@ -51,5 +74,6 @@ pub fn print_help() {
     println!("SUPPORTED FILE EXTENSIONS:");
     print_info(".asmod    Asmodeus assembly source files");
     print_info(".bin      Binary machine code files (with valid Asmodeus syntax)");
+    print_info(".o        Relocatable object files, linked with asmod link");
     println!();
 }
@@ -4,6 +4,6 @@ mod args;
 mod commands;
 mod help;
 
-pub use args::{Args, Mode};
-pub use commands::parse_args;
+pub use args::{Args, Mode, ImageFormat, MessageFormat};
+pub use commands::{parse_args, requested_message_format};
 pub use help::print_help;
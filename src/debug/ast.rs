@@ -52,6 +52,10 @@ pub fn print_ast_debug(ast: &Program) {
                 println!("MacroCall(...)");
                 print!("        ");
             }
+            ProgramElement::Conditional(_) => {
+                println!("Conditional(...)");
+                print!("        ");
+            }
         }
         if i < ast.elements.len() - 1 {
             println!(",");
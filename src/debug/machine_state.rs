@@ -1,4 +1,4 @@
-use asmachina::MachineW;
+use asmachina::{MachineW, flags};
 
 pub fn print_machine_state(machine: &MachineW) {
     let state = machine.get_current_state();
@@ -10,17 +10,33 @@ pub fn print_machine_state(machine: &MachineW) {
                          state.ak, state.ak, state.l, state.l, state.ad, state.ad);
     let kod_ws_line = format!("KOD: {:02X} ({})      WS: {:04X} ({})", 
                              state.kod, state.kod, state.ws, state.ws);
+    let flag_bits = [("Z", flags::Z), ("N", flags::N), ("C", flags::C), ("V", flags::V)];
+    let flags_line = format!("Flags: {}",
+                            flag_bits.iter()
+                                .map(|(name, bit)| format!("{}={}", name, u8::from(state.flags & bit != 0)))
+                                .collect::<Vec<_>>()
+                                .join(" "));
     let interrupts_line = format!("Interrupts: {}    Mask: {:04X}", 
                                  state.interrupts_enabled, state.interrupt_mask);
     let running_line = format!("Running: {}", state.is_running);
+    let register_lines: Vec<String> = state.registers.chunks(4).enumerate()
+        .map(|(row, chunk)| {
+            chunk.iter().enumerate()
+                .map(|(i, value)| format!("R{}: {:04X}", row * 4 + i, value))
+                .collect::<Vec<_>>()
+                .join("    ")
+        })
+        .collect();
    
     let padding = 4;
     let content_width = [
         title.len(),
         ak_line.len(),
         kod_ws_line.len(),
+        flags_line.len(),
         interrupts_line.len(),
-        running_line.len()
+        running_line.len(),
+        register_lines[0].len()
     ].iter().max().unwrap() + padding;
     
     let box_width = content_width.max(50);
@@ -65,6 +81,26 @@ pub fn print_machine_state(machine: &MachineW) {
                                  state.kod, state.kod, state.ws, state.ws);
     println!("{}", format_line(&kod_ws_line, &kod_ws_colored));
     
+    let flags_colored = format!("\x1b[1m\x1b[38;5;33mFlags:\x1b[0m {}",
+                               flag_bits.iter()
+                                   .map(|(name, bit)| {
+                                       let color = if state.flags & bit != 0 { "\x1b[1m\x1b[38;5;120m" } else { "\x1b[1m\x1b[38;5;244m" };
+                                       format!("{}{}={}\x1b[0m", color, name, u8::from(state.flags & bit != 0))
+                                   })
+                                   .collect::<Vec<_>>()
+                                   .join(" "));
+    println!("{}", format_line(&flags_line, &flags_colored));
+    
+    println!("│{}│", " ".repeat(box_width - 2));
+    
+    for (row, register_line) in register_lines.iter().enumerate() {
+        let register_colored = state.registers[row * 4..row * 4 + 4].iter().enumerate()
+            .map(|(i, value)| format!("\x1b[1m\x1b[38;5;33mR{}:\x1b[0m \x1b[1m\x1b[37m{:04X}\x1b[0m", row * 4 + i, value))
+            .collect::<Vec<_>>()
+            .join("    ");
+        println!("{}", format_line(register_line, &register_colored));
+    }
+    
     println!("│{}│", " ".repeat(box_width - 2));
     
     let interrupts_color = if state.interrupts_enabled { "\x1b[1m\x1b[38;5;120m" } else { "\x1b[1m\x1b[38;5;210m" };
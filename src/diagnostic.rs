@@ -0,0 +1,344 @@
+//! errors and warnings pointing into the source: file, line and column, with the line itself and a caret
+//! under the culprit, or the same as JSON for tools
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use asmachina::MachineError;
+use hephasm::{AssemblerError, DebugInfo, Warning};
+use parseid::{ParserError, SourceMap};
+use crate::error::AsmodeusError;
+
+/// how bad a diagnostic is, every error stops the program from being assembled, warnings and info do not
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+    /// lints that are off unless asked for with -W
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "Error"),
+            Severity::Warning => write!(f, "Warning"),
+            Severity::Info => write!(f, "Info"),
+        }
+    }
+}
+
+/// part of the toolchain that found the error
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stage {
+    Lexer,
+    Parser,
+    Assembler,
+    Linker,
+    Machine,
+    Disassembler,
+    /// warnings about a program that assembled
+    Lint,
+    Io,
+    Usage,
+}
+
+impl Stage {
+    /// as written in JSON diagnostics
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stage::Lexer => "lexer",
+            Stage::Parser => "parser",
+            Stage::Assembler => "assembler",
+            Stage::Linker => "linker",
+            Stage::Machine => "machine",
+            Stage::Disassembler => "disassembler",
+            Stage::Lint => "lint",
+            Stage::Io => "io",
+            Stage::Usage => "usage",
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Lexer => write!(f, "Lexer Error"),
+            Stage::Parser => write!(f, "Parser Error"),
+            Stage::Assembler => write!(f, "Assembler Error"),
+            Stage::Linker => write!(f, "Link Error"),
+            Stage::Machine => write!(f, "Machine Error"),
+            Stage::Disassembler => write!(f, "Disassembler Error"),
+            Stage::Lint => write!(f, "Lint"),
+            Stage::Io => write!(f, "I/O Error"),
+            Stage::Usage => write!(f, "Usage Error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub stage: Stage,
+    /// stable code of the kind of error, E0301 for an undefined symbol, W0302 for an unused label
+    pub code: &'static str,
+    pub message: String,
+    /// None for errors not found in a source file (link errors)
+    pub location: Option<Location>,
+    /// replacements for the text under the caret
+    pub suggestions: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub file: String,
+    pub line: usize,
+    /// 1-based, in characters
+    pub column: usize,
+    /// characters marked by the caret
+    pub width: usize,
+    /// text of the line, None when the file cannot be read
+    pub source_line: Option<String>,
+}
+
+/// text of the files diagnostics point into, each read once however many errors it has
+#[derive(Debug, Default)]
+pub struct Sources {
+    texts: HashMap<String, Option<String>>,
+}
+
+impl Sources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// None when the file cannot be read
+    pub fn text(&mut self, file: &str) -> Option<&str> {
+        self.texts.entry(file.to_string())
+            .or_insert_with(|| fs::read_to_string(file).ok())
+            .as_deref()
+    }
+}
+
+impl Location {
+    /// column given by the error, else where subject appears in the line as a whole word,
+    /// else the first character of the line; source is the text of the whole file
+    pub fn new(file: &str, source: Option<&str>, line: usize, column: Option<usize>, subject: Option<&str>) -> Self {
+        let source_line = source.and_then(|source| source.lines().nth(line.checked_sub(1)?).map(str::to_string));
+        let text = source_line.as_deref().unwrap_or_default();
+        let found = subject.filter(|subject| !subject.is_empty()).and_then(|subject| {
+            find_word(text, subject).map(|column| (column, subject.chars().count()))
+        });
+
+        let (column, width) = match (column, found) {
+            (Some(column), Some((found, width))) if found == column => (column, width),
+            (Some(column), _) => (column, 1),
+            (None, Some(found)) => found,
+            (None, None) => (text.chars().position(|c| !c.is_whitespace()).map_or(1, |index| index + 1), 1),
+        };
+
+        Self {
+            file: file.to_string(),
+            line,
+            column,
+            width,
+            source_line,
+        }
+    }
+}
+
+impl Diagnostic {
+    /// errors in included files point into those files
+    pub fn from_parser_error(error: ParserError, main_file: &str, sources: &mut Sources) -> Self {
+        let (file, error) = match error {
+            ParserError::InFile { file, error } => (file, *error),
+            error => (main_file.to_string(), error),
+        };
+        let subject = match &error {
+            ParserError::IncludeNotFound { path, .. } => Some(path.clone()),
+            _ => None,
+        };
+        let location = error.line().map(|line| Location::new(&file, sources.text(&file), line, error.column(), subject.as_deref()));
+        let (stage, message) = match &error {
+            ParserError::LexerError(e) => (Stage::Lexer, e.to_string()),
+            error => (Stage::Parser, error.to_string()),
+        };
+
+        Self {
+            severity: Severity::Error,
+            stage,
+            code: error.code(),
+            message,
+            location,
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// lines of the assembled program are mapped back to the files they came from,
+    /// the message giving the line within that file
+    pub fn from_assembler_error(mut error: AssemblerError, source_map: &SourceMap, sources: &mut Sources) -> Self {
+        let located = error.line_mut().and_then(|line| {
+            let (file, file_line) = source_map.locate(*line)?;
+            *line = file_line;
+            Some((file, file_line))
+        });
+        let location = located.map(|(file, line)| Location::new(file, sources.text(file), line, error.column(), error.subject()));
+
+        Self {
+            severity: Severity::Error,
+            stage: Stage::Assembler,
+            code: error.code(),
+            message: error.to_string(),
+            location,
+            suggestions: error.suggestion().map(str::to_string).into_iter().collect(),
+        }
+    }
+
+    /// placed like assembler errors, the message ending with the name of the lint to turn it off with -A
+    pub fn from_warning(mut warning: Warning, source_map: &SourceMap, sources: &mut Sources) -> Self {
+        let located = warning.line.as_mut().and_then(|line| {
+            let (file, file_line) = source_map.locate(*line)?;
+            *line = file_line;
+            Some((file, file_line))
+        });
+        let location = located.map(|(file, line)| Location::new(file, sources.text(file), line, None, warning.subject.as_deref()));
+
+        Self {
+            severity: if warning.lint.is_info() { Severity::Info } else { Severity::Warning },
+            stage: Stage::Lint,
+            code: warning.lint.code(),
+            message: format!("{} [{}]", warning, warning.lint),
+            location,
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// placed at the source of the instruction starting at address, unlocated when the debug info has no file for it
+    pub fn from_machine_error(error: MachineError, address: u16, debug_info: &DebugInfo, sources: &mut Sources) -> Self {
+        let location = debug_info.location(address).and_then(|location| {
+            let file = location.file.as_deref()?;
+            Some(Location::new(file, sources.text(file), location.line, Some(location.column), None))
+        });
+
+        Self {
+            severity: Severity::Error,
+            stage: Stage::Machine,
+            code: error.code(),
+            message: error.to_string(),
+            location,
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// any error of the CLI, those not found in a source file have no location
+    pub fn from_error(error: AsmodeusError) -> Vec<Self> {
+        let unlocated = |stage, code, message: String| Self {
+            severity: Severity::Error,
+            stage,
+            code,
+            message,
+            location: None,
+            suggestions: Vec::new(),
+        };
+
+        match error {
+            AsmodeusError::Diagnostics(diagnostics) => diagnostics,
+            AsmodeusError::LinkErrors(errors) => errors.into_iter()
+                .map(|e| unlocated(Stage::Linker, e.code(), e.to_string()))
+                .collect(),
+            AsmodeusError::LexerError(e) => vec![unlocated(Stage::Lexer, e.code(), e.to_string())],
+            AsmodeusError::ParserError(e) => vec![unlocated(Stage::Parser, e.code(), e.to_string())],
+            AsmodeusError::AssemblerError(e) => vec![unlocated(Stage::Assembler, e.code(), e.to_string())],
+            AsmodeusError::MachineError(e) => vec![unlocated(Stage::Machine, e.code(), e.to_string())],
+            AsmodeusError::DisassemblerError(e) => vec![unlocated(Stage::Disassembler, e.code(), e.to_string())],
+            // not about the program itself
+            AsmodeusError::IoError(e) => vec![unlocated(Stage::Io, "E0001", e.to_string())],
+            AsmodeusError::UsageError(e) => vec![unlocated(Stage::Usage, "E0002", e)],
+        }
+    }
+
+    /// one line: {"stage":"assembler","code":"E0301","severity":"error","message":"...","file":"program.asmod",
+    /// "line":3,"column":9,"span":{"start":9,"end":14},"suggestions":[]}, file to span null without a location;
+    /// columns count characters from 1, span end is past the last marked one
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"stage\":{},\"code\":{},\"severity\":{},\"message\":{}",
+            json_string(self.stage.name()),
+            json_string(self.code),
+            json_string(&self.severity.to_string().to_lowercase()),
+            json_string(&self.message),
+        );
+        match &self.location {
+            Some(location) => json.push_str(&format!(
+                ",\"file\":{},\"line\":{},\"column\":{},\"span\":{{\"start\":{},\"end\":{}}}",
+                json_string(&location.file),
+                location.line,
+                location.column,
+                location.column,
+                location.column + location.width.max(1),
+            )),
+            None => json.push_str(",\"file\":null,\"line\":null,\"column\":null,\"span\":null"),
+        }
+        let suggestions: Vec<String> = self.suggestions.iter().map(|s| json_string(s)).collect();
+        json.push_str(&format!(",\"suggestions\":[{}]}}", suggestions.join(",")));
+        json
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    ///   Error: Assembler Error: Undefined symbol: vaule at line 3
+    ///     --> program.asmod:3:9
+    ///      |
+    ///    3 |     POB vaule
+    ///      |         ^^^^^
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.stage, self.message)?;
+        let Some(location) = &self.location else {
+            return Ok(());
+        };
+
+        let gutter = location.line.to_string().len();
+        write!(f, "\n{:gutter$}--> {}:{}:{}", "", location.file, location.line, location.column)?;
+        if let Some(text) = &location.source_line {
+            // tabs kept so the caret lines up with the text above it
+            let padding: String = text.chars()
+                .take(location.column.saturating_sub(1))
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            write!(f, "\n{:gutter$} |", "")?;
+            write!(f, "\n{} | {}", location.line, text)?;
+            write!(f, "\n{:gutter$} | {}{}", "", padding, "^".repeat(location.width.max(1)))?;
+        }
+        Ok(())
+    }
+}
+
+/// 1-based column of the first occurrence of word not inside a longer name
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(word).find_map(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        let whole = !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char);
+        whole.then(|| text[..start].chars().count() + 1)
+    })
+}
+
+/// quoted, with quotes, backslashes and control characters escaped
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
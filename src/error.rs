@@ -1,6 +1,7 @@
 //! Error handling for Asmodeus CLI
 
 use std::fmt;
+use crate::diagnostic::Diagnostic;
 
 #[derive(Debug)]
 pub enum AsmodeusError {
@@ -8,9 +9,13 @@ pub enum AsmodeusError {
     LexerError(lexariel::LexerError),
     ParserError(parseid::ParserError),
     AssemblerError(hephasm::AssemblerError),
+    /// every undefined and duplicate symbol found while linking
+    LinkErrors(Vec<hephasm::LinkError>),
     MachineError(asmachina::MachineError),
     DisassemblerError(dismael::DisassemblerError),
     UsageError(String),
+    /// every error found in the source files, with the lines they point at
+    Diagnostics(Vec<Diagnostic>),
 }
 
 impl fmt::Display for AsmodeusError {
@@ -20,9 +25,21 @@ impl fmt::Display for AsmodeusError {
             AsmodeusError::LexerError(e) => write!(f, "Lexer Error: {}", e),
             AsmodeusError::ParserError(e) => write!(f, "Parser Error: {}", e),
             AsmodeusError::AssemblerError(e) => write!(f, "Assembler Error: {}", e),
+            AsmodeusError::LinkErrors(errors) => {
+                let errors: Vec<String> = errors.iter().map(|e| format!("Link Error: {}", e)).collect();
+                write!(f, "{}", errors.join("\n"))
+            }
             AsmodeusError::MachineError(e) => write!(f, "Machine Error: {}", e),
             AsmodeusError::DisassemblerError(e) => write!(f, "Disassembler Error: {}", e),
             AsmodeusError::UsageError(e) => write!(f, "Usage Error: {}", e),
+            AsmodeusError::Diagnostics(diagnostics) => {
+                let rendered: Vec<String> = diagnostics.iter().map(Diagnostic::to_string).collect();
+                write!(f, "{}", rendered.join("\n\n"))?;
+                if diagnostics.len() > 1 {
+                    write!(f, "\n\n{} errors found", diagnostics.len())?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -43,7 +60,11 @@ impl From<lexariel::LexerError> for AsmodeusError {
 
 impl From<parseid::ParserError> for AsmodeusError {
     fn from(error: parseid::ParserError) -> Self {
-        AsmodeusError::ParserError(error)
+        match error {
+            // parsing a file tokenizes it (and every file it includes) too
+            parseid::ParserError::LexerError(e) => AsmodeusError::LexerError(e),
+            e => AsmodeusError::ParserError(e),
+        }
     }
 }
 
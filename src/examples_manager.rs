@@ -160,6 +160,7 @@ fn determine_category(path: &Path) -> String {
                 "basic" => "🎯 Basic".to_string(),
                 "arithmetic" => "🧮 Arithmetic".to_string(),
                 "extended_set" => "⚡ Extended Set".to_string(),
+                "bits" => "🔣 Bitwise Set".to_string(),
                 "io" => "💾 I/O Operations".to_string(),
                 "arrays" => "📊 Arrays".to_string(),
                 "advanced" => "🔧 Advanced".to_string(),
@@ -1,7 +1,11 @@
 use std::fs;
 use std::path::Path;
 use crate::error::AsmodeusError;
-use crate::cli::Mode;
+use crate::cli::{Mode, ImageFormat};
+use hephasm::{DebugInfo, ObjectFile, Segment};
+
+/// first bytes of a sparse image, see write_image
+const SPARSE_IMAGE_MAGIC: &[u8; 4] = b"ASMS";
 
 pub fn validate_file_extension(path: &str, mode: Mode) -> Result<(), AsmodeusError> {
     let path = Path::new(path);
@@ -10,6 +14,7 @@ pub fn validate_file_extension(path: &str, mode: Mode) -> Result<(), AsmodeusErr
     match (mode.clone(), extension) {
         (Mode::Run | Mode::Assemble | Mode::Debug | Mode::Interactive | Mode::Check | Mode::Format, Some("asmod")) => Ok(()),
         (Mode::Disassemble, Some("bin")) => Ok(()),
+        (Mode::Link, Some("o")) => Ok(()),
         (Mode::Help, _) => Ok(()), // help mode doesnt need file validation
         (Mode::Examples, _) => Ok(()),
         (Mode::New, _) => Ok(()),
@@ -23,9 +28,14 @@ pub fn validate_file_extension(path: &str, mode: Mode) -> Result<(), AsmodeusErr
                 format!("Expected .bin file for disassembly, but got .{} file.", ext)
             ))
         }
+        (Mode::Link, Some(ext)) => {
+            Err(AsmodeusError::UsageError(
+                format!("Expected .o object file for linking, but got .{} file.", ext)
+            ))
+        }
         (_, None) => {
             Err(AsmodeusError::UsageError(
-                format!("File has no extension. Expected: .asmod for run/assemble/debug, .bin for disassemble")
+                "File has no extension. Expected: .asmod for run/assemble/debug, .bin for disassemble, .o for link".to_string()
             ))
         }
     }
@@ -52,6 +62,36 @@ pub fn write_binary(path: &str, data: &[u16]) -> Result<(), AsmodeusError> {
     })
 }
 
+/// machine_code starts at address 0, segments say which words the program actually uses;
+/// a sparse image holds ASMS and then start, word count and words of each segment, all little endian
+pub fn write_image(path: &str, machine_code: &[u16], segments: &[Segment], format: Option<ImageFormat>) -> Result<(), AsmodeusError> {
+    match format {
+        None => write_binary(path, machine_code),
+        Some(ImageFormat::Full) => {
+            let mut memory = machine_code.to_vec();
+            memory.resize(2048, 0);
+            write_binary(path, &memory)
+        }
+        Some(ImageFormat::Sparse) => {
+            let mut bytes = SPARSE_IMAGE_MAGIC.to_vec();
+            for segment in segments {
+                bytes.extend(segment.start.to_le_bytes());
+                bytes.extend((segment.len() as u16).to_le_bytes());
+                for word in &machine_code[segment.start as usize..segment.end as usize] {
+                    bytes.extend(word.to_le_bytes());
+                }
+            }
+            fs::write(path, bytes).map_err(|e| {
+                AsmodeusError::IoError(std::io::Error::new(
+                    e.kind(),
+                    format!("Failed to write binary file '{}': {}", path, e)
+                ))
+            })
+        }
+    }
+}
+
+/// words from address 0, sparse images are expanded with zeros between the segments
 pub fn read_binary(path: &str) -> Result<Vec<u16>, AsmodeusError> {
     let bytes = fs::read(path).map_err(|e| {
         AsmodeusError::IoError(std::io::Error::new(
@@ -70,5 +110,87 @@ pub fn read_binary(path: &str) -> Result<Vec<u16>, AsmodeusError> {
         .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
         .collect();
     
-    Ok(words)
+    match bytes.strip_prefix(SPARSE_IMAGE_MAGIC) {
+        Some(_) => expand_sparse_image(&words[2..]).map_err(|reason| {
+            AsmodeusError::UsageError(format!("Invalid sparse image '{}': {}", path, reason))
+        }),
+        None => Ok(words),
+    }
+}
+
+fn expand_sparse_image(mut records: &[u16]) -> Result<Vec<u16>, String> {
+    let mut memory = Vec::new();
+    while let [start, count, rest @ ..] = records {
+        let (start, count) = (*start as usize, *count as usize);
+        if start + count > 2048 {
+            return Err(format!("segment at {} with {} words goes past the end of memory", start, count));
+        }
+        let words = rest.get(..count)
+            .ok_or_else(|| format!("segment at {} is missing {} words", start, count - rest.len()))?;
+        if memory.len() < start + count {
+            memory.resize(start + count, 0);
+        }
+        memory[start..start + count].copy_from_slice(words);
+        records = &rest[count..];
+    }
+    if !records.is_empty() {
+        return Err("truncated segment header".to_string());
+    }
+    Ok(memory)
+}
+
+/// debug info sidecar of a binary: program.bin -> program.dbg
+pub fn debug_info_path(binary_path: &str) -> String {
+    Path::new(binary_path).with_extension("dbg").display().to_string()
+}
+
+/// object file of a source: module.asmod -> module.o
+pub fn object_path(source_path: &str) -> String {
+    Path::new(source_path).with_extension("o").display().to_string()
+}
+
+/// listing of a binary: program.bin -> program.lst
+pub fn listing_path(binary_path: &str) -> String {
+    Path::new(binary_path).with_extension("lst").display().to_string()
+}
+
+pub fn write_listing(path: &str, listing: &str) -> Result<(), AsmodeusError> {
+    fs::write(path, listing).map_err(|e| {
+        AsmodeusError::IoError(std::io::Error::new(
+            e.kind(),
+            format!("Failed to write listing file '{}': {}", path, e)
+        ))
+    })
+}
+
+pub fn write_debug_info(path: &str, debug_info: &DebugInfo) -> Result<(), AsmodeusError> {
+    fs::write(path, debug_info.to_string()).map_err(|e| {
+        AsmodeusError::IoError(std::io::Error::new(
+            e.kind(),
+            format!("Failed to write debug info file '{}': {}", path, e)
+        ))
+    })
+}
+
+pub fn read_debug_info(path: &str) -> Result<DebugInfo, AsmodeusError> {
+    let text = read_file(path)?;
+    text.parse().map_err(|reason| {
+        AsmodeusError::UsageError(format!("Invalid debug info file '{}': {}", path, reason))
+    })
+}
+
+pub fn write_object(path: &str, object: &ObjectFile) -> Result<(), AsmodeusError> {
+    fs::write(path, object.to_string()).map_err(|e| {
+        AsmodeusError::IoError(std::io::Error::new(
+            e.kind(),
+            format!("Failed to write object file '{}': {}", path, e)
+        ))
+    })
+}
+
+pub fn read_object(path: &str) -> Result<ObjectFile, AsmodeusError> {
+    let text = read_file(path)?;
+    text.parse().map_err(|reason| {
+        AsmodeusError::UsageError(format!("Invalid object file '{}': {}", path, reason))
+    })
 }
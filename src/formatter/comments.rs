@@ -1,5 +1,5 @@
 pub fn split_instruction_and_comment(text: &str) -> (&str, Option<&str>) {
-    if let Some(comment_pos) = text.find(';') {
+    if let Some(comment_pos) = find_comment_start(text) {
         let inst = text[..comment_pos].trim();
         let comment = text[comment_pos..].trim();
         (inst, Some(comment))
@@ -8,6 +8,26 @@ pub fn split_instruction_and_comment(text: &str) -> (&str, Option<&str>) {
     }
 }
 
+/// first ';' that is not inside a "..." or '...' literal
+fn find_comment_start(text: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (pos, ch) in text.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if ch == '\\' => escaped = true,
+            Some(open) if ch == open => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == ';' => return Some(pos),
+            None => {}
+        }
+    }
+
+    None
+}
+
 pub fn fix_comment_spacing(comment: &str) -> String {
     if comment.starts_with(';') && comment.len() > 1 && !comment.chars().nth(1).unwrap().is_whitespace() {
         format!("; {}", &comment[1..])
@@ -38,6 +58,14 @@ mod tests {
         let (inst, comment) = split_instruction_and_comment("POB #42");
         assert_eq!(inst, "POB #42");
         assert_eq!(comment, None);
+
+        let (inst, comment) = split_instruction_and_comment(r#"NAPIS "a; \"b\"" ; text"#);
+        assert_eq!(inst, r#"NAPIS "a; \"b\"""#);
+        assert_eq!(comment, Some("; text"));
+
+        let (inst, comment) = split_instruction_and_comment("POB #';'");
+        assert_eq!(inst, "POB #';'");
+        assert_eq!(comment, None);
     }
 
     #[test]
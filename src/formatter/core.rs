@@ -44,8 +44,8 @@ pub fn format_assembly_content(content: &str) -> String {
             continue;
         }
         
-        // data labels (label: RST/RPA value)
-        if trimmed.contains(':') && (trimmed.contains("RST") || trimmed.contains("RPA")) {
+        // data labels (label: RST/RPA value, label: NAPIS "text")
+        if trimmed.contains(':') && (trimmed.contains("RST") || trimmed.contains("RPA") || trimmed.contains("NAPIS")) {
             if is_array_start(&lines, i) {
                 let processed = format_array_block(&lines, &mut i);
                 formatted_lines.extend(processed);
@@ -29,6 +29,11 @@ pub fn format_directive(directive: &str) -> String {
 }
 
 fn normalize_instruction_spacing(text: &str) -> String {
+    // spacing inside string and char literals is kept as written
+    if text.contains('"') || text.contains('\'') {
+        return collapse_spacing_outside_literals(text);
+    }
+
     if text.contains(' ') {
         let parts: Vec<&str> = text.split_whitespace().collect();
         format!("{} {}", parts[0], parts[1..].join(" "))
@@ -37,6 +42,33 @@ fn normalize_instruction_spacing(text: &str) -> String {
     }
 }
 
+/// single spaces between words, literals left untouched
+pub fn collapse_spacing_outside_literals(text: &str) -> String {
+    let mut result = String::new();
+    let mut quote = None;
+    let mut escaped = false;
+
+    for ch in text.trim().chars() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if ch == '\\' => escaped = true,
+            Some(open) if ch == open => quote = None,
+            Some(_) => {}
+            None if ch.is_whitespace() => {
+                if !result.ends_with(' ') {
+                    result.push(' ');
+                }
+                continue;
+            }
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None => {}
+        }
+        result.push(ch);
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,6 +80,8 @@ mod tests {
         assert_eq!(format_instruction("POB #42;comment"), "POB #42              ; comment");
         assert_eq!(format_instruction("POB #42   ;comment"), "POB #42              ; comment");
         assert_eq!(format_instruction("WYJSCIE"), "WYJSCIE");
+        assert_eq!(format_instruction(r#"NAPIS   "a  b""#), r#"NAPIS "a  b""#);
+        assert_eq!(format_instruction("POB   #' '"), "POB #' '");
     }
 
     #[test]
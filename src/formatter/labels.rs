@@ -1,16 +1,17 @@
 use crate::formatter::{
     comments::{split_instruction_and_comment, format_line_with_comment},
-    instructions::format_instruction
+    instructions::{format_instruction, collapse_spacing_outside_literals}
 };
 
 pub fn has_instruction_after_colon(line: &str) -> bool {
     if let Some(colon_pos) = line.find(':') {
         let after_colon = line[colon_pos + 1..].trim();
         
-        // content after colon thats not RST/RPA (data directives)
+        // content after colon thats not RST/RPA/NAPIS (data directives)
         if !after_colon.is_empty() 
             && !after_colon.starts_with("RST") 
-            && !after_colon.starts_with("RPA") {
+            && !after_colon.starts_with("RPA") 
+            && !after_colon.starts_with("NAPIS") {
             // not just a comment
             if !after_colon.starts_with(';') {
                 return true;
@@ -67,6 +68,11 @@ pub fn format_label_with_data(line: &str) -> String {
 }
 
 fn normalize_data_spacing(data: &str) -> String {
+    // spacing inside string and char literals is kept as written
+    if data.contains('"') || data.contains('\'') {
+        return collapse_spacing_outside_literals(data);
+    }
+
     if data.contains(' ') {
         let parts: Vec<&str> = data.split_whitespace().collect();
         format!("{} {}", parts[0], parts[1..].join(" "))
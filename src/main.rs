@@ -1,6 +1,7 @@
 use std::process;
 
 mod error;
+mod diagnostic;
 mod cli;
 mod file_utils;
 mod debug;
@@ -12,12 +13,19 @@ mod examples_manager;
 mod template_generator;
 mod formatter;
 
-use cli::{parse_args, print_help, Mode};
-use modes::{run_mode_assemble, run_mode_run, run_mode_disassemble, run_mode_debug, run_mode_interactive, run_mode_check};
+use cli::{parse_args, print_help, requested_message_format, MessageFormat, Mode};
+use diagnostic::Diagnostic;
+use modes::{run_mode_assemble, run_mode_run, run_mode_disassemble, run_mode_debug, run_mode_interactive, run_mode_check, run_mode_link};
 
 fn main() {
     let args = match parse_args() {
         Ok(args) => args,
+        Err(e) if requested_message_format() == MessageFormat::Json => {
+            for diagnostic in Diagnostic::from_error(e) {
+                println!("{}", diagnostic.to_json());
+            }
+            process::exit(1);
+        }
         Err(e) => {
             eprintln!("Error: {}", e);
             eprintln!("Use --help for usage information.");
@@ -39,10 +47,26 @@ fn main() {
         Mode::New => template_generator::handle_new_command(&args),
         Mode::Check => run_mode_check(&args),
         Mode::Format => formatter::handle_format_command(&args),
+        Mode::Link => run_mode_link(&args),
     };
 
-    if let Err(e) = result {
-        eprintln!("Error: {}", e);
-        process::exit(1);
+    match result {
+        Ok(()) => {}
+        // one object per line, errors without a place in the source included
+        Err(e) if args.message_format == MessageFormat::Json => {
+            for diagnostic in Diagnostic::from_error(e) {
+                println!("{}", diagnostic.to_json());
+            }
+            process::exit(1);
+        }
+        // every diagnostic names its own severity
+        Err(e @ error::AsmodeusError::Diagnostics(_)) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
     }
 }
@@ -1,9 +1,11 @@
 use asmachina::MachineW;
+use asmodeus_shared::IsaProfile;
+use hephasm::{Linker, Segment, DEFAULT_SECTION};
 use crate::error::AsmodeusError;
-use crate::cli::{Args, Mode};
-use crate::file_utils::{validate_file_extension, write_binary};
-use crate::assembler::{assemble_file, run_program, disassemble_file, run_interactive_program};
-use crate::bugseer::interactive_debugger_loop;
+use crate::cli::{Args, MessageFormat, Mode};
+use crate::file_utils::{validate_file_extension, write_image, write_debug_info, debug_info_path, write_listing, listing_path, write_object, read_object, object_path};
+use crate::assembler::{assemble_file_with_debug_info, assemble_object_file, check_file, format_listing, run_program, disassemble_file, run_interactive_program};
+use crate::bugseer::{interactive_debugger_loop, print_source_location};
 use crate::debug::{print_machine_state, print_program_loaded_banner};
 use crate::ascii_art::{print_info, print_bugseer_logo};
 use std::time::Duration;
@@ -16,15 +18,36 @@ pub fn run_mode_assemble(args: &Args) -> Result<(), AsmodeusError> {
     
     validate_file_extension(input_path, Mode::Assemble)?;
     
-    let machine_code = assemble_file(input_path, args)?;
+    if args.object {
+        return assemble_object(input_path, args);
+    }
+    
+    if args.debug_info && args.output_file.is_none() {
+        return Err(AsmodeusError::UsageError("--debug-info needs an output file (-o)".to_string()));
+    }
+    
+    let (machine_code, _, debug_info, segments) = assemble_file_with_debug_info(input_path, args)?;
+    let listing = args.listing.then(|| format_listing(&machine_code, &debug_info, input_path));
     
     if let Some(output_path) = &args.output_file {
-        write_binary(output_path, &machine_code)?;
+        write_image(output_path, &machine_code, &segments, args.image)?;
         if args.verbose {
             println!("Binary written to: {}", output_path);
         } else {
             println!("Assembly successful. Binary written to: {}", output_path);
         }
+        if args.debug_info {
+            let debug_path = debug_info_path(output_path);
+            write_debug_info(&debug_path, &debug_info)?;
+            println!("Debug info written to: {}", debug_path);
+        }
+        if let Some(listing) = &listing {
+            let listing_path = listing_path(output_path);
+            write_listing(&listing_path, listing)?;
+            println!("Listing written to: {}", listing_path);
+        }
+    } else if let Some(listing) = &listing {
+        print!("{}", listing);
     } else {
         println!("Assembly successful!");
         println!("Machine code ({} words):", machine_code.len());
@@ -36,6 +59,56 @@ pub fn run_mode_assemble(args: &Args) -> Result<(), AsmodeusError> {
     Ok(())
 }
 
+fn assemble_object(input_path: &str, args: &Args) -> Result<(), AsmodeusError> {
+    if args.debug_info || args.listing || args.image.is_some() {
+        return Err(AsmodeusError::UsageError("--object cannot be combined with --debug-info, --listing or --image".to_string()));
+    }
+    
+    let object = assemble_object_file(input_path, args)?;
+    let output_path = args.output_file.clone().unwrap_or_else(|| object_path(input_path));
+    write_object(&output_path, &object)?;
+    println!("Assembly successful. Object file written to: {}", output_path);
+    
+    Ok(())
+}
+
+pub fn run_mode_link(args: &Args) -> Result<(), AsmodeusError> {
+    if args.object_files.is_empty() {
+        return Err(AsmodeusError::UsageError("No object files specified for linking".to_string()));
+    }
+    
+    let mut linker = Linker::new();
+    for path in &args.object_files {
+        validate_file_extension(path, Mode::Link)?;
+        if args.verbose {
+            println!("📖 Reading object file: {}", path);
+        }
+        linker.add_module(path.as_str(), read_object(path)?);
+    }
+    
+    let machine_code = linker.link().map_err(AsmodeusError::LinkErrors)?;
+    
+    if let Some(output_path) = &args.output_file {
+        // linked modules follow each other without gaps
+        let segments = [Segment {
+            section: DEFAULT_SECTION.to_string(),
+            start: 0,
+            end: machine_code.len() as u16,
+            line: 0,
+        }];
+        write_image(output_path, &machine_code, &segments, args.image)?;
+        println!("Link successful. Binary written to: {}", output_path);
+    } else {
+        println!("Link successful!");
+        println!("Machine code ({} words):", machine_code.len());
+        for (i, word) in machine_code.iter().enumerate() {
+            println!("  {:04X}: {:04X} ({})", i, word, word);
+        }
+    }
+    
+    Ok(())
+}
+
 pub fn run_mode_run(args: &Args) -> Result<(), AsmodeusError> {
     if args.watch {
         return run_mode_watch(args);
@@ -46,8 +119,8 @@ pub fn run_mode_run(args: &Args) -> Result<(), AsmodeusError> {
     
     validate_file_extension(input_path, Mode::Run)?;
     
-    let machine_code = assemble_file(input_path, args)?;
-    run_program(&machine_code, args)
+    let (machine_code, isa, debug_info, _) = assemble_file_with_debug_info(input_path, args)?;
+    run_program(&machine_code, isa, &debug_info, args)
 }
 
 pub fn run_mode_watch(args: &Args) -> Result<(), AsmodeusError> {
@@ -91,8 +164,8 @@ fn run_program_once(input_path: &str, args: &Args) -> Result<(), AsmodeusError>
     let mut run_args = args.clone();
     run_args.watch = false;
     
-    let machine_code = assemble_file(input_path, &run_args)?;
-    run_program(&machine_code, &run_args)
+    let (machine_code, isa, debug_info, _) = assemble_file_with_debug_info(input_path, &run_args)?;
+    run_program(&machine_code, isa, &debug_info, &run_args)
 }
 
 pub fn run_mode_disassemble(args: &Args) -> Result<(), AsmodeusError> {
@@ -113,24 +186,11 @@ pub fn run_mode_check(args: &Args) -> Result<(), AsmodeusError> {
     validate_file_extension(input_path, Mode::Check)?;
     
     if args.verbose {
-        print_info(&format!("Checking syntax of: {}", input_path));
+        print_info(&format!("Checking: {}", input_path));
     }
 
-    let source = std::fs::read_to_string(input_path)
-        .map_err(|e| AsmodeusError::IoError(e))?;
-    
-    if args.verbose {
-        print_info("📝 Tokenizing source code...");
-    }
-    
-    let tokens = lexariel::tokenize(&source)?;
-    
-    if args.verbose {
-        print_info(&format!("✅ Tokenization successful: {} tokens found", tokens.len()));
-        print_info("🔍 Parsing tokens to AST...");
-    }
-    
-    let ast = parseid::parse(tokens)?;
+    // parses and assembles without writing anything, reporting every error
+    let (ast, source_map, warnings) = check_file(input_path, args)?;
     
     if args.verbose {
         print_info(&format!("✅ Parsing successful: {} program elements found", ast.elements.len()));
@@ -140,6 +200,7 @@ pub fn run_mode_check(args: &Args) -> Result<(), AsmodeusError> {
         let mut macros = 0;
         let mut macro_calls = 0;
         let mut labels = 0;
+        let mut conditionals = 0;
         
         for element in &ast.elements {
             match element {
@@ -148,6 +209,7 @@ pub fn run_mode_check(args: &Args) -> Result<(), AsmodeusError> {
                 parseid::ProgramElement::MacroDefinition(_) => macros += 1,
                 parseid::ProgramElement::MacroCall(_) => macro_calls += 1,
                 parseid::ProgramElement::LabelDefinition(_) => labels += 1,
+                parseid::ProgramElement::Conditional(_) => conditionals += 1,
             }
         }        
 
@@ -157,15 +219,29 @@ pub fn run_mode_check(args: &Args) -> Result<(), AsmodeusError> {
         println!("   Data directives: {}", directives);
         println!("   Macro definitions: {}", macros);
         println!("   Macro calls: {}", macro_calls);
+        println!("   Conditional blocks: {}", conditionals);
+        println!("   Included files: {}", source_map.files().count() - 1);
     }
     
-    println!("✅ {}: Syntax check passed", input_path);
-    
-    if args.extended {
-        if args.verbose {
-            print_info("🔍 Checking for extended instructions...");
+    // nothing but diagnostics is written as JSON, warnings do not fail the check
+    if args.message_format == MessageFormat::Json {
+        for warning in &warnings {
+            println!("{}", warning.to_json());
         }
-        println!("ℹ️  Extended instruction set enabled");
+        return Ok(());
+    }
+    
+    for warning in &warnings {
+        eprintln!("{}\n", warning);
+    }
+    match warnings.len() {
+        0 => println!("✅ {}: Check passed, no errors found", input_path),
+        1 => println!("✅ {}: Check passed, no errors found, 1 warning", input_path),
+        count => println!("✅ {}: Check passed, no errors found, {} warnings", input_path, count),
+    }
+    
+    if args.isa != IsaProfile::default() {
+        println!("ℹ️  Instruction set profile: {}", args.isa);
     }
     
     Ok(())
@@ -181,9 +257,10 @@ pub fn run_mode_debug(args: &Args) -> Result<(), AsmodeusError> {
         print_info(&format!("Starting Bugseer for: {}", input_path));
     }
 
-    let machine_code = assemble_file(input_path, args)?;
+    let (machine_code, isa, debug_info, _) = assemble_file_with_debug_info(input_path, args)?;
     
     let mut machine = MachineW::new();
+    machine.set_isa_profile(isa);
     machine.load_program(&machine_code).map_err(|e| {
         AsmodeusError::MachineError(e)
     })?;
@@ -194,8 +271,9 @@ pub fn run_mode_debug(args: &Args) -> Result<(), AsmodeusError> {
     print_program_loaded_banner(input_path, machine_code.len());
     
     print_machine_state(&machine);
+    print_source_location(&machine, &debug_info);
     
-    interactive_debugger_loop(&mut machine)?;
+    interactive_debugger_loop(&mut machine, &debug_info)?;
     
     Ok(())
 }
@@ -206,6 +284,6 @@ pub fn run_mode_interactive(args: &Args) -> Result<(), AsmodeusError> {
     
     validate_file_extension(input_path, Mode::Interactive)?;
     
-    let machine_code = assemble_file(input_path, args)?;
-    run_interactive_program(&machine_code, args)
+    let (machine_code, isa, debug_info, _) = assemble_file_with_debug_info(input_path, args)?;
+    run_interactive_program(&machine_code, isa, &debug_info, args)
 }
@@ -329,6 +329,54 @@ fn test_extended_run_mode_execution() {
     assert!(stdout.contains("4"), "Expected output '4', got: {}", stdout);
 }
 
+#[test]
+fn test_bits_run_mode_execution() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_bits_run.asmod");
+    
+    // (0x0A << 4) | 0x05 = 0xA5 = 165
+    let program = r#"
+        start:
+            POB #0x0A      ; high nibble
+            SHL #4         ; shift into place (160)
+            OR #0x05       ; low nibble (165)
+            WYJSCIE        ; output result
+            STP
+    "#;
+    
+    fs::write(&input_file, program).unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", "--bits", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(output.status.success(), "Bitwise run failed: {}", String::from_utf8_lossy(&output.stderr));
+    
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("165"), "Expected output '165', got: {}", stdout);
+}
+
+#[test]
+fn test_bitwise_instructions_without_flag_fail() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_no_bits.asmod");
+    
+    fs::write(&input_file, "AND #1\nSTP\n").unwrap();
+    
+    // --extended does not enable the bitwise set
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", "--extended", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(!output.status.success(), "Run should have failed without --bits flag");
+    
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--bits") || stderr.contains("AND"), 
+            "Error message should mention bitwise instruction: {}", stderr);
+}
+
 #[test]
 fn test_extended_disassemble_mode() {
     let temp_dir = TempDir::new().unwrap();
@@ -437,3 +485,548 @@ fn test_complex_extended_program() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("1"), "Expected output '1' (120 % 7), got: {}", stdout);
 }
+
+#[test]
+fn test_isa_profile_option() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_isa.asmod");
+    
+    fs::write(&input_file, "POB #6\nMNO #7\nWYJSCIE\nSTP\n").unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", "--isa", "classic-w+muldiv", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(output.status.success(), "Run with --isa failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("42"));
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", "--isa", "mips", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(!output.status.success(), "Unknown profile should be rejected");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("mips"), "Error message should mention the profile: {}", stderr);
+}
+
+#[test]
+fn test_isa_directive_restricts_program() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_isa_directive.asmod");
+    
+    let program = r#"
+        ISA classic-w
+        start:
+            POB #1
+            WYW sub
+            STP
+        sub:
+            POW
+    "#;
+    
+    fs::write(&input_file, program).unwrap();
+    
+    // directive wins over --isa full
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", "--isa", "full", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(!output.status.success(), "WYW should not assemble under classic-w");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("WYW") && stderr.contains("classic-w"), 
+            "Error message should mention instruction and profile: {}", stderr);
+}
+
+#[test]
+fn test_isa_directive_cannot_widen_isa_option() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_isa_widen.asmod");
+    
+    fs::write(&input_file, "ISA full\nPOB #6\nMNO #7\nWYJSCIE\nSTP\n").unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", "--isa", "classic-w", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(!output.status.success(), "ISA full should not lift --isa classic-w");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("ISA full goes beyond instruction set profile 'classic-w'"), "{}", stderr);
+    
+    // without --isa the directive selects the profile
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("42"));
+}
+
+#[test]
+fn test_include_with_search_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let lib_dir = temp_dir.path().join("lib");
+    fs::create_dir(&lib_dir).unwrap();
+    let input_file = temp_dir.path().join("test_include.asmod");
+    
+    fs::write(lib_dir.join("io.asmod"), r#"
+        MAKRO wypisz wartosc
+            POB wartosc
+            WYJSCIE
+        KONM
+    "#).unwrap();
+    fs::write(&input_file, r#"
+        DOŁĄCZ "io.asmod"
+        start:
+            wypisz value
+            STP
+        value: RST 42
+    "#).unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", "-I", lib_dir.to_str().unwrap(), input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(output.status.success(), "Run failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("42"));
+    
+    // without the search path the file is not found
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Included file not found: io.asmod"));
+}
+
+#[test]
+fn test_error_in_included_file_names_file_and_line() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_include_error.asmod");
+    
+    fs::write(temp_dir.path().join("routines.asmod"), "STP\n\nPOB missing\n").unwrap();
+    fs::write(&input_file, "DOŁĄCZ \"routines.asmod\"\nSTP\n").unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "assemble", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute assemble command");
+    
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Undefined symbol: missing at line 3") && stderr.contains("routines.asmod:3:5"),
+            "Error should point into the included file: {}", stderr);
+}
+
+#[test]
+fn test_define_selects_conditional_branch() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_define.asmod");
+    
+    fs::write(&input_file, r#"
+        STAŁA VALUE = 7
+        JEŻELI VALUE > 10
+            POB #1
+        INACZEJ
+            POB #VALUE
+        KONJ
+            WYJSCIE
+            STP
+    "#).unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(output.status.success(), "Run failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("7 (0x0007)"));
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", "-D", "VALUE=0x20", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(output.status.success(), "Run failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("1 (0x0001)"));
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", "-DVALUE=70000", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid value in definition"));
+}
+
+#[test]
+fn test_assemble_writes_debug_info_for_disassembler() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_debug_info.asmod");
+    let binary_file = temp_dir.path().join("test_debug_info.bin");
+    
+    fs::write(&input_file, "start:\n    POB counter\nloop: SOB loop\ncounter: RST 3\n").unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "assemble", "-g", input_file.to_str().unwrap(), "-o", binary_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute assembler");
+    
+    assert!(output.status.success(), "Assembly failed: {}", String::from_utf8_lossy(&output.stderr));
+    let debug_file = temp_dir.path().join("test_debug_info.dbg");
+    let debug_info = fs::read_to_string(&debug_file).unwrap();
+    assert!(debug_info.contains("symbol counter label 2"));
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "disassemble", binary_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute disassembler");
+    
+    assert!(output.status.success(), "Disassembly failed: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("POB counter ;"));
+    assert!(stdout.contains("test_debug_info.asmod:3:7"));
+    assert!(stdout.contains("loop:"));
+    
+    // without an output file there is nowhere to put it
+    let output = Command::new("cargo")
+        .args(["run", "--", "assemble", "-g", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute assembler");
+    
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_assemble_listing() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_listing.asmod");
+    let binary_file = temp_dir.path().join("test_listing.bin");
+    
+    fs::write(&input_file, "STAŁA STEP = 2\nstart:\n    POB #STEP\n    DOD value\n    WYJ\n    STP\nvalue: RST 40\n").unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "assemble", "--listing", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute assembler");
+    
+    assert!(output.status.success(), "Assembly failed: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("0000  2102       immediate"));
+    assert!(stdout.contains("POB #STEP"));
+    // instructions written without an operand have no addressing mode
+    assert!(stdout.contains("0002  7800       -  "), "{}", stdout);
+    assert!(stdout.contains("0003  3800       -  "), "{}", stdout);
+    assert!(stdout.contains("0004  0028       data"));
+    assert!(stdout.contains("Symbol table:"));
+    assert!(stdout.contains("Cross-reference:"));
+    assert!(stdout.lines().any(|line| line.starts_with("value") && line.ends_with(" 4")));
+    
+    // with an output file the listing goes next to the binary
+    let output = Command::new("cargo")
+        .args(["run", "--", "assemble", "-l", input_file.to_str().unwrap(), "-o", binary_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute assembler");
+    
+    assert!(output.status.success());
+    let listing = fs::read_to_string(temp_dir.path().join("test_listing.lst")).unwrap();
+    assert!(listing.contains("start:"));
+}
+
+#[test]
+fn test_assemble_objects_and_link() {
+    let temp_dir = TempDir::new().unwrap();
+    let main_file = temp_dir.path().join("main.asmod");
+    let lib_file = temp_dir.path().join("lib.asmod");
+    let binary_file = temp_dir.path().join("program.bin");
+    
+    fs::write(&main_file, "IMPORTUJ value\nPOB value\nSTP\n").unwrap();
+    fs::write(&lib_file, "EKSPORTUJ value\nvalue: RST 42\n").unwrap();
+    
+    for source in [&main_file, &lib_file] {
+        let output = Command::new("cargo")
+            .args(["run", "--", "assemble", "-c", source.to_str().unwrap()])
+            .output()
+            .expect("Failed to execute assembler");
+        assert!(output.status.success(), "Assembly failed: {}", String::from_utf8_lossy(&output.stderr));
+    }
+    
+    let main_object = temp_dir.path().join("main.o");
+    let lib_object = temp_dir.path().join("lib.o");
+    let output = Command::new("cargo")
+        .args(["run", "--", "link", main_object.to_str().unwrap(), lib_object.to_str().unwrap(), "-o", binary_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute linker");
+    
+    assert!(output.status.success(), "Link failed: {}", String::from_utf8_lossy(&output.stderr));
+    let bytes = fs::read(&binary_file).unwrap();
+    let words: Vec<u16> = bytes.chunks_exact(2).map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]])).collect();
+    assert_eq!(words, [0xF800, 0x2003, 0x3800, 0x002A]);
+    
+    // without lib.o nothing defines value
+    let output = Command::new("cargo")
+        .args(["run", "--", "link", main_object.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute linker");
+    
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Undefined symbol: value imported by"));
+}
+
+#[test]
+fn test_assemble_sparse_and_full_images() {
+    let temp_dir = TempDir::new().unwrap();
+    let source_file = temp_dir.path().join("vector.asmod");
+    let sparse_file = temp_dir.path().join("sparse.bin");
+    let full_file = temp_dir.path().join("full.bin");
+    
+    fs::write(&source_file, "handler: STP\nSEKCJA wektor, 2000\nRST handler\n").unwrap();
+    
+    for (format, binary_file) in [("sparse", &sparse_file), ("full", &full_file)] {
+        let output = Command::new("cargo")
+            .args(["run", "--", "assemble", "--image", format, source_file.to_str().unwrap(), "-o", binary_file.to_str().unwrap()])
+            .output()
+            .expect("Failed to execute assembler");
+        assert!(output.status.success(), "Assembly failed: {}", String::from_utf8_lossy(&output.stderr));
+    }
+    
+    // ASMS, then start, length and words of each segment
+    let bytes = fs::read(&sparse_file).unwrap();
+    assert_eq!(bytes, [b'A', b'S', b'M', b'S', 0x00, 0x00, 0x01, 0x00, 0x00, 0x38, 0xD0, 0x07, 0x01, 0x00, 0x00, 0x00]);
+    
+    let bytes = fs::read(&full_file).unwrap();
+    assert_eq!(bytes.len(), 4096);
+    
+    // the disassembler expands the sparse image
+    let output = Command::new("cargo")
+        .args(["run", "--", "disassemble", sparse_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute disassembler");
+    assert!(output.status.success(), "Disassembly failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("STP"));
+}
+
+#[test]
+fn test_check_and_assemble_report_every_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_errors.asmod");
+    
+    fs::write(&input_file, "start:\n    POB vaule\n    FOO 3\nstart:\n    STP\n").unwrap();
+    
+    for mode in ["check", "assemble"] {
+        let output = Command::new("cargo")
+            .args(["run", "--", mode, input_file.to_str().unwrap()])
+            .output()
+            .expect("Failed to execute command");
+        
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("Undefined symbol: vaule") && stderr.contains("test_errors.asmod:2:9"), "{}", stderr);
+        assert!(stderr.contains("2 |     POB vaule\n  |         ^^^^^"), "Caret should mark the symbol: {}", stderr);
+        assert!(stderr.contains("Macro not found: FOO") && stderr.contains("test_errors.asmod:3:5"), "{}", stderr);
+        assert!(stderr.contains("Duplicate symbol definition: start") && stderr.contains("test_errors.asmod:4:1"), "{}", stderr);
+        assert!(stderr.contains("3 errors found"), "{}", stderr);
+    }
+    assert!(!temp_dir.path().join("test_errors.bin").exists());
+}
+
+#[test]
+fn test_errors_point_at_operand_and_overflowing_line() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_locations.asmod");
+
+    fs::write(&input_file, "STP\n    POB [3000]\n").unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("test_locations.asmod:2:9"), "{}", stderr);
+    assert!(stderr.contains("2 |     POB [3000]\n  |         ^"), "Caret should mark the operand: {}", stderr);
+
+    fs::write(&input_file, "ADRES 2047\nSTP\n    RST 1, 2\n").unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Memory overflow: program too large for available memory at line 3"), "{}", stderr);
+    assert!(stderr.contains("test_locations.asmod:3:5"), "{}", stderr);
+}
+
+#[test]
+fn test_check_reports_parser_errors_on_every_line() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_parse_errors.asmod");
+    
+    fs::write(&input_file, "POB 5\nDOD )\nSTP\nŁAD ) 1\n").unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("test_parse_errors.asmod:2:5") && stderr.contains("test_parse_errors.asmod:4:5"), "{}", stderr);
+    assert!(stderr.contains("2 errors found"), "{}", stderr);
+    
+    fs::write(&input_file, "POB 5\nSTP\n").unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("no errors found"));
+}
+
+#[test]
+fn test_check_json_diagnostics() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_json.asmod");
+    
+    fs::write(&input_file, "start:\n    POB vaule\n    DOD )\n").unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", "--message-format=json", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    
+    // parser errors stop the program from being assembled, so only the one on line 3 is reported
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "{}", stdout);
+    assert!(lines[0].starts_with(r#"{"stage":"parser","code":"E0201","severity":"error","message":"Unexpected token at line 3"#), "{}", stdout);
+    assert!(lines[0].contains(r#""line":3,"column":9,"span":{"start":9,"end":10},"suggestions":[]}"#), "{}", stdout);
+    
+    fs::write(&input_file, "start:\n    POB vaule\n    SOB start\n").unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--", "assemble", "--message-format", "json", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute assemble command");
+    
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let file = input_file.to_str().unwrap().replace('\\', "\\\\");
+    assert!(stdout.contains(&format!(
+        r#"{{"stage":"assembler","code":"E0301","severity":"error","message":"Undefined symbol: vaule at line 2","file":"{}","line":2,"column":9,"span":{{"start":9,"end":14}},"suggestions":[]}}"#,
+        file
+    )), "{}", stdout);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Undefined symbol"));
+    
+    fs::write(&input_file, "value: RST 1\n    POB vaule\n    POBB value\n").unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", "--message-format=json", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(r#""message":"Undefined symbol: vaule at line 2 (did you mean value?)""#), "{}", stdout);
+    assert!(stdout.contains(r#""line":2,"column":9,"span":{"start":9,"end":14},"suggestions":["value"]}"#), "{}", stdout);
+    assert!(stdout.contains(r#""code":"E0304","severity":"error","message":"Invalid opcode: POBB at line 3 (did you mean POB?)""#) && stdout.contains(r#""suggestions":["POB"]"#), "{}", stdout);
+    
+    fs::write(&input_file, "STP\n").unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", "--message-format=json", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_json_usage_and_runtime_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_runtime.asmod");
+    
+    fs::write(&input_file, "    POB #5\n    DZI zero\n    STP\nzero: RST 0\n").unwrap();
+    
+    // runtime errors are placed with the debug info, options take their value after '=' too
+    let output = Command::new("cargo")
+        .args(["run", "--", "run", "--message-format=json", "--isa=full", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute run command");
+    
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let file = input_file.to_str().unwrap().replace('\\', "\\\\");
+    assert!(stdout.contains(&format!(
+        r#"{{"stage":"machine","code":"E0505","severity":"error","message":"Division by zero at address 1","file":"{}","line":2,"column":5,"#,
+        file
+    )), "{}", stdout);
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", "--isa=bogus", "--message-format=json", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with(r#"{"stage":"usage","code":"E0002","severity":"error","message":"#) && stdout.contains(r#""line":null"#), "{}", stdout);
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", "--message-format", "json", "--warn=nope", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with(r#"{"stage":"usage","code":"E0002""#) && stdout.contains("Unknown lint: nope"), "{}", stdout);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Unknown lint"));
+}
+
+#[test]
+fn test_check_reports_warnings() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("test_warnings.asmod");
+    
+    fs::write(&input_file, "start:  POB #300\n        ŁAD patch\npatch:  DOD one\nunused: STP\n        POB one\none:    RST 1\n").unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    
+    // warnings do not fail the check
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Warning: Lint: Label unused is never used at line 4 [unused-label]"), "{}", stderr);
+    assert!(stderr.contains("4 | unused: STP\n  | ^^^^^^"), "{}", stderr);
+    assert!(stderr.contains("Unreachable code after STP at line 5 [unreachable-code]"), "{}", stderr);
+    assert!(stderr.contains("Execution falls from POB into data (RST) at line 5 [fall-into-data]"), "{}", stderr);
+    assert!(stderr.contains("Immediate value 300 does not fit in 8 bits, POB takes two words at line 1 [wide-immediate]"), "{}", stderr);
+    assert!(!stderr.contains("Self-modifying code"), "{}", stderr);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("no errors found, 4 warnings"));
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", "-W", "self-modifying-code", "-Aunused-label", "--message-format=json", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4, "{}", stdout);
+    assert!(lines[0].contains(r#""code":"W0305","severity":"warning""#), "{}", stdout);
+    assert!(lines[1].starts_with(r#"{"stage":"lint","code":"W0304","severity":"info","message":"Self-modifying code: ŁAD writes into the instruction at patch at line 2 [self-modifying-code]""#), "{}", stdout);
+    assert!(lines[1].contains(r#""line":2,"column":9,"span":{"start":9,"end":12}"#), "{}", stdout);
+    assert!(lines[2].contains(r#""code":"W0301","severity":"warning""#), "{}", stdout);
+    
+    let output = Command::new("cargo")
+        .args(["run", "--", "check", "-W", "unused", input_file.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute check command");
+    
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown lint: unused"));
+}
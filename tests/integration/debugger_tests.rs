@@ -220,3 +220,57 @@ fn test_debugger_state_snapshot() {
     assert_eq!(state.ws, 1500);
     assert_eq!(state.is_running, false); // default state
 }
+
+#[test]
+fn test_debugger_step_over_call() {
+    let mut machine = MachineW::new();
+    
+    let program = vec![
+        (0b10101 << 11) | 4,                // WYW 4
+        (0b00111 << 11),                    // STP
+        0, 0,                               // padding
+        (0b00100 << 11) | (0b001 << 8) | 7, // address 4: POB #7
+        (0b10110 << 11),                    // POW
+    ];
+    
+    assert!(machine.load_program(&program).is_ok());
+    machine.is_running = true;
+    
+    // whole subroutine runs, stops right after the call
+    assert!(machine.step_over().is_ok());
+    assert_eq!(machine.l, 1);
+    assert_eq!(machine.ak, 7);
+    assert!(machine.get_call_stack().is_empty());
+}
+
+#[test]
+fn test_debugger_step_over_stops_at_breakpoint_inside_call() {
+    let mut machine = MachineW::new();
+    
+    let program = vec![
+        (0b10101 << 11) | 4,                // WYW 4
+        (0b00111 << 11),                    // STP
+        0, 0,                               // padding
+        (0b00100 << 11) | (0b001 << 8) | 7, // address 4: POB #7
+        (0b10110 << 11),                    // POW
+    ];
+    
+    assert!(machine.load_program(&program).is_ok());
+    machine.is_running = true;
+    assert!(machine.add_breakpoint(5).is_ok());
+    
+    let result = machine.step_over();
+    assert!(matches!(result, Err(MachineError::BreakpointHit { address: 5 })));
+    
+    // backtrace shows the active call
+    let call_stack = machine.get_call_stack();
+    assert_eq!(call_stack.len(), 1);
+    assert_eq!(call_stack[0].target, 4);
+    assert_eq!(call_stack[0].return_address, 1);
+    
+    // finish runs to the return
+    machine.remove_breakpoint(5);
+    assert!(machine.step_out().is_ok());
+    assert_eq!(machine.l, 1);
+    assert!(machine.get_call_stack().is_empty());
+}
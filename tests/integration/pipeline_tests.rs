@@ -148,6 +148,86 @@ fn test_complete_pipeline_with_macros() {
     assert_eq!(machine.get_output_buffer(), &[40]); // 15 + 25
 }
 
+#[test]
+fn test_complete_pipeline_with_register_loop() {
+    // sum of an array walked with a register pointer
+    let source = r#"
+            SOB start
+        tab: RST 10
+            RST 20
+            RST 12
+
+        start:
+            POB #1
+            ŁAD R1          ; pointer
+            POB #3
+            ŁAD R2          ; remaining count
+        loop:
+            POB R2
+            SOZ end
+            POB R3
+            DOD [R1]
+            ŁAD R3          ; running sum
+            INR R1
+            DER R2
+            SOB loop
+        end:
+            POB R3
+            WYJSCIE
+            STP
+    "#;
+
+    let tokens = tokenize(source).unwrap();
+    let ast = parse(tokens).unwrap();
+    let machine_code = assemble_program(&ast).unwrap();
+
+    let mut machine = MachineW::new();
+    machine.load_program(&machine_code).unwrap();
+    machine.run().unwrap();
+
+    assert_eq!(machine.get_output_buffer(), &[42]);
+    assert_eq!(machine.registers[1], 4);
+    assert_eq!(machine.registers[2], 0);
+}
+
+#[test]
+fn test_multiword_addition_pipeline() {
+    // 0x0001FFFF + 0x00000001 with carry propagated by SOC
+    let source = r#"
+        start:
+            POB a_lo
+            DOD b_lo
+            ŁAD r_lo
+            POB a_hi
+            SOC carry
+            SOB add_hi
+        carry:
+            DOD #1
+        add_hi:
+            DOD b_hi
+            ŁAD r_hi
+            STP
+        a_hi: RST 1
+        a_lo: RST 0xFFFF
+        b_hi: RST 0
+        b_lo: RST 1
+        r_hi: RST 0
+        r_lo: RST 0
+    "#;
+
+    let tokens = tokenize(source).unwrap();
+    let ast = parse(tokens).unwrap();
+    let machine_code = assemble_program(&ast).unwrap();
+
+    let mut machine = MachineW::new();
+    machine.load_program(&machine_code).unwrap();
+    machine.run().unwrap();
+
+    let r_hi = machine_code.len() - 2;
+    assert_eq!(machine.memory[r_hi], 2);
+    assert_eq!(machine.memory[r_hi + 1], 0);
+}
+
 #[test]
 fn test_all_instructions_integration() {
     let source = r#"
@@ -86,3 +86,164 @@ fn test_roundtrip_complex_program() {
     assert!(disasm_text.contains("SOM"));
     assert!(disasm_text.contains("STP"));
 }
+
+#[test]
+fn test_roundtrip_far_addresses() {
+    let source = format!(r#"
+        start:
+            POB value
+            DOD #1000
+            ŁAD result
+            SOB end
+        {}
+        value: RST 7
+        result: RST 0
+        end:
+            STP
+    "#, "RST 0\n".repeat(300));
+
+    let tokens = tokenize(&source).unwrap();
+    let ast = parse(tokens).unwrap();
+    let machine_code = assemble_program(&ast).unwrap();
+
+    // every instruction above needs the far form
+    assert_eq!(machine_code.len(), 8 + 300 + 3);
+
+    let disassembled = disassemble(&machine_code).unwrap();
+    let disasm_text = disassembled.join("\n");
+
+    assert!(disasm_text.contains("DOD #1000"));
+    assert!(disasm_text.contains("SOB L_0136"));
+    assert!(disasm_text.contains("L_0136:"));
+
+    let mut machine = asmachina::MachineW::new();
+    machine.load_program(&machine_code).unwrap();
+    machine.run().unwrap();
+
+    assert_eq!(machine.memory[309], 1007);
+}
+
+#[test]
+fn test_roundtrip_relative_addressing() {
+    let source = r#"
+        start:
+            POB +count
+        loop:
+            ODE +one
+            WYJSCIE
+            SOZ +2
+            SOB +loop
+            STP
+        count: RST 3
+        one: RST 1
+    "#;
+
+    let machine_code = assemble_program(&parse(tokenize(source).unwrap()).unwrap()).unwrap();
+
+    let mut machine = asmachina::MachineW::new();
+    machine.load_program(&machine_code).unwrap();
+    machine.run().unwrap();
+    assert_eq!(machine.get_output_buffer(), &[2, 1, 0]);
+
+    // targets are shown as labels, the disassembly assembles back to the same words
+    let disassembled = disassemble(&machine_code).unwrap();
+    let disasm_text = disassembled.join("\n");
+    assert!(disasm_text.contains("SOB +L_0001"), "{}", disasm_text);
+    assert!(disasm_text.contains("SOZ +L_0005"), "{}", disasm_text);
+
+    let reassembled = assemble_program(&parse(tokenize(&disasm_text).unwrap()).unwrap()).unwrap();
+    assert_eq!(reassembled, machine_code);
+}
+
+#[test]
+fn test_roundtrip_relative_code_runs_anywhere() {
+    // the same words work at any address
+    let machine_code = assemble_program(&parse(tokenize("POB +value\nWYJSCIE\nSOB +end\nend: STP\nvalue: RST 7").unwrap()).unwrap()).unwrap();
+
+    for origin in [0usize, 100, 1500] {
+        let mut machine = asmachina::MachineW::new();
+        machine.memory[origin..origin + machine_code.len()].copy_from_slice(&machine_code);
+        machine.l = origin as u16;
+        machine.run().unwrap();
+        assert_eq!(machine.get_output_buffer(), &[7]);
+    }
+}
+
+#[test]
+fn test_roundtrip_labels_in_indirect_and_register_modes() {
+    let source = r#"
+            POB #0
+            ŁAD R1
+        loop:
+            POB tab[R1]
+            SOZ done
+            WYJSCIE
+            INR R1
+            SOB loop
+        done:
+            POB [ptr]
+            WYJSCIE
+            POB [[handle]]
+            WYJSCIE
+            POB R1[tab + 1]
+            WYJSCIE
+            STP
+        tab: RST 4, 5, 0, 6
+        ptr: RST tab + 1
+        handle: RST ptr
+    "#;
+
+    let machine_code = assemble_program(&parse(tokenize(source).unwrap()).unwrap()).unwrap();
+
+    let mut machine = asmachina::MachineW::new();
+    machine.load_program(&machine_code).unwrap();
+    machine.run().unwrap();
+    assert_eq!(machine.get_output_buffer(), &[4, 5, 5, 5, 6]);
+
+    let disassembled = disassemble(&machine_code).unwrap();
+    let disasm_text = disassembled.join("\n");
+    assert!(disasm_text.contains("POB R1["), "{}", disasm_text);
+
+    let reassembled = assemble_program(&parse(tokenize(&disasm_text).unwrap()).unwrap()).unwrap();
+    assert_eq!(reassembled, machine_code);
+}
+
+#[test]
+fn test_roundtrip_far_form_outside_classic_w() {
+    use asmachina::MachineError;
+    use asmodeus_shared::IsaProfile;
+    use hephasm::{assemble_program_with_profile, AssemblerError};
+
+    let source = "POB val\nWYJSCIE\nSTP\nADRES 300\nval: RST 7\n";
+    let ast = parse(tokenize(source).unwrap()).unwrap();
+
+    // val is past the 8 bits of a single word, classic-w has no DAL prefix to reach it
+    let error = assemble_program_with_profile(&ast, IsaProfile::CLASSIC_W).unwrap_err();
+    assert!(matches!(
+        &error,
+        AssemblerError::FarFormNotInProfile { instruction, argument: 300, profile, line: 1 } if instruction == "POB" && profile == "classic-w"
+    ), "{:?}", error);
+
+    let machine_code = assemble_program_with_profile(&ast, IsaProfile::W).unwrap();
+    assert_eq!(&machine_code[..2], [0xF801, 0x202C]);
+
+    let mut machine = asmachina::MachineW::new();
+    machine.load_program(&machine_code).unwrap();
+    machine.run().unwrap();
+    assert_eq!(machine.get_output_buffer(), &[7]);
+
+    // the same code is refused by a classic-w machine and flagged by the disassembler
+    let mut machine = asmachina::MachineW::new();
+    machine.set_isa_profile(IsaProfile::CLASSIC_W);
+    machine.load_program(&machine_code).unwrap();
+    assert!(matches!(
+        machine.run(),
+        Err(MachineError::InstructionNotInProfile { opcode: 0b11111, address: 0, .. })
+    ));
+
+    let disassembled = dismael::disassemble_with_profile(&machine_code, IsaProfile::CLASSIC_W).unwrap();
+    let far = disassembled.iter().find(|line| line.contains("POB")).unwrap();
+    assert!(far.contains("not in ISA profile classic-w"), "{}", far);
+    let disassembled = dismael::disassemble_with_profile(&machine_code, IsaProfile::W).unwrap();
+    assert!(disassembled.iter().all(|line| !line.contains("not in ISA profile")), "{:?}", disassembled);
+}